@@ -1,4 +1,7 @@
-use crate::{gui::AssetItemMessage, load_image, preview::PreviewPanel, GameEngine};
+use crate::{
+    effect::EFFECT_EXTENSION, gui::AssetItemMessage, load_image, preview::PreviewPanel,
+    sound_preview::SoundPreviewPanel, GameEngine,
+};
 use rg3d::gui::message::UiMessage;
 use rg3d::gui::widget::Widget;
 use rg3d::gui::{BuildContext, UiNode, UserInterface};
@@ -48,6 +51,7 @@ pub enum AssetKind {
     Texture,
     Sound,
     Shader,
+    Effect,
 }
 
 impl Deref for AssetItem {
@@ -171,6 +175,10 @@ impl AssetItemBuilder {
                     kind = AssetKind::Shader;
                     load_image(include_bytes!("../resources/embed/shader.png"))
                 }
+                EFFECT_EXTENSION => {
+                    kind = AssetKind::Effect;
+                    load_image(include_bytes!("../resources/embed/add_sphere_emitter.png"))
+                }
                 _ => None,
             })
             .flatten();
@@ -233,6 +241,7 @@ pub struct AssetBrowser {
     scroll_panel: Handle<UiNode>,
     selected_properties: Handle<UiNode>,
     preview: PreviewPanel,
+    sound_preview: SoundPreviewPanel,
     items: Vec<Handle<UiNode>>,
     item_to_select: Option<PathBuf>,
 }
@@ -241,6 +250,7 @@ impl AssetBrowser {
     pub fn new(engine: &mut GameEngine) -> Self {
         let preview = PreviewPanel::new(engine, 250, 250);
         let mut ctx = engine.user_interface.build_ctx();
+        let sound_preview = SoundPreviewPanel::new(&mut ctx);
 
         let content_panel;
         let folder_browser;
@@ -310,7 +320,8 @@ impl AssetBrowser {
                                 WidgetBuilder::new()
                                     .on_column(2)
                                     .with_background(Brush::Solid(Color::opaque(80, 80, 80)))
-                                    .with_child(preview.root),
+                                    .with_child(preview.root)
+                                    .with_child(sound_preview.root),
                             )
                             .build(&mut ctx),
                         ),
@@ -328,6 +339,7 @@ impl AssetBrowser {
             content_panel,
             folder_browser,
             preview,
+            sound_preview,
             scroll_panel,
             selected_properties,
             items: Default::default(),
@@ -337,6 +349,7 @@ impl AssetBrowser {
 
     pub fn clear_preview(&mut self, engine: &mut GameEngine) {
         self.preview.clear(engine);
+        self.sound_preview.clear(engine, self.preview.scene());
     }
 
     pub fn set_working_directory(&mut self, engine: &mut GameEngine, dir: &Path) {
@@ -353,6 +366,8 @@ impl AssetBrowser {
         scope_profile!();
 
         self.preview.handle_message(message, engine);
+        self.sound_preview
+            .handle_message(message, engine, self.preview.scene());
 
         let ui = &mut engine.user_interface;
 
@@ -378,11 +393,44 @@ impl AssetBrowser {
                         format!("Path: {:?}", item.path),
                     ));
 
-                    if item.kind == AssetKind::Model {
-                        let path = item.path.clone();
-                        rg3d::core::futures::executor::block_on(
-                            self.preview.load_model(&path, engine),
-                        );
+                    // Sounds have their own preview instead of the scene.
+                    let kind = item.kind;
+                    let path = item.path.clone();
+                    let is_sound = kind == AssetKind::Sound;
+                    ui.send_message(WidgetMessage::visibility(
+                        self.preview.root,
+                        MessageDirection::ToWidget,
+                        !is_sound,
+                    ));
+                    ui.send_message(WidgetMessage::visibility(
+                        self.sound_preview.root,
+                        MessageDirection::ToWidget,
+                        is_sound,
+                    ));
+
+                    match kind {
+                        AssetKind::Model => {
+                            self.sound_preview.clear(engine, self.preview.scene());
+                            rg3d::core::futures::executor::block_on(
+                                self.preview.load_model(&path, engine),
+                            );
+                        }
+                        AssetKind::Effect => {
+                            self.sound_preview.clear(engine, self.preview.scene());
+                            rg3d::core::futures::executor::block_on(
+                                self.preview.load_effect(&path, engine),
+                            );
+                        }
+                        AssetKind::Sound => {
+                            rg3d::core::futures::executor::block_on(self.sound_preview.load_sound(
+                                &path,
+                                engine,
+                                self.preview.scene(),
+                            ));
+                        }
+                        _ => {
+                            self.sound_preview.clear(engine, self.preview.scene());
+                        }
                     }
                 }
             }
@@ -403,18 +451,19 @@ impl AssetBrowser {
                     for entry in dir_iter.flatten() {
                         fn check_ext(ext: &OsStr) -> bool {
                             let ext = ext.to_string_lossy().to_lowercase();
-                            matches!(
-                                ext.as_str(),
-                                "rgs"
-                                    | "fbx"
-                                    | "jpg"
-                                    | "tga"
-                                    | "png"
-                                    | "bmp"
-                                    | "ogg"
-                                    | "wav"
-                                    | "shader"
-                            )
+                            ext == EFFECT_EXTENSION
+                                || matches!(
+                                    ext.as_str(),
+                                    "rgs"
+                                        | "fbx"
+                                        | "jpg"
+                                        | "tga"
+                                        | "png"
+                                        | "bmp"
+                                        | "ogg"
+                                        | "wav"
+                                        | "shader"
+                                )
                         }
 
                         let entry_path = entry.path();
@@ -10,6 +10,11 @@ macro_rules! define_command_stack {
             fn execute(&mut self, context: &mut $context);
             fn revert(&mut self, context: &mut $context);
             fn finalize(&mut self, _: &mut $context) {}
+            /// Returns false for commands that change only state of the editor (like selection)
+            /// and leave the scene itself untouched.
+            fn changes_scene(&self) -> bool {
+                true
+            }
         }
 
         pub struct $command_stack {
@@ -106,6 +111,20 @@ macro_rules! define_command_stack {
                 }
             }
 
+            /// Returns command that will be reverted by next undo.
+            pub fn undo_command(&self) -> Option<&dyn $command_trait> {
+                self.top
+                    .and_then(|top| self.commands.get(top))
+                    .map(|command| &**command)
+            }
+
+            /// Returns command that will be executed by next redo.
+            pub fn redo_command(&self) -> Option<&dyn $command_trait> {
+                self.commands
+                    .get(self.top.map_or(0, |top| top + 1))
+                    .map(|command| &**command)
+            }
+
             pub fn clear(&mut self, mut context: $context) {
                 for mut dropped_command in self.commands.drain(..) {
                     if self.debug {
@@ -0,0 +1,52 @@
+//! Effects are particle systems saved to standalone files, so the same effect can be reused
+//! across scenes instead of being copy-pasted between them.
+
+use rg3d::{
+    core::visitor::{Visit, Visitor},
+    engine::resource_manager::ResourceManager,
+    scene::{base::Base, node::Node, particle_system::ParticleSystem},
+};
+use std::path::Path;
+
+pub const EFFECT_EXTENSION: &str = "effect";
+
+const FIELD_NAME: &str = "Effect";
+
+/// Makes standalone copy of the particle system, without any links to the scene it lives in.
+/// Raw copy is used because it is the only way to take color over lifetime gradient along.
+pub fn make_effect(particle_system: &ParticleSystem) -> Node {
+    let mut effect = particle_system.raw_copy();
+    effect.clear_particles();
+    // Transform, tag, resource, etc. belong to the scene, the effect keeps only the name.
+    *effect = Base::default();
+    effect.set_name(particle_system.name());
+    Node::ParticleSystem(effect)
+}
+
+pub fn save_effect(particle_system: &ParticleSystem, path: &Path) -> Result<(), String> {
+    let mut effect = make_effect(particle_system);
+    let mut visitor = Visitor::new();
+    effect
+        .visit(FIELD_NAME, &mut visitor)
+        .and_then(|_| visitor.save_binary(path))
+        .map_err(|e| format!("Unable to save effect {}. Reason: {:?}", path.display(), e))
+}
+
+pub async fn load_effect(path: &Path, resource_manager: ResourceManager) -> Result<Node, String> {
+    let reason = |e| format!("Unable to load effect {}. Reason: {:?}", path.display(), e);
+
+    let mut visitor = Visitor::load_binary(path).await.map_err(reason)?;
+    let mut effect = Node::default();
+    effect.visit(FIELD_NAME, &mut visitor).map_err(reason)?;
+
+    if let Node::ParticleSystem(particle_system) = &mut effect {
+        // Only path of the texture is stored in the file, request actual resource.
+        if let Some(texture) = particle_system.texture() {
+            let texture_path = texture.state().path().to_path_buf();
+            particle_system.set_texture(Some(resource_manager.request_texture(texture_path, None)));
+        }
+        Ok(effect)
+    } else {
+        Err(format!("{} is not an effect.", path.display()))
+    }
+}
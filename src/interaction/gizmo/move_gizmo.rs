@@ -1,12 +1,13 @@
 use crate::{
     interaction::plane::PlaneKind,
     make_color_material,
+    physics::Physics,
     scene::{EditorScene, Selection},
     set_mesh_diffuse_color, GameEngine,
 };
 use rg3d::{
     core::{
-        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
+        algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3},
         color::Color,
         math::Matrix4Ext,
         pool::Handle,
@@ -300,7 +301,13 @@ impl MoveGizmo {
         Vector3::default()
     }
 
-    pub fn sync_transform(&self, scene: &mut Scene, selection: &Selection, scale: Vector3<f32>) {
+    pub fn sync_transform(
+        &self,
+        scene: &mut Scene,
+        selection: &Selection,
+        physics: &Physics,
+        scale: Vector3<f32>,
+    ) {
         let graph = &mut scene.graph;
         match selection {
             Selection::Graph(selection) => {
@@ -323,6 +330,23 @@ impl MoveGizmo {
                         .set_scale(scale);
                 }
             }
+            Selection::JointAnchor(selection) => {
+                let position = physics
+                    .body_global_transform(
+                        physics.joint_anchor_body(selection.joint, selection.anchor),
+                        graph,
+                    )
+                    .transform_point(&Point3::from(
+                        physics.joint_anchor(selection.joint, selection.anchor),
+                    ))
+                    .coords;
+                graph[self.origin]
+                    .set_visibility(true)
+                    .local_transform_mut()
+                    .set_position(position)
+                    .set_rotation(Default::default())
+                    .set_scale(scale);
+            }
             _ => (),
         }
     }
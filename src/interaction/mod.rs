@@ -25,6 +25,7 @@ pub mod plane;
 pub mod rotate_mode;
 pub mod scale_mode;
 pub mod select_mode;
+pub mod sound_radius;
 pub mod terrain;
 
 pub trait InteractionModeTrait {
@@ -1,3 +1,5 @@
+use crate::physics::{JointAnchorSelection, Physics};
+use crate::scene::commands::physics::SetJointAnchorCommand;
 use crate::scene::commands::sound::MoveSpatialSoundSourceCommand;
 use crate::scene::commands::SceneCommand;
 use crate::sound::SoundSelection;
@@ -30,10 +32,11 @@ use std::sync::mpsc::Sender;
 enum MovableEntity {
     Node(Handle<Node>),
     Sound(Handle<SoundSource>),
+    JointAnchor(JointAnchorSelection),
 }
 
 impl MovableEntity {
-    fn position(&self, scene: &Scene) -> Vector3<f32> {
+    fn position(&self, scene: &Scene, physics: &Physics) -> Vector3<f32> {
         match *self {
             MovableEntity::Node(node) => **scene.graph[node].local_transform().position(),
             MovableEntity::Sound(sound) => {
@@ -43,10 +46,13 @@ impl MovableEntity {
                     SoundSource::Spatial(spatial) => spatial.position(),
                 }
             }
+            MovableEntity::JointAnchor(selection) => {
+                physics.joint_anchor(selection.joint, selection.anchor)
+            }
         }
     }
 
-    fn set_position(&self, scene: &mut Scene, position: Vector3<f32>) {
+    fn set_position(&self, scene: &mut Scene, physics: &mut Physics, position: Vector3<f32>) {
         match *self {
             MovableEntity::Node(node) => {
                 scene.graph[node]
@@ -59,6 +65,9 @@ impl MovableEntity {
                     spatial.set_position(position);
                 }
             }
+            MovableEntity::JointAnchor(selection) => {
+                physics.set_joint_anchor(selection.joint, selection.anchor, position);
+            }
         }
     }
 }
@@ -204,6 +213,66 @@ impl MoveContext {
         }
     }
 
+    /// Anchor is moved in local space of its body, so the body plays role of a parent.
+    pub fn from_joint_anchor_selection(
+        selection: &JointAnchorSelection,
+        physics: &Physics,
+        graph: &Graph,
+        move_gizmo: &MoveGizmo,
+        camera_controller: &CameraController,
+        plane_kind: PlaneKind,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> Self {
+        let gizmo_origin = &graph[move_gizmo.origin];
+
+        let gizmo_inv_transform = gizmo_origin
+            .global_transform()
+            .try_inverse()
+            .unwrap_or_default();
+
+        let look_direction =
+            gizmo_inv_transform.transform_vector(&graph[camera_controller.camera].look_vector());
+
+        let plane = plane_kind.make_plane_from_view(look_direction);
+
+        let plane_point = plane_kind.project_point(
+            camera_controller
+                .pick_on_plane(plane, graph, mouse_pos, frame_size, gizmo_inv_transform)
+                .unwrap_or_default(),
+        );
+
+        let body_transform = physics.body_global_transform(
+            physics.joint_anchor_body(selection.joint, selection.anchor),
+            graph,
+        );
+        let local_position = physics.joint_anchor(selection.joint, selection.anchor);
+        let position = body_transform
+            .transform_point(&Point3::from(local_position))
+            .coords;
+
+        Self {
+            plane,
+            objects: vec![Entry {
+                entity: MovableEntity::JointAnchor(*selection),
+                initial_offset_gizmo_space: gizmo_inv_transform
+                    .transform_point(&Point3::from(position))
+                    .coords
+                    - plane_point
+                    - gizmo_inv_transform
+                        .transform_vector(&(position - gizmo_origin.global_position())),
+                new_local_position: local_position,
+                initial_local_position: local_position,
+                initial_parent_inv_global_transform: body_transform
+                    .try_inverse()
+                    .unwrap_or_default(),
+            }],
+            gizmo_local_transform: gizmo_origin.local_transform().matrix(),
+            gizmo_inv_transform,
+            plane_kind,
+        }
+    }
+
     pub fn update(
         &mut self,
         graph: &Graph,
@@ -326,6 +395,18 @@ impl InteractionModeTrait for MoveInteractionMode {
                             frame_size,
                         ));
                     }
+                    Selection::JointAnchor(selection) => {
+                        self.move_context = Some(MoveContext::from_joint_anchor_selection(
+                            selection,
+                            &editor_scene.physics,
+                            graph,
+                            &self.move_gizmo,
+                            &editor_scene.camera_controller,
+                            plane_kind,
+                            mouse_pos,
+                            frame_size,
+                        ));
+                    }
                     _ => {}
                 }
             }
@@ -345,7 +426,9 @@ impl InteractionModeTrait for MoveInteractionMode {
             let mut changed = false;
 
             for initial_state in move_context.objects.iter() {
-                if initial_state.entity.position(scene) != initial_state.initial_local_position {
+                if initial_state.entity.position(scene, &editor_scene.physics)
+                    != initial_state.initial_local_position
+                {
                     changed = true;
                     break;
                 }
@@ -377,6 +460,21 @@ impl InteractionModeTrait for MoveInteractionMode {
                                     }
                                 }
                             }
+                            MovableEntity::JointAnchor(selection) => {
+                                let physics = &mut editor_scene.physics;
+                                let position =
+                                    physics.joint_anchor(selection.joint, selection.anchor);
+                                // Command swaps values, so it must start from initial position.
+                                physics.set_joint_anchor(
+                                    selection.joint,
+                                    selection.anchor,
+                                    initial_state.initial_local_position,
+                                );
+                                Some(SceneCommand::new(SetJointAnchorCommand::new(
+                                    selection.joint,
+                                    (selection.anchor, position),
+                                )))
+                            }
                         })
                         .flatten()
                         .collect::<Vec<_>>(),
@@ -446,7 +544,11 @@ impl InteractionModeTrait for MoveInteractionMode {
             );
 
             for entry in move_context.objects.iter() {
-                entry.entity.set_position(scene, entry.new_local_position);
+                entry.entity.set_position(
+                    scene,
+                    &mut editor_scene.physics,
+                    entry.new_local_position,
+                );
             }
         }
     }
@@ -462,8 +564,12 @@ impl InteractionModeTrait for MoveInteractionMode {
         if !editor_scene.selection.is_empty() {
             let scale = calculate_gizmo_distance_scaling(graph, camera, self.move_gizmo.origin);
             self.move_gizmo.set_visible(graph, true);
-            self.move_gizmo
-                .sync_transform(scene, &editor_scene.selection, scale);
+            self.move_gizmo.sync_transform(
+                scene,
+                &editor_scene.selection,
+                &editor_scene.physics,
+                scale,
+            );
         } else {
             self.move_gizmo.set_visible(graph, false);
         }
@@ -28,9 +28,10 @@ use rg3d::gui::message::UiMessage;
 use rg3d::gui::{BuildContext, UiNode};
 use rg3d::{
     core::{
-        algebra::{Vector2, Vector3},
+        algebra::{Point3, Vector2, Vector3},
+        arrayvec::ArrayVec,
         color::Color,
-        math::ray::CylinderKind,
+        math::ray::{CylinderKind, Ray},
         pool::Handle,
         scope_profile,
     },
@@ -50,7 +51,13 @@ use rg3d::{
         window::{WindowBuilder, WindowTitle},
         Orientation, Thickness, VerticalAlignment,
     },
-    scene::{camera::Camera, node::Node},
+    scene::{
+        camera::Camera,
+        graph::Graph,
+        mesh::buffer::{VertexAttributeUsage, VertexReadTrait},
+        node::Node,
+        terrain::TerrainRayCastResult,
+    },
 };
 use std::{collections::HashMap, rc::Rc, sync::mpsc::Sender};
 
@@ -58,12 +65,112 @@ pub mod data_model;
 pub mod selection;
 
 const VERTEX_RADIUS: f32 = 0.2;
+// Ground probe starts a bit above a vertex, so vertices that sank below the floor
+// can be brought back too.
+const GROUND_PROBE_HEIGHT: f32 = 1.0;
+const GROUND_PROBE_DEPTH: f32 = 1000.0;
+
+/// Casts a ray straight down from a point slightly above the given position and returns
+/// a point on the closest mesh or terrain below, lifted by `offset`. Editor nodes are ignored.
+fn project_to_ground(
+    graph: &Graph,
+    editor_root: Handle<Node>,
+    position: Vector3<f32>,
+    offset: f32,
+) -> Option<Vector3<f32>> {
+    let ray = Ray::from_two_points(
+        position + Vector3::new(0.0, GROUND_PROBE_HEIGHT, 0.0),
+        position - Vector3::new(0.0, GROUND_PROBE_DEPTH, 0.0),
+    );
+
+    let mut closest: Option<Vector3<f32>> = None;
+    let mut check_point = |point: Vector3<f32>| {
+        let distance = point.metric_distance(&ray.origin);
+        if closest.map_or(true, |closest| {
+            distance < closest.metric_distance(&ray.origin)
+        }) {
+            closest = Some(point);
+        }
+    };
+
+    let mut stack = vec![graph.get_root()];
+    while let Some(handle) = stack.pop() {
+        if handle == editor_root {
+            continue;
+        }
+
+        let node = &graph[handle];
+
+        stack.extend_from_slice(node.children());
+
+        if !node.global_visibility() {
+            continue;
+        }
+
+        match node {
+            Node::Mesh(mesh) => {
+                let transform = mesh.global_transform();
+                let object_space_ray = ray.transform(transform.try_inverse().unwrap_or_default());
+
+                // Do coarse intersection test with bounding box first.
+                if object_space_ray
+                    .aabb_intersection_points(&mesh.bounding_box())
+                    .is_none()
+                {
+                    continue;
+                }
+
+                for surface in mesh.surfaces() {
+                    let data = surface.data();
+                    let data = data.read().unwrap();
+
+                    let vertex_position = |index: u32| {
+                        data.vertex_buffer
+                            .get(index as usize)
+                            .unwrap()
+                            .read_3_f32(VertexAttributeUsage::Position)
+                            .unwrap()
+                    };
+
+                    for triangle in data.geometry_buffer.triangles_ref() {
+                        let vertices = [
+                            vertex_position(triangle[0]),
+                            vertex_position(triangle[1]),
+                            vertex_position(triangle[2]),
+                        ];
+
+                        if let Some(point) = object_space_ray.triangle_intersection(&vertices) {
+                            check_point(transform.transform_point(&Point3::from(point)).coords);
+                        }
+                    }
+                }
+            }
+            Node::Terrain(terrain) => {
+                let mut intersections = ArrayVec::<TerrainRayCastResult, 128>::new();
+                terrain.raycast(ray, &mut intersections, true);
+
+                if let Some(first) = intersections.first() {
+                    check_point(
+                        terrain
+                            .global_transform()
+                            .transform_point(&Point3::from(first.position))
+                            .coords,
+                    );
+                }
+            }
+            _ => (),
+        }
+    }
+
+    closest.map(|point| Vector3::new(position.x, point.y + offset, position.z))
+}
 
 pub struct NavmeshPanel {
     pub window: Handle<UiNode>,
     navmeshes: Handle<UiNode>,
     add: Handle<UiNode>,
     connect: Handle<UiNode>,
+    project_to_ground: Handle<UiNode>,
     remove: Handle<UiNode>,
     sender: Sender<Message>,
     selected: Handle<Navmesh>,
@@ -75,6 +182,7 @@ impl NavmeshPanel {
         let remove;
         let navmeshes;
         let connect;
+        let project_to_ground;
         let window = WindowBuilder::new(WidgetBuilder::new())
             .with_title(WindowTitle::text("Navmesh"))
             .with_content(
@@ -111,6 +219,15 @@ impl NavmeshPanel {
                                         .with_text("Connect")
                                         .build(ctx);
                                         connect
+                                    })
+                                    .with_child({
+                                        project_to_ground = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Project To Ground")
+                                        .build(ctx);
+                                        project_to_ground
                                     }),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -167,6 +284,7 @@ impl NavmeshPanel {
             remove,
             navmeshes,
             connect,
+            project_to_ground,
             selected: Default::default(),
         }
     }
@@ -213,6 +331,15 @@ impl NavmeshPanel {
                 ),
             );
 
+            send_sync_message(
+                ui,
+                WidgetMessage::enabled(
+                    self.project_to_ground,
+                    MessageDirection::ToWidget,
+                    !selection.is_empty(),
+                ),
+            );
+
             editor_scene
                 .navmeshes
                 .pair_iter()
@@ -223,6 +350,11 @@ impl NavmeshPanel {
                 WidgetMessage::enabled(self.connect, MessageDirection::ToWidget, false),
             );
 
+            send_sync_message(
+                ui,
+                WidgetMessage::enabled(self.project_to_ground, MessageDirection::ToWidget, false),
+            );
+
             self.selected = Handle::NONE;
 
             None
@@ -253,6 +385,7 @@ impl NavmeshPanel {
         editor_scene: &EditorScene,
         engine: &GameEngine,
         edit_mode: &mut EditNavmeshMode,
+        settings: &Settings,
     ) {
         scope_profile!();
 
@@ -293,6 +426,43 @@ impl NavmeshPanel {
                             )))
                             .unwrap();
                     }
+                } else if message.destination() == self.project_to_ground {
+                    if let Selection::Navmesh(selection) = &editor_scene.selection {
+                        let navmesh_handle = selection.navmesh();
+                        if editor_scene.navmeshes.is_valid_handle(navmesh_handle) {
+                            let navmesh = &editor_scene.navmeshes[navmesh_handle];
+                            let graph = &engine.scenes[editor_scene.scene].graph;
+
+                            let commands = selection
+                                .clone()
+                                .unique_vertices()
+                                .iter()
+                                .filter_map(|&vertex| {
+                                    let old_position = navmesh.vertices[vertex].position;
+                                    project_to_ground(
+                                        graph,
+                                        editor_scene.root,
+                                        old_position,
+                                        settings.navmesh_settings.ground_offset,
+                                    )
+                                    .map(|new_position| {
+                                        SceneCommand::new(MoveNavmeshVertexCommand::new(
+                                            navmesh_handle,
+                                            vertex,
+                                            old_position,
+                                            new_position,
+                                        ))
+                                    })
+                                })
+                                .collect::<Vec<_>>();
+
+                            if !commands.is_empty() {
+                                self.sender
+                                    .send(Message::do_scene_command(CommandGroup::from(commands)))
+                                    .unwrap();
+                            }
+                        }
+                    }
                 }
             }
             UiMessageData::ListView(ListViewMessage::SelectionChanged(selection)) => {
@@ -536,7 +706,7 @@ impl InteractionModeTrait for EditNavmeshMode {
         editor_scene: &mut EditorScene,
         engine: &mut GameEngine,
         frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         if editor_scene.navmeshes.is_valid_handle(self.navmesh) && self.drag_context.is_some() {
             let offset = self.move_gizmo.calculate_offset(
@@ -580,18 +750,36 @@ impl InteractionModeTrait for EditNavmeshMode {
                 }
             }
 
+            let graph = &engine.scenes[editor_scene.scene].graph;
+            let editor_root = editor_scene.root;
+            let navmesh_settings = &settings.navmesh_settings;
+            let move_vertex = |vertex: &mut NavmeshVertex| {
+                vertex.position += offset;
+
+                if navmesh_settings.project_while_dragging {
+                    if let Some(position) = project_to_ground(
+                        graph,
+                        editor_root,
+                        vertex.position,
+                        navmesh_settings.ground_offset,
+                    ) {
+                        vertex.position = position;
+                    }
+                }
+            };
+
             if let Some(drag_context) = self.drag_context.as_mut() {
                 match drag_context {
                     DragContext::MoveSelection { .. } => {
                         if let Selection::Navmesh(navmesh_selection) = &mut editor_scene.selection {
                             for &vertex in navmesh_selection.unique_vertices() {
-                                navmesh.vertices[vertex].position += offset;
+                                move_vertex(&mut navmesh.vertices[vertex]);
                             }
                         }
                     }
                     DragContext::EdgeDuplication { vertices, .. } => {
                         for vertex in vertices.iter_mut() {
-                            vertex.position += offset;
+                            move_vertex(vertex);
                         }
                     }
                 }
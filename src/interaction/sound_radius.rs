@@ -0,0 +1,252 @@
+//! Spheres of radius and max distance of spatial sound sources. Selected sources also get a
+//! handle on each sphere, dragging a handle changes the value the sphere shows.
+
+use crate::{
+    scene::{
+        commands::sound::{
+            SetSpatialSoundSourceMaxDistanceCommand, SetSpatialSoundSourceRadiusCommand,
+        },
+        EditorScene, Selection,
+    },
+    GameEngine, Message,
+};
+use rg3d::{
+    core::{
+        algebra::{Matrix4, Vector2, Vector3},
+        color::Color,
+        math::plane::Plane,
+        pool::Handle,
+    },
+    scene::{node::Node, Scene},
+    sound::source::{spatial::SpatialSource, SoundSource},
+};
+use std::sync::mpsc::Sender;
+
+/// Maximum distance in pixels between cursor and a handle to pick the handle.
+const PICK_DISTANCE: f32 = 8.0;
+
+/// Max distance is infinite by default, spheres larger than this are neither drawn nor
+/// have a handle.
+const MAX_SPHERE_RADIUS: f32 = 10_000.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum SphereKind {
+    Radius,
+    MaxDistance,
+}
+
+impl SphereKind {
+    fn value(self, spatial: &SpatialSource) -> f32 {
+        match self {
+            SphereKind::Radius => spatial.radius(),
+            SphereKind::MaxDistance => spatial.max_distance(),
+        }
+    }
+
+    /// Returns value only if its sphere can be drawn.
+    fn visible_value(self, spatial: &SpatialSource) -> Option<f32> {
+        let value = self.value(spatial);
+        if value.is_finite() && value <= MAX_SPHERE_RADIUS {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn set_value(self, spatial: &mut SpatialSource, value: f32) {
+        match self {
+            SphereKind::Radius => spatial.set_radius(value),
+            SphereKind::MaxDistance => spatial.set_max_distance(value),
+        };
+    }
+}
+
+struct Drag {
+    source: Handle<SoundSource>,
+    kind: SphereKind,
+    initial_value: f32,
+}
+
+pub struct SoundRadiusHandles {
+    drag: Option<Drag>,
+    message_sender: Sender<Message>,
+}
+
+/// Handle is placed on the right side of a sphere as it seen from the camera, so it is
+/// never hidden behind the source.
+fn handle_position(spatial: &SpatialSource, radius: f32, camera: &Node) -> Vector3<f32> {
+    spatial.position()
+        + camera
+            .side_vector()
+            .try_normalize(std::f32::EPSILON)
+            .unwrap_or_else(Vector3::x)
+            .scale(radius)
+}
+
+impl SoundRadiusHandles {
+    pub fn new(message_sender: Sender<Message>) -> Self {
+        Self {
+            drag: None,
+            message_sender,
+        }
+    }
+
+    pub fn draw(&self, scene: &mut Scene, camera: Handle<Node>, selection: &Selection) {
+        let state = scene.sound_context.state();
+        let camera = &scene.graph[camera];
+
+        for (handle, source) in state.sources().pair_iter() {
+            if let SoundSource::Spatial(spatial) = source {
+                let selected = match selection {
+                    Selection::Sound(selection) => selection.sources().contains(&handle),
+                    _ => false,
+                };
+
+                for &(kind, color, highlight) in &[
+                    (
+                        SphereKind::Radius,
+                        Color::opaque(0, 120, 200),
+                        Color::opaque(0, 200, 255),
+                    ),
+                    (
+                        SphereKind::MaxDistance,
+                        Color::opaque(100, 100, 100),
+                        Color::opaque(255, 200, 0),
+                    ),
+                ] {
+                    let radius = match kind.visible_value(spatial) {
+                        Some(radius) => radius,
+                        None => continue,
+                    };
+                    let color = if selected { highlight } else { color };
+                    scene
+                        .drawing_context
+                        .draw_sphere(spatial.position(), 12, 12, radius, color);
+                    if selected {
+                        scene.drawing_context.draw_sphere(
+                            handle_position(spatial, radius, camera),
+                            6,
+                            6,
+                            0.05,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Starts dragging a handle under the cursor, returns `true` if there is such handle.
+    pub fn try_begin_drag(
+        &mut self,
+        editor_scene: &EditorScene,
+        engine: &GameEngine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> bool {
+        if let Selection::Sound(selection) = &editor_scene.selection {
+            let scene = &engine.scenes[editor_scene.scene];
+            let camera = &scene.graph[editor_scene.camera_controller.camera];
+            let state = scene.sound_context.state();
+
+            for &source in selection.sources() {
+                if !state.is_valid_handle(source) {
+                    continue;
+                }
+                if let SoundSource::Spatial(spatial) = state.source(source) {
+                    // Max distance goes first, because usually it is larger and its handle
+                    // can be overlapped by radius handle only if the values are equal.
+                    for &kind in &[SphereKind::MaxDistance, SphereKind::Radius] {
+                        let radius = match kind.visible_value(spatial) {
+                            Some(radius) => radius,
+                            None => continue,
+                        };
+                        let picked = camera
+                            .as_camera()
+                            .project(handle_position(spatial, radius, camera), frame_size)
+                            .map_or(false, |screen_pos| {
+                                screen_pos.metric_distance(&mouse_pos) <= PICK_DISTANCE
+                            });
+                        if picked {
+                            self.drag = Some(Drag {
+                                source,
+                                kind,
+                                initial_value: radius,
+                            });
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Changes dragged value directly, it will be committed as a command when drag ends.
+    /// Returns `true` if a handle is being dragged.
+    pub fn on_mouse_move(
+        &mut self,
+        editor_scene: &EditorScene,
+        engine: &GameEngine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) -> bool {
+        if let Some(drag) = self.drag.as_ref() {
+            let scene = &engine.scenes[editor_scene.scene];
+            let mut state = scene.sound_context.state();
+            if !state.is_valid_handle(drag.source) {
+                return true;
+            }
+            if let SoundSource::Spatial(spatial) = state.source_mut(drag.source) {
+                let camera_controller = &editor_scene.camera_controller;
+                let plane = Plane::from_normal_and_point(
+                    &scene.graph[camera_controller.camera].look_vector(),
+                    &spatial.position(),
+                );
+                if let Some(point) = camera_controller.pick_on_plane(
+                    plane,
+                    &scene.graph,
+                    mouse_pos,
+                    frame_size,
+                    Matrix4::identity(),
+                ) {
+                    drag.kind
+                        .set_value(spatial, point.metric_distance(&spatial.position()));
+                }
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Finishes dragging and commits new value, returns `true` if there was a drag.
+    pub fn end_drag(&mut self, editor_scene: &EditorScene, engine: &GameEngine) -> bool {
+        if let Some(drag) = self.drag.take() {
+            let scene = &engine.scenes[editor_scene.scene];
+            let mut state = scene.sound_context.state();
+            if state.is_valid_handle(drag.source) {
+                if let SoundSource::Spatial(spatial) = state.source_mut(drag.source) {
+                    let value = drag.kind.value(spatial);
+                    // Commands swap values, so they must start from initial value.
+                    drag.kind.set_value(spatial, drag.initial_value);
+                    if value != drag.initial_value {
+                        let message = match drag.kind {
+                            SphereKind::Radius => Message::do_scene_command(
+                                SetSpatialSoundSourceRadiusCommand::new(drag.source, value),
+                            ),
+                            SphereKind::MaxDistance => Message::do_scene_command(
+                                SetSpatialSoundSourceMaxDistanceCommand::new(drag.source, value),
+                            ),
+                        };
+                        self.message_sender.send(message).unwrap();
+                    }
+                }
+            }
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -6,11 +6,15 @@ use crate::{
         commands::terrain::{ModifyTerrainHeightCommand, ModifyTerrainLayerMaskCommand},
         EditorScene, Selection,
     },
+    terrain::{
+        brush::{Brush, BrushMode, BrushShape},
+        mask::copy_layer_masks,
+    },
     GameEngine, Message,
 };
 use rg3d::{
     core::{
-        algebra::{Matrix4, Point3, Vector2, Vector3},
+        algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3},
         arrayvec::ArrayVec,
         color::Color,
         math::vector_to_quat,
@@ -24,7 +28,7 @@ use rg3d::{
             MeshBuilder, RenderPath,
         },
         node::Node,
-        terrain::{Brush, BrushMode, BrushShape, Terrain, TerrainRayCastResult},
+        terrain::TerrainRayCastResult,
     },
 };
 use std::sync::{mpsc::Sender, Arc, Mutex, RwLock};
@@ -90,14 +94,6 @@ impl BrushGizmo {
     }
 }
 
-fn copy_layer_masks(terrain: &Terrain, layer: usize) -> Vec<Vec<u8>> {
-    terrain.layers()[layer]
-        .chunk_masks()
-        .iter()
-        .map(|mask| mask.data_ref().data().to_vec())
-        .collect()
-}
-
 impl InteractionModeTrait for TerrainInteractionMode {
     fn on_left_mouse_button_down(
         &mut self,
@@ -112,17 +108,34 @@ impl InteractionModeTrait for TerrainInteractionMode {
                 let handle = selection.nodes()[0];
 
                 if let Node::Terrain(terrain) = &graph[handle] {
-                    match self.brush.lock().unwrap().mode {
-                        BrushMode::ModifyHeightMap { .. } => {
+                    let mut brush = self.brush.lock().unwrap();
+
+                    // Brush center is kept up-to-date on mouse move, so it is exactly the
+                    // point under cursor.
+                    let clicked = terrain
+                        .global_transform()
+                        .try_inverse()
+                        .unwrap_or_default()
+                        .transform_point(&Point3::from(brush.center))
+                        .coords;
+
+                    match &mut brush.mode {
+                        BrushMode::DrawOnMask { layer, .. } => {
+                            self.masks = copy_layer_masks(terrain, *layer);
+                        }
+                        mode => {
+                            match mode {
+                                BrushMode::Flatten { height, .. } => *height = clicked.y,
+                                BrushMode::Ramp { begin } => *begin = clicked,
+                                _ => (),
+                            }
+
                             self.heightmaps = terrain
                                 .chunks_ref()
                                 .iter()
                                 .map(|c| c.heightmap().to_vec())
                                 .collect();
                         }
-                        BrushMode::DrawOnMask { layer, .. } => {
-                            self.masks = copy_layer_masks(terrain, layer);
-                        }
                     }
 
                     self.interacting = true;
@@ -152,25 +165,25 @@ impl InteractionModeTrait for TerrainInteractionMode {
                             .collect();
 
                         match self.brush.lock().unwrap().mode {
-                            BrushMode::ModifyHeightMap { .. } => {
+                            BrushMode::DrawOnMask { layer, .. } => {
                                 self.message_sender
                                     .send(Message::do_scene_command(
-                                        ModifyTerrainHeightCommand::new(
+                                        ModifyTerrainLayerMaskCommand::new(
                                             handle,
-                                            std::mem::take(&mut self.heightmaps),
-                                            new_heightmaps,
+                                            std::mem::take(&mut self.masks),
+                                            copy_layer_masks(terrain, layer),
+                                            layer,
                                         ),
                                     ))
                                     .unwrap();
                             }
-                            BrushMode::DrawOnMask { layer, .. } => {
+                            _ => {
                                 self.message_sender
                                     .send(Message::do_scene_command(
-                                        ModifyTerrainLayerMaskCommand::new(
+                                        ModifyTerrainHeightCommand::new(
                                             handle,
-                                            std::mem::take(&mut self.masks),
-                                            copy_layer_masks(terrain, layer),
-                                            layer,
+                                            std::mem::take(&mut self.heightmaps),
+                                            new_heightmaps,
                                         ),
                                     ))
                                     .unwrap();
@@ -216,27 +229,32 @@ impl InteractionModeTrait for TerrainInteractionMode {
                             brush.center = global_position;
 
                             let mut brush_copy = brush.clone();
-                            match &mut brush_copy.mode {
-                                BrushMode::ModifyHeightMap { amount } => {
-                                    if engine.user_interface.keyboard_modifiers().shift {
+                            if engine.user_interface.keyboard_modifiers().shift {
+                                match &mut brush_copy.mode {
+                                    BrushMode::ModifyHeightMap { amount }
+                                    | BrushMode::Noise { amount, .. } => {
                                         *amount *= -1.0;
                                     }
-                                }
-                                BrushMode::DrawOnMask { alpha, .. } => {
-                                    if engine.user_interface.keyboard_modifiers().shift {
+                                    BrushMode::DrawOnMask { alpha, .. } => {
                                         *alpha = -1.0;
                                     }
+                                    _ => (),
                                 }
                             }
 
                             if self.interacting {
-                                terrain.draw(&brush_copy);
+                                brush_copy.draw(terrain, &self.heightmaps);
                             }
 
-                            let scale = match brush.shape {
-                                BrushShape::Circle { radius } => Vector3::new(radius, 1.0, radius),
+                            let (scale, angle) = match brush.shape {
+                                BrushShape::Circle { radius } => {
+                                    (Vector3::new(radius, 1.0, radius), 0.0)
+                                }
                                 BrushShape::Rectangle { width, length } => {
-                                    Vector3::new(width, 1.0, length)
+                                    (Vector3::new(width, 1.0, length), 0.0)
+                                }
+                                BrushShape::Stamp { size, rotation, .. } => {
+                                    (Vector3::new(size, 1.0, size), rotation)
                                 }
                             };
 
@@ -244,7 +262,13 @@ impl InteractionModeTrait for TerrainInteractionMode {
                                 .local_transform_mut()
                                 .set_position(global_position)
                                 .set_scale(scale)
-                                .set_rotation(vector_to_quat(closest.normal));
+                                .set_rotation(
+                                    vector_to_quat(closest.normal)
+                                        * UnitQuaternion::from_axis_angle(
+                                            &Vector3::y_axis(),
+                                            angle,
+                                        ),
+                                );
                         }
                     }
                 }
@@ -15,6 +15,7 @@ pub mod asset;
 pub mod camera;
 pub mod command;
 pub mod configurator;
+pub mod effect;
 pub mod gui;
 pub mod interaction;
 pub mod light;
@@ -22,6 +23,7 @@ pub mod log;
 pub mod material;
 pub mod menu;
 pub mod overlay;
+pub mod particle;
 pub mod physics;
 pub mod preview;
 pub mod project_dirs;
@@ -29,6 +31,9 @@ pub mod scene;
 pub mod settings;
 pub mod sidebar;
 pub mod sound;
+pub mod sound_context;
+pub mod sound_preview;
+pub mod terrain;
 pub mod utils;
 pub mod world_outliner;
 
@@ -40,6 +45,7 @@ use crate::{
     camera::CameraController,
     command::{panel::CommandStackViewer, CommandStack},
     configurator::Configurator,
+    effect::load_effect,
     gui::make_dropdown_list_option,
     interaction::{
         move_mode::MoveInteractionMode,
@@ -50,6 +56,7 @@ use crate::{
         rotate_mode::RotateInteractionMode,
         scale_mode::ScaleInteractionMode,
         select_mode::SelectInteractionMode,
+        sound_radius::SoundRadiusHandles,
         terrain::TerrainInteractionMode,
         InteractionMode, InteractionModeKind, InteractionModeTrait,
     },
@@ -58,19 +65,24 @@ use crate::{
     material::MaterialEditor,
     menu::{Menu, MenuContext},
     overlay::OverlayRenderPass,
-    physics::Physics,
+    particle::ParticleGradients,
+    physics::{preview::PhysicsPreview, ColliderSources, Physics},
     scene::{
         commands::{
-            graph::LoadModelCommand, make_delete_selection_command, mesh::SetMeshTextureCommand,
-            particle_system::SetParticleSystemTextureCommand, sound::DeleteSoundSourceCommand,
-            sprite::SetSpriteTextureCommand, ChangeSelectionCommand, CommandGroup, PasteCommand,
-            SceneContext,
+            graph::{AddNodeCommand, LoadModelCommand, MoveNodeCommand, RotateNodeCommand},
+            make_delete_selection_command,
+            mesh::SetMeshTextureCommand,
+            particle_system::SetParticleSystemTextureCommand,
+            sound::{AddSoundSourceCommand, DeleteSoundSourceCommand, SetSoundSourceNodeCommand},
+            sprite::SetSpriteTextureCommand,
+            ChangeSelectionCommand, CommandGroup, PasteCommand, SceneContext,
         },
         EditorScene, Selection,
     },
-    settings::{Settings, SettingsSectionKind},
+    settings::{physics::PhysicsSettings, Settings, SettingsSectionKind},
     sidebar::SideBar,
-    sound::SoundPanel,
+    sound::{make_spatial_source, SoundBinder, SoundPanel},
+    sound_context::SoundContextPanel,
     utils::path_fixer::PathFixer,
     world_outliner::WorldOutliner,
 };
@@ -79,9 +91,9 @@ use rg3d::gui::message::UiMessage;
 use rg3d::gui::{BuildContext, UiNode, UserInterface};
 use rg3d::{
     core::{
-        algebra::{Point3, Vector2},
+        algebra::{Matrix4, Point3, Vector2, Vector3},
         color::Color,
-        math::aabb::AxisAlignedBoundingBox,
+        math::{aabb::AxisAlignedBoundingBox, plane::Plane},
         pool::{Handle, Pool},
         scope_profile,
     },
@@ -744,6 +756,12 @@ impl ScenePreview {
                     self.sender
                         .send(Message::OpenSettings(SettingsSectionKind::MoveModeSettings))
                         .unwrap();
+                } else if ui.is_node_child_of(message.destination(), self.navmesh_mode)
+                    && *button == MouseButton::Right
+                {
+                    self.sender
+                        .send(Message::OpenSettings(SettingsSectionKind::NavmeshSettings))
+                        .unwrap();
                 }
             }
             _ => {}
@@ -764,12 +782,23 @@ pub enum Message {
     CloseScene,
     SetInteractionMode(InteractionModeKind),
     Log(String),
-    Configure { working_directory: PathBuf },
+    Configure {
+        working_directory: PathBuf,
+    },
     NewScene,
-    Exit { force: bool },
+    Exit {
+        force: bool,
+    },
     OpenSettings(SettingsSectionKind),
     OpenMaterialEditor(Arc<Mutex<Material>>),
     ShowInAssetBrowser(PathBuf),
+    StartPhysicsPreview,
+    OpenPhysicsMaterialWindow,
+    PausePhysicsPreview,
+    StopPhysicsPreview {
+        /// Records simulated poses of nodes by a command.
+        apply_poses: bool,
+    },
 }
 
 impl Message {
@@ -818,6 +847,8 @@ struct Editor {
     save_file_selector: Handle<UiNode>,
     light_panel: LightPanel,
     sound_panel: SoundPanel,
+    sound_context_panel: SoundContextPanel,
+    sound_radius_handles: SoundRadiusHandles,
     menu: Menu,
     exit: bool,
     configurator: Configurator,
@@ -893,6 +924,7 @@ impl Editor {
         let world_outliner = WorldOutliner::new(ctx, message_sender.clone());
         let command_stack_viewer = CommandStackViewer::new(ctx, message_sender.clone());
         let sound_panel = SoundPanel::new(ctx);
+        let sound_context_panel = SoundContextPanel::new(ctx);
         let log = Log::new(ctx);
         let model_import_dialog = ModelImportDialog::new(ctx);
 
@@ -1034,6 +1066,8 @@ impl Editor {
             navmesh_panel,
             sidebar,
             sound_panel,
+            sound_context_panel,
+            sound_radius_handles: SoundRadiusHandles::new(message_sender.clone()),
             preview,
             scene: None,
             command_stack: CommandStack::new(false),
@@ -1111,12 +1145,25 @@ impl Editor {
             });
         }
 
+        let mut physics = Physics::new(&scene);
+        if let Some(path) = path.as_deref() {
+            physics.restore_collider_sources(&ColliderSources::load(path));
+        }
+
         let editor_scene = EditorScene {
             path: path.clone(),
             root,
             camera_controller,
-            physics: Physics::new(&scene),
+            physics,
+            physics_preview: None,
+            terrain_preview: None,
             navmeshes,
+            sound_binder: path.as_deref().map(SoundBinder::load).unwrap_or_default(),
+            particle_gradients: path
+                .as_deref()
+                .map(ParticleGradients::load)
+                .unwrap_or_default(),
+            audition_backup: Default::default(),
             scene: engine.scenes.add(scene),
             selection: Default::default(),
             clipboard: Default::default(),
@@ -1208,6 +1255,7 @@ impl Editor {
                 asset_window: self.asset_browser.window,
                 configurator_window: self.configurator.window,
                 light_panel: self.light_panel.window,
+                sound_context_panel: self.sound_context_panel.window,
                 log_panel: self.log.window,
                 settings: &mut self.settings,
                 path_fixer: self.path_fixer.window,
@@ -1232,13 +1280,14 @@ impl Editor {
                 } else {
                     unreachable!()
                 },
+                &self.settings,
             );
 
             self.sound_panel
                 .handle_ui_message(&self.message_sender, editor_scene, message, engine);
 
             self.sidebar
-                .handle_ui_message(message, editor_scene, engine);
+                .handle_ui_message(message, editor_scene, engine, &self.settings);
 
             self.world_outliner
                 .handle_ui_message(message, editor_scene, engine);
@@ -1246,6 +1295,13 @@ impl Editor {
             self.light_panel
                 .handle_ui_message(message, editor_scene, engine);
 
+            self.sound_context_panel.handle_ui_message(
+                message,
+                editor_scene,
+                engine,
+                &self.message_sender,
+            );
+
             self.preview
                 .handle_ui_message(message, &engine.user_interface);
 
@@ -1270,13 +1326,22 @@ impl Editor {
                         WidgetMessage::MouseDown { button, pos, .. } => {
                             engine.user_interface.capture_mouse(self.preview.frame);
                             if button == MouseButton::Left {
-                                if let Some(current_im) = self.current_interaction_mode {
-                                    let screen_bounds = engine
-                                        .user_interface
-                                        .node(self.preview.frame)
-                                        .screen_bounds();
-                                    let rel_pos = pos - screen_bounds.position;
-
+                                let screen_bounds = engine
+                                    .user_interface
+                                    .node(self.preview.frame)
+                                    .screen_bounds();
+                                let rel_pos = pos - screen_bounds.position;
+
+                                // Handles of sound spheres are above any interaction mode.
+                                let handle_picked = self.sound_radius_handles.try_begin_drag(
+                                    editor_scene,
+                                    engine,
+                                    rel_pos,
+                                    frame_size,
+                                );
+                                if let (false, Some(current_im)) =
+                                    (handle_picked, self.current_interaction_mode)
+                                {
                                     self.preview.click_mouse_pos = Some(rel_pos);
 
                                     self.interaction_modes[current_im as usize]
@@ -1295,7 +1360,11 @@ impl Editor {
 
                             if button == MouseButton::Left {
                                 self.preview.click_mouse_pos = None;
-                                if let Some(current_im) = self.current_interaction_mode {
+                                let was_dragging =
+                                    self.sound_radius_handles.end_drag(editor_scene, engine);
+                                if let (false, Some(current_im)) =
+                                    (was_dragging, self.current_interaction_mode)
+                                {
                                     let screen_bounds = engine
                                         .user_interface
                                         .node(self.preview.frame)
@@ -1326,7 +1395,15 @@ impl Editor {
                                 .screen_bounds();
                             let rel_pos = pos - screen_bounds.position;
 
-                            if let Some(current_im) = self.current_interaction_mode {
+                            let dragging = self.sound_radius_handles.on_mouse_move(
+                                editor_scene,
+                                engine,
+                                rel_pos,
+                                frame_size,
+                            );
+                            if let (false, Some(current_im)) =
+                                (dragging, self.current_interaction_mode)
+                            {
                                 self.interaction_modes[current_im as usize].on_mouse_move(
                                     mouse_offset,
                                     rel_pos,
@@ -1404,6 +1481,7 @@ impl Editor {
                                             graph_selection,
                                             editor_scene.scene,
                                             &editor_scene.physics,
+                                            &editor_scene.sound_binder,
                                             engine,
                                         );
                                     }
@@ -1434,10 +1512,20 @@ impl Editor {
                                                 let mut commands = selection
                                                     .sources()
                                                     .iter()
-                                                    .map(|&source| {
-                                                        SceneCommand::new(
-                                                            DeleteSoundSourceCommand::new(source),
-                                                        )
+                                                    .flat_map(|&source| {
+                                                        vec![
+                                                            SceneCommand::new(
+                                                                SetSoundSourceNodeCommand::new(
+                                                                    source,
+                                                                    Handle::NONE,
+                                                                ),
+                                                            ),
+                                                            SceneCommand::new(
+                                                                DeleteSoundSourceCommand::new(
+                                                                    source,
+                                                                ),
+                                                            ),
+                                                        ]
                                                     })
                                                     .collect::<Vec<_>>();
 
@@ -1471,11 +1559,80 @@ impl Editor {
                                     // This will make scenes portable.
                                     let relative_path = make_relative_path(&item.path);
 
+                                    // Dropped objects are put on a surface under cursor, or
+                                    // on the ground if there is nothing.
+                                    let frame = self.preview.frame;
+                                    let drop_position = || {
+                                        let cursor_pos = engine.user_interface.cursor_position();
+                                        let screen_bounds =
+                                            engine.user_interface.node(frame).screen_bounds();
+                                        let rel_pos = cursor_pos - screen_bounds.position;
+                                        let graph = &engine.scenes[editor_scene.scene].graph;
+                                        let camera_controller = &editor_scene.camera_controller;
+                                        camera_controller
+                                            .pick(
+                                                rel_pos,
+                                                graph,
+                                                editor_scene.root,
+                                                frame_size,
+                                                false,
+                                                |_, _| true,
+                                            )
+                                            .map(|result| result.position)
+                                            .or_else(|| {
+                                                camera_controller.pick_on_plane(
+                                                    Plane::from_normal_and_point(
+                                                        &Vector3::y(),
+                                                        &Vector3::default(),
+                                                    ),
+                                                    graph,
+                                                    rel_pos,
+                                                    frame_size,
+                                                    Matrix4::identity(),
+                                                )
+                                            })
+                                            .unwrap_or_default()
+                                    };
+
                                     match item.kind {
                                         AssetKind::Model => {
                                             self.model_import_dialog
                                                 .open(relative_path, &engine.user_interface);
                                         }
+                                        AssetKind::Sound => {
+                                            let position = drop_position();
+                                            let message = match make_spatial_source(
+                                                &relative_path,
+                                                position,
+                                                engine.resource_manager.clone(),
+                                            ) {
+                                                Ok(source) => Message::do_scene_command(
+                                                    AddSoundSourceCommand::new(source),
+                                                ),
+                                                Err(reason) => Message::Log(reason),
+                                            };
+                                            self.message_sender.send(message).unwrap();
+                                        }
+                                        AssetKind::Effect => {
+                                            let message =
+                                                match rg3d::core::futures::executor::block_on(
+                                                    load_effect(
+                                                        &relative_path,
+                                                        engine.resource_manager.clone(),
+                                                    ),
+                                                ) {
+                                                    Ok(mut effect) => {
+                                                        effect
+                                                            .local_transform_mut()
+                                                            .set_position(drop_position());
+                                                        Message::do_scene_command(
+                                                            AddNodeCommand::new(effect),
+                                                        )
+                                                    }
+                                                    Err(reason) => Message::Log(reason),
+                                                };
+                                            self.message_sender.send(message).unwrap();
+                                        }
                                         AssetKind::Texture => {
                                             let cursor_pos =
                                                 engine.user_interface.cursor_position();
@@ -1607,9 +1764,11 @@ impl Editor {
 
         if let Some(editor_scene) = self.scene.as_mut() {
             self.world_outliner.sync_to_model(editor_scene, engine);
-            self.sidebar.sync_to_model(editor_scene, engine);
+            self.sidebar
+                .sync_to_model(editor_scene, engine, &self.settings);
             self.navmesh_panel.sync_to_model(editor_scene, engine);
             self.sound_panel.sync_to_model(editor_scene, engine);
+            self.sound_context_panel.sync_to_model(editor_scene, engine);
             self.material_editor
                 .sync_to_model(&mut engine.user_interface);
             self.command_stack_viewer.sync_to_model(
@@ -1646,6 +1805,14 @@ impl Editor {
             match message {
                 Message::DoSceneCommand(command) => {
                     if let Some(editor_scene) = self.scene.as_mut() {
+                        // Commands must see original poses, not simulated ones. Selection
+                        // does not care about poses, so bodies can be inspected while simulated.
+                        if command.changes_scene() {
+                            let graph = &mut engine.scenes[editor_scene.scene].graph;
+                            editor_scene.stop_physics_preview(graph);
+                            editor_scene.discard_terrain_preview(graph);
+                        }
+
                         self.command_stack.do_command(
                             command.into_inner(),
                             SceneContext {
@@ -1660,6 +1827,15 @@ impl Editor {
                 }
                 Message::UndoSceneCommand => {
                     if let Some(editor_scene) = self.scene.as_mut() {
+                        if self
+                            .command_stack
+                            .undo_command()
+                            .map_or(false, |command| command.changes_scene())
+                        {
+                            let graph = &mut engine.scenes[editor_scene.scene].graph;
+                            editor_scene.stop_physics_preview(graph);
+                            editor_scene.discard_terrain_preview(graph);
+                        }
                         self.command_stack.undo(SceneContext {
                             scene: &mut engine.scenes[editor_scene.scene],
                             message_sender: self.message_sender.clone(),
@@ -1671,6 +1847,15 @@ impl Editor {
                 }
                 Message::RedoSceneCommand => {
                     if let Some(editor_scene) = self.scene.as_mut() {
+                        if self
+                            .command_stack
+                            .redo_command()
+                            .map_or(false, |command| command.changes_scene())
+                        {
+                            let graph = &mut engine.scenes[editor_scene.scene].graph;
+                            editor_scene.stop_physics_preview(graph);
+                            editor_scene.discard_terrain_preview(graph);
+                        }
                         self.command_stack.redo(SceneContext {
                             scene: &mut engine.scenes[editor_scene.scene],
                             message_sender: self.message_sender.clone(),
@@ -1693,6 +1878,12 @@ impl Editor {
                 }
                 Message::SelectionChanged => {
                     self.world_outliner.sync_selection = true;
+
+                    // Generated heights are bound to the terrain they were generated for.
+                    if let Some(editor_scene) = self.scene.as_mut() {
+                        editor_scene
+                            .discard_terrain_preview(&mut engine.scenes[editor_scene.scene].graph);
+                    }
                 }
                 Message::SyncToModel => {
                     needs_sync = true;
@@ -1809,6 +2000,23 @@ impl Editor {
                     self.model_import_dialog
                         .set_working_directory(engine, &working_directory);
 
+                    self.sidebar
+                        .physics_section
+                        .reload_materials(&mut engine.user_interface);
+
+                    self.settings.physics_settings = match PhysicsSettings::load() {
+                        Ok(physics_settings) => physics_settings,
+                        Err(e) => {
+                            self.message_sender
+                                .send(Message::Log(format!(
+                                    "Unable to load collision layers, fallback to default. Reason: {:?}",
+                                    e
+                                )))
+                                .unwrap();
+                            Default::default()
+                        }
+                    };
+
                     self.message_sender
                         .send(Message::Log(format!(
                             "New working directory was successfully set: {:?}",
@@ -1835,6 +2043,61 @@ impl Editor {
                 Message::ShowInAssetBrowser(path) => {
                     self.asset_browser.locate_path(&engine.user_interface, path);
                 }
+                Message::OpenPhysicsMaterialWindow => {
+                    self.sidebar
+                        .physics_section
+                        .material_window
+                        .open(&engine.user_interface);
+                }
+                Message::StartPhysicsPreview => {
+                    if let Some(editor_scene) = self.scene.as_mut() {
+                        if let Some(preview) = editor_scene.physics_preview.as_mut() {
+                            preview.paused = false;
+                        } else {
+                            editor_scene.physics_preview = Some(PhysicsPreview::new(
+                                &editor_scene.physics,
+                                &engine.scenes[editor_scene.scene].graph,
+                            ));
+                        }
+                    }
+                }
+                Message::PausePhysicsPreview => {
+                    if let Some(preview) =
+                        self.scene.as_mut().and_then(|s| s.physics_preview.as_mut())
+                    {
+                        preview.paused = true;
+                    }
+                }
+                Message::StopPhysicsPreview { apply_poses } => {
+                    if let Some(editor_scene) = self.scene.as_mut() {
+                        let graph = &mut engine.scenes[editor_scene.scene].graph;
+                        let simulated_poses = editor_scene.stop_physics_preview(graph);
+
+                        if apply_poses {
+                            let mut commands = Vec::new();
+                            for pose in simulated_poses {
+                                let transform = graph[pose.node].local_transform();
+                                commands.push(SceneCommand::new(MoveNodeCommand::new(
+                                    pose.node,
+                                    **transform.position(),
+                                    pose.position,
+                                )));
+                                commands.push(SceneCommand::new(RotateNodeCommand::new(
+                                    pose.node,
+                                    **transform.rotation(),
+                                    pose.rotation,
+                                )));
+                            }
+                            if !commands.is_empty() {
+                                self.message_sender
+                                    .send(Message::do_scene_command(CommandGroup::from(commands)))
+                                    .unwrap();
+                            }
+                        }
+
+                        needs_sync = true;
+                    }
+                }
             }
         }
 
@@ -1997,16 +2260,47 @@ impl Editor {
                 self.settings.debugging.show_bounds,
             );
 
+            self.sound_radius_handles.draw(
+                scene,
+                editor_scene.camera_controller.camera,
+                &editor_scene.selection,
+            );
+
             if self.settings.debugging.show_physics {
-                editor_scene
-                    .physics
-                    .draw(&mut scene.drawing_context, &scene.graph);
+                let highlighted_joint = match &editor_scene.selection {
+                    Selection::Graph(selection) if selection.is_single_selection() => editor_scene
+                        .physics
+                        .binder
+                        .value_of(&selection.nodes()[0])
+                        .map_or(Handle::NONE, |&body| editor_scene.physics.find_joint(body)),
+                    Selection::JointAnchor(selection) => selection.joint,
+                    _ => Handle::NONE,
+                };
+
+                editor_scene.physics.draw(
+                    &mut scene.drawing_context,
+                    &scene.graph,
+                    highlighted_joint,
+                );
             }
 
+            if let Some(preview) = editor_scene.physics_preview.as_mut() {
+                preview.update(&mut scene.graph);
+            }
+
+            self.sidebar
+                .physics_section
+                .convex_generator
+                .draw(&mut scene.drawing_context, &scene.graph);
+
             let graph = &mut scene.graph;
 
             editor_scene.camera_controller.update(graph, dt);
 
+            editor_scene.sound_binder.update(scene);
+
+            self.sound_panel.update(editor_scene, scene);
+
             if let Some(mode) = self.current_interaction_mode {
                 self.interaction_modes[mode as usize].update(
                     editor_scene,
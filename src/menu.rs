@@ -89,12 +89,19 @@ pub struct Menu {
     open_settings: Handle<UiNode>,
     configure: Handle<UiNode>,
     light_panel: Handle<UiNode>,
+    sound_context_panel: Handle<UiNode>,
     pub settings: SettingsWindow,
     configure_message: Handle<UiNode>,
     log_panel: Handle<UiNode>,
     create: Handle<UiNode>,
     edit: Handle<UiNode>,
     open_path_fixer: Handle<UiNode>,
+    physics: Handle<UiNode>,
+    play_physics: Handle<UiNode>,
+    pause_physics: Handle<UiNode>,
+    stop_physics: Handle<UiNode>,
+    apply_physics: Handle<UiNode>,
+    physics_materials: Handle<UiNode>,
 }
 
 pub struct MenuContext<'a, 'b> {
@@ -105,6 +112,7 @@ pub struct MenuContext<'a, 'b> {
     pub asset_window: Handle<UiNode>,
     pub configurator_window: Handle<UiNode>,
     pub light_panel: Handle<UiNode>,
+    pub sound_context_panel: Handle<UiNode>,
     pub log_panel: Handle<UiNode>,
     pub settings: &'b mut Settings,
     pub path_fixer: Handle<UiNode>,
@@ -155,11 +163,17 @@ impl Menu {
         let open_settings;
         let configure;
         let light_panel;
+        let sound_context_panel;
         let log_panel;
         let create_pivot;
         let create_sound_source;
         let create_spatial_sound_source;
         let open_path_fixer;
+        let play_physics;
+        let pause_physics;
+        let stop_physics;
+        let apply_physics;
+        let physics_materials;
         let ctx = &mut engine.user_interface.build_ctx();
         let configure_message = MessageBoxBuilder::new(
             WindowBuilder::new(WidgetBuilder::new().with_width(250.0).with_height(150.0))
@@ -333,6 +347,48 @@ impl Menu {
             ])
             .build(ctx);
 
+        let physics =
+            MenuItemBuilder::new(WidgetBuilder::new().with_margin(Thickness::right(10.0)))
+                .with_content(MenuItemContent::text_with_shortcut("Physics", ""))
+                .with_items(vec![
+                    {
+                        play_physics =
+                            MenuItemBuilder::new(WidgetBuilder::new().with_min_size(min_size))
+                                .with_content(MenuItemContent::text("Play"))
+                                .build(ctx);
+                        play_physics
+                    },
+                    {
+                        pause_physics =
+                            MenuItemBuilder::new(WidgetBuilder::new().with_min_size(min_size))
+                                .with_content(MenuItemContent::text("Pause"))
+                                .build(ctx);
+                        pause_physics
+                    },
+                    {
+                        stop_physics =
+                            MenuItemBuilder::new(WidgetBuilder::new().with_min_size(min_size))
+                                .with_content(MenuItemContent::text("Stop"))
+                                .build(ctx);
+                        stop_physics
+                    },
+                    {
+                        apply_physics =
+                            MenuItemBuilder::new(WidgetBuilder::new().with_min_size(min_size))
+                                .with_content(MenuItemContent::text("Apply Simulated Poses"))
+                                .build(ctx);
+                        apply_physics
+                    },
+                    {
+                        physics_materials =
+                            MenuItemBuilder::new(WidgetBuilder::new().with_min_size(min_size))
+                                .with_content(MenuItemContent::text("Apply Material Preset..."))
+                                .build(ctx);
+                        physics_materials
+                    },
+                ])
+                .build(ctx);
+
         let menu = MenuBuilder::new(WidgetBuilder::new().on_row(0))
             .with_items(vec![
                 MenuItemBuilder::new(WidgetBuilder::new().with_margin(Thickness::right(10.0)))
@@ -415,6 +471,7 @@ impl Menu {
                     .build(ctx),
                 edit,
                 create,
+                physics,
                 MenuItemBuilder::new(WidgetBuilder::new().with_margin(Thickness::right(10.0)))
                     .with_content(MenuItemContent::text_with_shortcut("View", ""))
                     .with_items(vec![
@@ -446,6 +503,13 @@ impl Menu {
                                     .build(ctx);
                             light_panel
                         },
+                        {
+                            sound_context_panel =
+                                MenuItemBuilder::new(WidgetBuilder::new().with_min_size(min_size))
+                                    .with_content(MenuItemContent::text("Sound Context"))
+                                    .build(ctx);
+                            sound_context_panel
+                        },
                         {
                             log_panel =
                                 MenuItemBuilder::new(WidgetBuilder::new().with_min_size(min_size))
@@ -510,6 +574,7 @@ impl Menu {
             configure,
             configure_message,
             light_panel,
+            sound_context_panel,
             copy,
             paste,
             log_panel,
@@ -521,6 +586,12 @@ impl Menu {
             edit,
             open_path_fixer,
             create_decal,
+            physics,
+            play_physics,
+            pause_physics,
+            stop_physics,
+            apply_physics,
+            physics_materials,
         }
     }
 
@@ -546,6 +617,7 @@ impl Menu {
             self.save_as,
             self.create,
             self.edit,
+            self.physics,
         ]
         .iter()
         {
@@ -784,6 +856,7 @@ impl Menu {
                                 selection,
                                 editor_scene.scene,
                                 &editor_scene.physics,
+                                &editor_scene.sound_binder,
                                 ctx.engine,
                             );
                         }
@@ -810,6 +883,12 @@ impl Menu {
                     switch_window_state(ctx.asset_window, &mut ctx.engine.user_interface, false);
                 } else if message.destination() == self.light_panel {
                     switch_window_state(ctx.light_panel, &mut ctx.engine.user_interface, true);
+                } else if message.destination() == self.sound_context_panel {
+                    switch_window_state(
+                        ctx.sound_context_panel,
+                        &mut ctx.engine.user_interface,
+                        true,
+                    );
                 } else if message.destination() == self.world_outliner {
                     switch_window_state(
                         ctx.world_outliner_window,
@@ -823,6 +902,26 @@ impl Menu {
                 } else if message.destination() == self.open_settings {
                     self.settings
                         .open(&ctx.engine.user_interface, ctx.settings, None);
+                } else if message.destination() == self.play_physics {
+                    self.message_sender
+                        .send(Message::StartPhysicsPreview)
+                        .unwrap();
+                } else if message.destination() == self.pause_physics {
+                    self.message_sender
+                        .send(Message::PausePhysicsPreview)
+                        .unwrap();
+                } else if message.destination() == self.stop_physics {
+                    self.message_sender
+                        .send(Message::StopPhysicsPreview { apply_poses: false })
+                        .unwrap();
+                } else if message.destination() == self.apply_physics {
+                    self.message_sender
+                        .send(Message::StopPhysicsPreview { apply_poses: true })
+                        .unwrap();
+                } else if message.destination() == self.physics_materials {
+                    self.message_sender
+                        .send(Message::OpenPhysicsMaterialWindow)
+                        .unwrap();
                 } else if message.destination() == self.open_path_fixer {
                     ctx.engine
                         .user_interface
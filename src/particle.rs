@@ -0,0 +1,91 @@
+//! Engine allows to set color over lifetime gradient of a particle system, but not to read it
+//! back. Editor keeps its own copy of every gradient it has set and saves it next to the scene,
+//! the same way as sound binder. Gradients that were set outside of the editor still work, but
+//! the editor does not know them until they are edited.
+
+use rg3d::{
+    core::{
+        color::Color,
+        color_gradient::{ColorGradient, GradientPoint},
+        futures::executor::block_on,
+        pool::Handle,
+        visitor::{Visit, VisitResult, Visitor},
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::{collections::HashMap, path::Path};
+
+#[derive(Default, Debug, Clone)]
+pub struct ParticleGradients {
+    map: HashMap<Handle<Node>, ColorGradient>,
+}
+
+/// Particles of a particle system without gradient are white, so a white gradient stands in
+/// for a missing one - engine has no way to remove a gradient.
+fn white_gradient() -> ColorGradient {
+    let mut gradient = ColorGradient::new();
+    gradient.add_point(GradientPoint::new(0.0, Color::WHITE));
+    gradient
+}
+
+impl ParticleGradients {
+    /// Name of the field of scene file.
+    pub const FIELD_NAME: &'static str = "ParticleGradients";
+
+    /// Loads gradients saved with a scene. Scenes without the field have no known gradients.
+    pub fn load(path: &Path) -> Self {
+        let mut gradients = Self::default();
+        if let Ok(mut visitor) = block_on(Visitor::load_binary(path)) {
+            if gradients.visit(Self::FIELD_NAME, &mut visitor).is_err() {
+                gradients = Self::default();
+            }
+        }
+        gradients
+    }
+
+    pub fn get(&self, particle_system: Handle<Node>) -> Option<&ColorGradient> {
+        self.map.get(&particle_system)
+    }
+
+    /// Sets gradient of the particle system, or removes it if the gradient is none. Returns
+    /// previous gradient of the particle system.
+    pub fn set(
+        &mut self,
+        graph: &mut Graph,
+        particle_system: Handle<Node>,
+        gradient: Option<ColorGradient>,
+    ) -> Option<ColorGradient> {
+        graph[particle_system]
+            .as_particle_system_mut()
+            .set_color_over_lifetime_gradient(gradient.clone().unwrap_or_else(white_gradient));
+
+        match gradient {
+            Some(gradient) => self.map.insert(particle_system, gradient),
+            None => self.map.remove(&particle_system),
+        }
+    }
+
+    /// Makes gradients with nodes replaced by their pairs from the mapping. Gradients of nodes
+    /// that are not in the mapping are dropped.
+    pub fn remap(&self, old_to_new: &HashMap<Handle<Node>, Handle<Node>>) -> Self {
+        Self {
+            map: self
+                .map
+                .iter()
+                .filter_map(|(node, gradient)| {
+                    old_to_new.get(node).map(|&node| (node, gradient.clone()))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Visit for ParticleGradients {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        visitor.enter_region(name)?;
+
+        self.map.visit("Map", visitor)?;
+
+        visitor.leave_region()
+    }
+}
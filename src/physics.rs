@@ -2,14 +2,17 @@ use rg3d::{
     core::{
         algebra::{Isometry3, Matrix4, Point3, Translation, Translation3, Vector3},
         color::Color,
+        futures::executor::block_on,
         math::aabb::AxisAlignedBoundingBox,
         pool::{ErasedHandle, Handle, Pool},
         uuid::Uuid,
+        visitor::{Visit, VisitResult, Visitor},
         BiDirHashMap,
     },
     physics3d::{
         desc::{
-            ColliderDesc, ColliderShapeDesc, JointDesc, JointParamsDesc, PhysicsDesc, RigidBodyDesc,
+            ColliderDesc, ColliderShapeDesc, JointDesc, JointParamsDesc, PhysicsDesc,
+            RigidBodyDesc, RoundCylinderDesc,
         },
         ColliderHandle, JointHandle, RigidBodyHandle,
     },
@@ -24,12 +27,75 @@ use rg3d::{
         Scene,
     },
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
+
+pub mod convex;
+pub mod material;
+pub mod preview;
 
 pub type RigidBody = RigidBodyDesc<ErasedHandle>;
 pub type Collider = ColliderDesc<ErasedHandle>;
 pub type Joint = JointDesc<ErasedHandle>;
 
+/// Each joint has two anchors, first one is attached to the first body of the joint and
+/// second one - to the connected body.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JointAnchor {
+    First,
+    Second,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct JointAnchorSelection {
+    pub joint: Handle<Joint>,
+    pub anchor: JointAnchor,
+}
+
+/// Sources of trimesh and height field colliders in the form they are saved in scene file.
+/// Engine has no such binding, so it is saved next to the scene the same way as sound binder.
+#[derive(Default, Debug, Clone)]
+pub struct ColliderSources {
+    map: HashMap<ColliderHandle, Handle<Node>>,
+}
+
+impl ColliderSources {
+    /// Name of the field of scene file.
+    pub const FIELD_NAME: &'static str = "ColliderSources";
+
+    /// Loads sources saved with a scene. Scenes without the field have no sources.
+    pub fn load(path: &Path) -> Self {
+        let mut sources = Self::default();
+        if let Ok(mut visitor) = block_on(Visitor::load_binary(path)) {
+            if sources.visit(Self::FIELD_NAME, &mut visitor).is_err() {
+                sources = Self::default();
+            }
+        }
+        sources
+    }
+
+    /// Makes sources with nodes replaced by their pairs from the mapping. Colliders with
+    /// sources that are not in the mapping are dropped.
+    pub fn remap(&self, old_to_new: &HashMap<Handle<Node>, Handle<Node>>) -> Self {
+        Self {
+            map: self
+                .map
+                .iter()
+                .filter_map(|(&collider, node)| old_to_new.get(node).map(|&node| (collider, node)))
+                .collect(),
+        }
+    }
+}
+
+impl Visit for ColliderSources {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        visitor.enter_region(name)?;
+
+        self.map.visit("Map", visitor)?;
+
+        visitor.leave_region()
+    }
+}
+
 /// Editor uses its own data model for physics because engine's is not suitable
 /// for editor. Algorithm is very simple:
 /// 1) After scene is loaded - convert its physics to editor's
@@ -47,6 +113,9 @@ pub struct Physics {
     pub colliders: Pool<Collider>,
     pub joints: Pool<Joint>,
     pub binder: BiDirHashMap<Handle<Node>, Handle<RigidBody>>,
+    /// Nodes trimesh and height field colliders take their geometry from. Colliders without
+    /// a source take geometry from the node bound to their body.
+    pub collider_sources: HashMap<Handle<Collider>, Handle<Node>>,
 
     body_handle_map: HashMap<Handle<RigidBody>, RigidBodyHandle>,
     collider_handle_map: HashMap<Handle<Collider>, ColliderHandle>,
@@ -105,7 +174,7 @@ impl Physics {
                 mass: b.mass(),
                 x_rotation_locked: rotation_locked[0],
                 y_rotation_locked: rotation_locked[1],
-                z_rotation_locked: rotation_locked[1],
+                z_rotation_locked: rotation_locked[2],
                 translation_locked: b.is_translation_locked(),
             });
 
@@ -214,17 +283,61 @@ impl Physics {
             colliders,
             joints,
             binder,
+            collider_sources: Default::default(),
             body_handle_map,
             collider_handle_map,
             joint_handle_map,
         }
     }
 
+    /// Puts sources saved with the scene back to colliders they belong to.
+    pub fn restore_collider_sources(&mut self, sources: &ColliderSources) {
+        for (&collider, engine_handle) in self.collider_handle_map.iter() {
+            if let Some(&node) = sources.map.get(engine_handle) {
+                self.collider_sources.insert(collider, node);
+            }
+        }
+    }
+
+    /// Returns node the collider takes its geometry from.
+    pub fn collider_source(&self, collider: Handle<Collider>) -> Handle<Node> {
+        self.collider_sources
+            .get(&collider)
+            .cloned()
+            .or_else(|| {
+                self.binder
+                    .key_of(&self.colliders[collider].parent.into())
+                    .cloned()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Sets source of the collider, or resets it to the bound node if the node is none.
+    /// Returns previous source of the collider.
+    pub fn set_collider_source(
+        &mut self,
+        collider: Handle<Collider>,
+        node: Handle<Node>,
+    ) -> Handle<Node> {
+        if node.is_some() {
+            self.collider_sources.insert(collider, node)
+        } else {
+            self.collider_sources.remove(&collider)
+        }
+        .unwrap_or_default()
+    }
+
     pub fn unbind_by_body(&mut self, body: Handle<RigidBody>) -> Handle<Node> {
         self.binder.remove_by_value(&body).unwrap_or_default()
     }
 
-    pub fn generate_engine_desc(&self) -> (PhysicsDesc, HashMap<Handle<Node>, RigidBodyHandle>) {
+    pub fn generate_engine_desc(
+        &self,
+    ) -> (
+        PhysicsDesc,
+        HashMap<Handle<Node>, RigidBodyHandle>,
+        ColliderSources,
+    ) {
         let mut editor_body_handle_to_engine_map = BiDirHashMap::default();
         let mut engine_body_handle_rapier_map = BiDirHashMap::default();
         for (i, (handle, _)) in self.bodies.pair_iter().enumerate() {
@@ -325,6 +438,18 @@ impl Physics {
                 .collect();
         }
 
+        let collider_sources = ColliderSources {
+            map: self
+                .collider_sources
+                .iter()
+                .filter_map(|(collider, &node)| {
+                    editor_collider_handle_to_engine_map
+                        .get(collider)
+                        .map(|&collider| (collider, node))
+                })
+                .collect(),
+        };
+
         let mut binder = HashMap::new();
 
         for (&node, body) in self.binder.forward_map().iter() {
@@ -373,6 +498,7 @@ impl Physics {
                 integration_parameters: Default::default(),
             },
             binder,
+            collider_sources,
         )
     }
 
@@ -386,7 +512,12 @@ impl Physics {
         Handle::NONE
     }
 
-    pub fn draw(&self, context: &mut SceneDrawingContext, graph: &Graph) {
+    pub fn draw(
+        &self,
+        context: &mut SceneDrawingContext,
+        graph: &Graph,
+        highlighted_joint: Handle<Joint>,
+    ) {
         for body in self.bodies.iter() {
             context.draw_transform(
                 Isometry3 {
@@ -401,7 +532,7 @@ impl Physics {
 
         let color = Color::opaque(255, 0, 255);
 
-        for collider in self.colliders.iter() {
+        for (collider_handle, collider) in self.colliders.pair_iter() {
             let parent = collider.parent.into();
             let body = self.bodies.borrow(parent);
 
@@ -435,7 +566,9 @@ impl Physics {
 
             match &collider.shape {
                 ColliderShapeDesc::Ball(ball) => {
-                    context.draw_sphere(body.position, 10, 10, ball.radius, color);
+                    // Each collider of a compound body has its own offset from the body.
+                    let center = transform.transform_point(&Point3::origin()).coords;
+                    context.draw_sphere(center, 10, 10, ball.radius, color);
                 }
                 ColliderShapeDesc::Cylinder(cylinder) => {
                     context.draw_cylinder(
@@ -448,14 +581,7 @@ impl Physics {
                     );
                 }
                 ColliderShapeDesc::RoundCylinder(round_cylinder) => {
-                    context.draw_cylinder(
-                        10,
-                        round_cylinder.radius,
-                        round_cylinder.half_height * 2.0,
-                        false,
-                        transform,
-                        color,
-                    );
+                    draw_round_cylinder(context, round_cylinder, transform, color);
                 }
                 ColliderShapeDesc::Cone(cone) => {
                     context.draw_cone(10, cone.radius, cone.half_height * 2.0, transform, color);
@@ -480,16 +606,22 @@ impl Physics {
                 ),
                 ColliderShapeDesc::Segment(segment) => {
                     context.add_line(Line {
-                        begin: segment.begin,
-                        end: segment.end,
+                        begin: transform
+                            .transform_point(&Point3::from(segment.begin))
+                            .coords,
+                        end: transform.transform_point(&Point3::from(segment.end)).coords,
                         color,
                     });
                 }
                 ColliderShapeDesc::Triangle(triangle) => {
-                    context.draw_triangle(triangle.a, triangle.b, triangle.c, color);
+                    let a = transform.transform_point(&Point3::from(triangle.a));
+                    let b = transform.transform_point(&Point3::from(triangle.b));
+                    let c = transform.transform_point(&Point3::from(triangle.c));
+                    context.draw_triangle(a.coords, b.coords, c.coords, color);
                 }
                 ColliderShapeDesc::Trimesh(_) => {
-                    if let Some(node) = self.binder.key_of(&parent).cloned() {
+                    let node = self.collider_source(collider_handle);
+                    if graph.is_valid_handle(node) {
                         let mut stack = vec![node];
                         while let Some(handle) = stack.pop() {
                             let node = &graph[handle];
@@ -508,7 +640,8 @@ impl Physics {
                     }
                 }
                 ColliderShapeDesc::Heightfield(_) => {
-                    if let Some(node) = self.binder.key_of(&parent).cloned() {
+                    let node = self.collider_source(collider_handle);
+                    if graph.is_valid_handle(node) {
                         if let Node::Terrain(terrain) = &graph[node] {
                             let transform = terrain.global_transform();
                             for chunk in terrain.chunks_ref() {
@@ -522,39 +655,331 @@ impl Physics {
             }
         }
 
-        for joint in self.joints.iter() {
-            match &joint.params {
-                JointParamsDesc::BallJoint(ball) => {
-                    let mut draw_anchor = |local_anchor: Vector3<f32>| -> Option<Vector3<f32>> {
-                        if joint.body1.is_some() {
-                            let frame_of_reference =
-                                self.bodies[joint.body1.into()].local_transform();
-
-                            let anchor = frame_of_reference
-                                .transform_point(&Point3::from(local_anchor))
-                                .coords;
-                            context.draw_sphere(anchor, 6, 6, 0.2, Color::BLUE);
-                            Some(anchor)
-                        } else {
-                            None
-                        }
-                    };
+        for (handle, joint) in self.joints.pair_iter() {
+            self.draw_joint(context, graph, joint, handle == highlighted_joint);
+        }
+    }
 
-                    let anchor1 = draw_anchor(ball.local_anchor1);
-                    let anchor2 = draw_anchor(-ball.local_anchor2);
+    /// Returns global transform of a body. Transform of a bound node is preferred, because
+    /// the node can be moved without the body (by the physics preview for instance).
+    pub fn body_global_transform(&self, body: Handle<RigidBody>, graph: &Graph) -> Matrix4<f32> {
+        if let Some(&node) = self.binder.key_of(&body) {
+            if graph.is_valid_handle(node) {
+                let (rotation, position) = graph.isometric_global_rotation_position(node);
+                return Isometry3 {
+                    rotation,
+                    translation: Translation { vector: position },
+                }
+                .to_homogeneous();
+            }
+        }
+        self.bodies[body].local_transform().to_homogeneous()
+    }
 
-                    if let (Some(anchor1), Some(anchor2)) = (anchor1, anchor2) {
+    /// Returns body of a joint to which given anchor is attached.
+    pub fn joint_anchor_body(
+        &self,
+        joint: Handle<Joint>,
+        anchor: JointAnchor,
+    ) -> Handle<RigidBody> {
+        let joint = &self.joints[joint];
+        match anchor {
+            JointAnchor::First => joint.body1.into(),
+            JointAnchor::Second => joint.body2.into(),
+        }
+    }
+
+    /// Returns position of a joint anchor in local space of its body.
+    pub fn joint_anchor(&self, joint: Handle<Joint>, anchor: JointAnchor) -> Vector3<f32> {
+        joint_anchor(&self.joints[joint].params, anchor)
+    }
+
+    pub fn set_joint_anchor(
+        &mut self,
+        joint: Handle<Joint>,
+        anchor: JointAnchor,
+        position: Vector3<f32>,
+    ) {
+        let params = &mut self.joints[joint].params;
+        let anchor = match (params, anchor) {
+            (JointParamsDesc::BallJoint(ball), JointAnchor::First) => &mut ball.local_anchor1,
+            (JointParamsDesc::BallJoint(ball), JointAnchor::Second) => &mut ball.local_anchor2,
+            (JointParamsDesc::FixedJoint(fixed), JointAnchor::First) => {
+                &mut fixed.local_anchor1_translation
+            }
+            (JointParamsDesc::FixedJoint(fixed), JointAnchor::Second) => {
+                &mut fixed.local_anchor2_translation
+            }
+            (JointParamsDesc::PrismaticJoint(prismatic), JointAnchor::First) => {
+                &mut prismatic.local_anchor1
+            }
+            (JointParamsDesc::PrismaticJoint(prismatic), JointAnchor::Second) => {
+                &mut prismatic.local_anchor2
+            }
+            (JointParamsDesc::RevoluteJoint(revolute), JointAnchor::First) => {
+                &mut revolute.local_anchor1
+            }
+            (JointParamsDesc::RevoluteJoint(revolute), JointAnchor::Second) => {
+                &mut revolute.local_anchor2
+            }
+        };
+        *anchor = position;
+    }
+
+    fn draw_joint(
+        &self,
+        context: &mut SceneDrawingContext,
+        graph: &Graph,
+        joint: &Joint,
+        highlighted: bool,
+    ) {
+        let color = if highlighted {
+            Color::opaque(255, 255, 0)
+        } else {
+            Color::opaque(0, 160, 255)
+        };
+
+        let mut anchors = Vec::new();
+
+        for &anchor in &[JointAnchor::First, JointAnchor::Second] {
+            let body = match anchor {
+                JointAnchor::First => joint.body1,
+                JointAnchor::Second => joint.body2,
+            };
+            if body.is_none() || !self.bodies.is_valid_handle(body.into()) {
+                continue;
+            }
+
+            let transform = self.body_global_transform(body.into(), graph);
+            let origin = transform.transform_point(&Point3::origin()).coords;
+            let position = transform
+                .transform_point(&Point3::from(joint_anchor(&joint.params, anchor)))
+                .coords;
+
+            // Anchor is connected with its body to make clear to which body it belongs.
+            context.draw_sphere(position, 6, 6, 0.05, color);
+            context.add_line(Line {
+                begin: origin,
+                end: position,
+                color,
+            });
+
+            let axis = match (&joint.params, anchor) {
+                (JointParamsDesc::PrismaticJoint(prismatic), JointAnchor::First) => {
+                    Some(prismatic.local_axis1)
+                }
+                (JointParamsDesc::PrismaticJoint(prismatic), JointAnchor::Second) => {
+                    Some(prismatic.local_axis2)
+                }
+                (JointParamsDesc::RevoluteJoint(revolute), JointAnchor::First) => {
+                    Some(revolute.local_axis1)
+                }
+                (JointParamsDesc::RevoluteJoint(revolute), JointAnchor::Second) => {
+                    Some(revolute.local_axis2)
+                }
+                _ => None,
+            };
+
+            match &joint.params {
+                JointParamsDesc::FixedJoint(fixed) => {
+                    let (translation, rotation) = match anchor {
+                        JointAnchor::First => (
+                            fixed.local_anchor1_translation,
+                            fixed.local_anchor1_rotation,
+                        ),
+                        JointAnchor::Second => (
+                            fixed.local_anchor2_translation,
+                            fixed.local_anchor2_rotation,
+                        ),
+                    };
+                    context.draw_transform(
+                        transform
+                            * Isometry3 {
+                                rotation,
+                                translation: Translation {
+                                    vector: translation,
+                                },
+                            }
+                            .to_homogeneous()
+                            * Matrix4::new_scaling(JOINT_AXIS_LENGTH),
+                    );
+                }
+                JointParamsDesc::PrismaticJoint(_) => {
+                    if let Some(axis) = axis
+                        .map(|a| transform.transform_vector(&a))
+                        .and_then(|a| a.try_normalize(f32::EPSILON))
+                    {
+                        // Descriptors have no translation limits, so the axis is dashed in
+                        // both directions to show that its range is unbounded.
+                        draw_dashed_line(
+                            context,
+                            position - axis.scale(JOINT_AXIS_LENGTH * 2.0),
+                            position + axis.scale(JOINT_AXIS_LENGTH * 2.0),
+                            color,
+                        );
+                    }
+                }
+                JointParamsDesc::RevoluteJoint(_) => {
+                    if let Some(axis) = axis
+                        .map(|a| transform.transform_vector(&a))
+                        .and_then(|a| a.try_normalize(f32::EPSILON))
+                    {
+                        // Descriptors have no rotation limits, so the circle around the axis
+                        // is dashed the same way as unbounded prismatic axis.
                         context.add_line(Line {
-                            begin: anchor1,
-                            end: anchor2,
-                            color: Color::BLUE,
-                        })
+                            begin: position,
+                            end: position + axis.scale(JOINT_AXIS_LENGTH),
+                            color,
+                        });
+                        draw_dashed_circle(context, position, axis, JOINT_AXIS_LENGTH * 0.5, color);
                     }
                 }
-                JointParamsDesc::FixedJoint(_) => {}
-                JointParamsDesc::PrismaticJoint(_) => {}
-                JointParamsDesc::RevoluteJoint(_) => {}
+                JointParamsDesc::BallJoint(_) => {}
             }
+
+            anchors.push(position);
+        }
+
+        if let [anchor1, anchor2] = anchors[..] {
+            context.add_line(Line {
+                begin: anchor1,
+                end: anchor2,
+                color,
+            });
+        }
+    }
+}
+
+const JOINT_AXIS_LENGTH: f32 = 0.5;
+const DASH_COUNT: usize = 16;
+
+/// Draws a line as a set of dashes.
+fn draw_dashed_line(
+    context: &mut SceneDrawingContext,
+    begin: Vector3<f32>,
+    end: Vector3<f32>,
+    color: Color,
+) {
+    let point = |i: usize| begin.lerp(&end, i as f32 / (DASH_COUNT * 2 - 1) as f32);
+    for i in 0..DASH_COUNT {
+        context.add_line(Line {
+            begin: point(i * 2),
+            end: point(i * 2 + 1),
+            color,
+        });
+    }
+}
+
+/// Draws a dashed circle around given axis.
+fn draw_dashed_circle(
+    context: &mut SceneDrawingContext,
+    center: Vector3<f32>,
+    axis: Vector3<f32>,
+    radius: f32,
+    color: Color,
+) {
+    let side = if axis.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let u = axis.cross(&side).normalize().scale(radius);
+    let v = axis.cross(&u);
+
+    let point = |i: usize| {
+        let angle = i as f32 / (DASH_COUNT * 2) as f32 * std::f32::consts::TAU;
+        center + u.scale(angle.cos()) + v.scale(angle.sin())
+    };
+    for i in 0..DASH_COUNT {
+        context.add_line(Line {
+            begin: point(i * 2),
+            end: point(i * 2 + 1),
+            color,
+        });
+    }
+}
+
+/// Draws outline of a cylinder with border radius added to its inner shape, the same way
+/// as rapier inflates round shapes.
+fn draw_round_cylinder(
+    context: &mut SceneDrawingContext,
+    round_cylinder: &RoundCylinderDesc,
+    transform: Matrix4<f32>,
+    color: Color,
+) {
+    let RoundCylinderDesc {
+        half_height,
+        radius,
+        border_radius,
+    } = *round_cylinder;
+
+    let point = |r: f32, y: f32, angle: f32| {
+        transform
+            .transform_point(&Point3::new(r * angle.cos(), y, r * angle.sin()))
+            .coords
+    };
+
+    let segments = 16;
+    for i in 0..segments {
+        let begin = i as f32 / segments as f32 * std::f32::consts::TAU;
+        let end = (i + 1) as f32 / segments as f32 * std::f32::consts::TAU;
+        for &(r, y) in &[
+            (radius + border_radius, half_height),
+            (radius + border_radius, -half_height),
+            (radius, half_height + border_radius),
+            (radius, -half_height - border_radius),
+        ] {
+            context.add_line(Line {
+                begin: point(r, y, begin),
+                end: point(r, y, end),
+                color,
+            });
+        }
+    }
+
+    // Profile of the border along a few meridians: side line and quarter arcs to caps.
+    let meridians = 8;
+    let arc_segments = 4;
+    for i in 0..meridians {
+        let angle = i as f32 / meridians as f32 * std::f32::consts::TAU;
+        context.add_line(Line {
+            begin: point(radius + border_radius, -half_height, angle),
+            end: point(radius + border_radius, half_height, angle),
+            color,
+        });
+        for &sign in &[1.0f32, -1.0] {
+            let arc_point = |k: usize| {
+                let t = k as f32 / arc_segments as f32 * std::f32::consts::FRAC_PI_2;
+                point(
+                    radius + border_radius * t.cos(),
+                    sign * (half_height + border_radius * t.sin()),
+                    angle,
+                )
+            };
+            for k in 0..arc_segments {
+                context.add_line(Line {
+                    begin: arc_point(k),
+                    end: arc_point(k + 1),
+                    color,
+                });
+            }
+        }
+    }
+}
+
+fn joint_anchor(params: &JointParamsDesc, anchor: JointAnchor) -> Vector3<f32> {
+    match (params, anchor) {
+        (JointParamsDesc::BallJoint(ball), JointAnchor::First) => ball.local_anchor1,
+        (JointParamsDesc::BallJoint(ball), JointAnchor::Second) => ball.local_anchor2,
+        (JointParamsDesc::FixedJoint(fixed), JointAnchor::First) => fixed.local_anchor1_translation,
+        (JointParamsDesc::FixedJoint(fixed), JointAnchor::Second) => {
+            fixed.local_anchor2_translation
+        }
+        (JointParamsDesc::PrismaticJoint(prismatic), JointAnchor::First) => prismatic.local_anchor1,
+        (JointParamsDesc::PrismaticJoint(prismatic), JointAnchor::Second) => {
+            prismatic.local_anchor2
         }
+        (JointParamsDesc::RevoluteJoint(revolute), JointAnchor::First) => revolute.local_anchor1,
+        (JointParamsDesc::RevoluteJoint(revolute), JointAnchor::Second) => revolute.local_anchor2,
     }
 }
@@ -0,0 +1,423 @@
+//! Convex hull and approximate convex decomposition of triangle meshes. Decomposition
+//! voxelizes the mesh and recursively splits the most concave part of it by axis-aligned
+//! planes until it reaches desired amount of parts.
+
+use rg3d::core::{
+    algebra::{Matrix3, Rotation3, UnitQuaternion, Vector3},
+    math::aabb::AxisAlignedBoundingBox,
+};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Debug, Default)]
+pub struct Hull {
+    pub vertices: Vec<Vector3<f32>>,
+    /// Indices of vertices of each face, counter-clockwise when looking from outside.
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl Hull {
+    pub fn volume(&self) -> f32 {
+        self.faces
+            .iter()
+            .map(|f| {
+                self.vertices[f[0]].dot(&self.vertices[f[1]].cross(&self.vertices[f[2]])) / 6.0
+            })
+            .sum::<f32>()
+            .abs()
+    }
+
+    /// Returns a box that bounds the hull. Box axes are principal axes of hull vertices,
+    /// so elongated or tilted hulls are bounded much tighter than by an axis-aligned box.
+    pub fn oriented_box(&self) -> OrientedBox {
+        let count = self.vertices.len().max(1) as f32;
+        let mean = self
+            .vertices
+            .iter()
+            .fold(Vector3::default(), |acc, v| acc + v)
+            .scale(1.0 / count);
+
+        let mut covariance = Matrix3::zeros();
+        for vertex in self.vertices.iter() {
+            let d = vertex - mean;
+            covariance += d * d.transpose();
+        }
+
+        let mut axes = covariance.scale(1.0 / count).symmetric_eigen().eigenvectors;
+        // Eigenvectors may form a left-handed basis, which is not a rotation.
+        if axes.determinant() < 0.0 {
+            let flipped = -axes.column(2);
+            axes.set_column(2, &flipped);
+        }
+        let rotation =
+            UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(axes));
+
+        let mut bounds = AxisAlignedBoundingBox::default();
+        for vertex in self.vertices.iter() {
+            bounds.add_point(rotation.inverse_transform_vector(vertex));
+        }
+
+        OrientedBox {
+            center: rotation.transform_vector(&bounds.center()),
+            rotation,
+            half_extents: (bounds.max - bounds.min).scale(0.5),
+        }
+    }
+}
+
+/// Box with arbitrary orientation, closest convex shape to a hull that physics has.
+#[derive(Clone, Debug)]
+pub struct OrientedBox {
+    pub center: Vector3<f32>,
+    pub rotation: UnitQuaternion<f32>,
+    pub half_extents: Vector3<f32>,
+}
+
+fn face_normal(points: &[Vector3<f32>], face: &[usize; 3]) -> Vector3<f32> {
+    (points[face[1]] - points[face[0]]).cross(&(points[face[2]] - points[face[0]]))
+}
+
+/// Builds convex hull of given points using incremental algorithm. Returns `None` if all
+/// points lie on a single plane.
+pub fn convex_hull(points: &[Vector3<f32>]) -> Option<Hull> {
+    let mut bounds = AxisAlignedBoundingBox::default();
+    for &point in points {
+        bounds.add_point(point);
+    }
+    let epsilon = (bounds.max - bounds.min).norm().max(f32::EPSILON) * 1.0e-5;
+
+    // Find initial tetrahedron.
+    let p0 = 0;
+    let p1 = (0..points.len()).max_by(|&a, &b| {
+        (points[a] - points[p0])
+            .norm()
+            .partial_cmp(&(points[b] - points[p0]).norm())
+            .unwrap()
+    })?;
+    let dir = (points[p1] - points[p0]).try_normalize(f32::EPSILON)?;
+    let distance_to_line = |i: usize| {
+        let v = points[i] - points[p0];
+        (v - dir.scale(v.dot(&dir))).norm()
+    };
+    let p2 = (0..points.len()).max_by(|&a, &b| {
+        distance_to_line(a)
+            .partial_cmp(&distance_to_line(b))
+            .unwrap()
+    })?;
+    if distance_to_line(p2) <= epsilon {
+        return None;
+    }
+    let plane_normal = face_normal(points, &[p0, p1, p2]).normalize();
+    let distance_to_plane = |i: usize| plane_normal.dot(&(points[i] - points[p0]));
+    let p3 = (0..points.len()).max_by(|&a, &b| {
+        distance_to_plane(a)
+            .abs()
+            .partial_cmp(&distance_to_plane(b).abs())
+            .unwrap()
+    })?;
+    if distance_to_plane(p3).abs() <= epsilon {
+        return None;
+    }
+
+    let mut faces = if distance_to_plane(p3) < 0.0 {
+        vec![[p0, p1, p2], [p0, p3, p1], [p1, p3, p2], [p2, p3, p0]]
+    } else {
+        vec![[p0, p2, p1], [p0, p1, p3], [p1, p2, p3], [p2, p0, p3]]
+    };
+
+    for (i, point) in points.iter().enumerate() {
+        if i == p0 || i == p1 || i == p2 || i == p3 {
+            continue;
+        }
+
+        let is_visible = |face: &[usize; 3]| {
+            let normal = face_normal(points, face);
+            normal.dot(&(point - points[face[0]])) > epsilon * normal.norm()
+        };
+
+        let (visible, hidden): (Vec<_>, Vec<_>) =
+            faces.iter().copied().partition(|f| is_visible(f));
+        if visible.is_empty() {
+            continue;
+        }
+
+        let visible_edges = visible
+            .iter()
+            .flat_map(|f| vec![(f[0], f[1]), (f[1], f[2]), (f[2], f[0])])
+            .collect::<HashSet<_>>();
+
+        faces = hidden;
+        for &(a, b) in visible_edges.iter() {
+            // Edge is on the horizon if the face on the other side of it is hidden.
+            if !visible_edges.contains(&(b, a)) {
+                faces.push([a, b, i]);
+            }
+        }
+    }
+
+    // Compact vertices, hull uses only a fraction of input points.
+    let mut remap = HashMap::new();
+    let mut vertices = Vec::new();
+    for face in faces.iter_mut() {
+        for index in face.iter_mut() {
+            let old = *index;
+            *index = *remap.entry(old).or_insert_with(|| {
+                vertices.push(points[old]);
+                vertices.len() - 1
+            });
+        }
+    }
+
+    Some(Hull { vertices, faces })
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DecompositionParameters {
+    /// Maximum amount of convex parts.
+    pub max_hulls: usize,
+    /// Amount of voxels along the longest side of the mesh.
+    pub resolution: usize,
+}
+
+impl Default for DecompositionParameters {
+    fn default() -> Self {
+        Self {
+            max_hulls: 8,
+            resolution: 24,
+        }
+    }
+}
+
+type Voxel = [i32; 3];
+
+struct Voxels {
+    origin: Vector3<f32>,
+    size: f32,
+}
+
+impl Voxels {
+    fn voxel_of(&self, point: Vector3<f32>) -> Voxel {
+        let local = (point - self.origin).scale(1.0 / self.size);
+        [
+            local.x.floor() as i32,
+            local.y.floor() as i32,
+            local.z.floor() as i32,
+        ]
+    }
+
+    /// Returns corners of voxels that lie on the border of the set, hull of these points
+    /// is the hull of the whole set.
+    fn border_corners(&self, set: &HashSet<Voxel>) -> Vec<Vector3<f32>> {
+        const NEIGHBOURS: [Voxel; 6] = [
+            [1, 0, 0],
+            [-1, 0, 0],
+            [0, 1, 0],
+            [0, -1, 0],
+            [0, 0, 1],
+            [0, 0, -1],
+        ];
+
+        let mut corners = HashSet::new();
+        for v in set.iter() {
+            let is_border = NEIGHBOURS
+                .iter()
+                .any(|n| !set.contains(&[v[0] + n[0], v[1] + n[1], v[2] + n[2]]));
+            if is_border {
+                for &dx in &[0, 1] {
+                    for &dy in &[0, 1] {
+                        for &dz in &[0, 1] {
+                            corners.insert([v[0] + dx, v[1] + dy, v[2] + dz]);
+                        }
+                    }
+                }
+            }
+        }
+
+        corners
+            .into_iter()
+            .map(|c| {
+                self.origin + Vector3::new(c[0] as f32, c[1] as f32, c[2] as f32).scale(self.size)
+            })
+            .collect()
+    }
+
+    fn concavity(&self, set: &HashSet<Voxel>) -> (f32, Option<Hull>) {
+        let hull = convex_hull(&self.border_corners(set));
+        let hull_volume = hull.as_ref().map_or(0.0, |h| h.volume());
+        let voxel_volume = set.len() as f32 * self.size * self.size * self.size;
+        ((hull_volume - voxel_volume).max(0.0), hull)
+    }
+}
+
+/// Marks every voxel touched by triangles of the mesh, then fills its interior.
+fn voxelize(
+    vertices: &[Vector3<f32>],
+    triangles: &[[usize; 3]],
+    voxels: &Voxels,
+) -> HashSet<Voxel> {
+    let mut surface = HashSet::new();
+    for triangle in triangles {
+        let a = vertices[triangle[0]];
+        let b = vertices[triangle[1]];
+        let c = vertices[triangle[2]];
+
+        // Sample triangle densely enough to not skip any voxel.
+        let longest = (b - a).norm().max((c - a).norm()).max((c - b).norm());
+        let steps = ((longest / voxels.size) * 2.0).ceil().max(1.0) as usize;
+        for i in 0..=steps {
+            for j in 0..=(steps - i) {
+                let u = i as f32 / steps as f32;
+                let v = j as f32 / steps as f32;
+                surface.insert(voxels.voxel_of(a + (b - a).scale(u) + (c - a).scale(v)));
+            }
+        }
+    }
+
+    let mut min = [i32::MAX; 3];
+    let mut max = [i32::MIN; 3];
+    for v in surface.iter() {
+        for k in 0..3 {
+            min[k] = min[k].min(v[k] - 1);
+            max[k] = max[k].max(v[k] + 1);
+        }
+    }
+
+    // Flood fill outside space starting from a corner of padded bounds, everything
+    // that is not reached is either surface or interior.
+    let mut outside = HashSet::new();
+    let mut stack = vec![min];
+    while let Some(v) = stack.pop() {
+        if (0..3).any(|k| v[k] < min[k] || v[k] > max[k])
+            || surface.contains(&v)
+            || !outside.insert(v)
+        {
+            continue;
+        }
+        for k in 0..3 {
+            let mut n = v;
+            n[k] += 1;
+            stack.push(n);
+            n[k] -= 2;
+            stack.push(n);
+        }
+    }
+
+    let mut solid = HashSet::new();
+    for x in min[0]..=max[0] {
+        for y in min[1]..=max[1] {
+            for z in min[2]..=max[2] {
+                if !outside.contains(&[x, y, z]) {
+                    solid.insert([x, y, z]);
+                }
+            }
+        }
+    }
+    solid
+}
+
+/// Splits a set of voxels by a plane that gives the least total concavity of both parts.
+fn split(
+    voxels: &Voxels,
+    set: &HashSet<Voxel>,
+) -> Option<(
+    (HashSet<Voxel>, f32, Option<Hull>),
+    (HashSet<Voxel>, f32, Option<Hull>),
+)> {
+    let mut min = [i32::MAX; 3];
+    let mut max = [i32::MIN; 3];
+    for v in set.iter() {
+        for k in 0..3 {
+            min[k] = min[k].min(v[k]);
+            max[k] = max[k].max(v[k]);
+        }
+    }
+
+    let mut best: Option<(f32, _, _)> = None;
+    for axis in 0..3 {
+        let extent = max[axis] - min[axis];
+        if extent < 1 {
+            continue;
+        }
+        // Few candidate planes per axis is enough for an approximate result.
+        let step = (extent / 4).max(1);
+        let mut plane = min[axis] + step;
+        while plane <= max[axis] {
+            let (left, right): (HashSet<Voxel>, HashSet<Voxel>) =
+                set.iter().partition(|v| v[axis] < plane);
+            if !left.is_empty() && !right.is_empty() {
+                let (left_concavity, left_hull) = voxels.concavity(&left);
+                let (right_concavity, right_hull) = voxels.concavity(&right);
+                let total = left_concavity + right_concavity;
+                if best.as_ref().map_or(true, |(b, _, _)| total < *b) {
+                    best = Some((
+                        total,
+                        (left, left_concavity, left_hull),
+                        (right, right_concavity, right_hull),
+                    ));
+                }
+            }
+            plane += step;
+        }
+    }
+
+    best.map(|(_, left, right)| (left, right))
+}
+
+/// Approximates mesh with a set of convex hulls. With `max_hulls == 1` result is exact
+/// convex hull of mesh vertices.
+pub fn decompose(
+    vertices: &[Vector3<f32>],
+    triangles: &[[usize; 3]],
+    parameters: &DecompositionParameters,
+) -> Vec<Hull> {
+    if parameters.max_hulls <= 1 {
+        return convex_hull(vertices).into_iter().collect();
+    }
+
+    let mut bounds = AxisAlignedBoundingBox::default();
+    for &vertex in vertices {
+        bounds.add_point(vertex);
+    }
+    let extent = bounds.max - bounds.min;
+    let longest = extent.x.max(extent.y).max(extent.z);
+    if longest <= f32::EPSILON {
+        return Vec::new();
+    }
+
+    let voxels = Voxels {
+        origin: bounds.min,
+        size: longest / parameters.resolution.max(1) as f32,
+    };
+
+    let solid = voxelize(vertices, triangles, &voxels);
+    let total_volume = solid.len() as f32 * voxels.size.powi(3);
+    let (concavity, hull) = voxels.concavity(&solid);
+    let mut parts = vec![(solid, concavity, hull)];
+
+    while parts.len() < parameters.max_hulls {
+        let (index, &(_, concavity, _)) = parts
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.1.partial_cmp(&b.1).unwrap())
+            .unwrap();
+
+        // Part is convex enough already.
+        if concavity <= total_volume * 0.01 {
+            break;
+        }
+
+        let (set, _, _) = parts.remove(index);
+        match split(&voxels, &set) {
+            Some((left, right)) => {
+                parts.push(left);
+                parts.push(right);
+            }
+            None => {
+                let (concavity, hull) = voxels.concavity(&set);
+                // Can't be split further, never try again.
+                parts.push((set, concavity.min(0.0), hull));
+            }
+        }
+    }
+
+    parts.into_iter().filter_map(|(_, _, hull)| hull).collect()
+}
@@ -0,0 +1,73 @@
+//! Named presets of collider surface properties. Presets are stored in a RON file in the
+//! working directory, so every project can have its own set of materials.
+
+use crate::settings::SettingsError;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, path::Path};
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct PhysicsMaterial {
+    pub name: String,
+    pub friction: f32,
+    pub restitution: f32,
+}
+
+impl PhysicsMaterial {
+    fn new(name: &str, friction: f32, restitution: f32) -> Self {
+        Self {
+            name: name.to_owned(),
+            friction,
+            restitution,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+pub struct PhysicsMaterialLibrary {
+    pub materials: Vec<PhysicsMaterial>,
+}
+
+impl Default for PhysicsMaterialLibrary {
+    fn default() -> Self {
+        Self {
+            materials: vec![
+                PhysicsMaterial::new("Default", 0.5, 0.0),
+                PhysicsMaterial::new("Ice", 0.02, 0.05),
+                PhysicsMaterial::new("Rubber", 1.0, 0.8),
+                PhysicsMaterial::new("Wood", 0.4, 0.3),
+                PhysicsMaterial::new("Metal", 0.3, 0.15),
+                PhysicsMaterial::new("Concrete", 0.7, 0.1),
+            ],
+        }
+    }
+}
+
+impl PhysicsMaterialLibrary {
+    /// Path relative to working directory.
+    pub const FILE_NAME: &'static str = "physics_materials.ron";
+
+    /// Loads presets of current working directory. If there is no file yet, default presets
+    /// are returned and nothing is written - the file appears only when presets are saved.
+    pub fn load() -> Result<Self, SettingsError> {
+        if Path::new(Self::FILE_NAME).exists() {
+            let file = File::open(Self::FILE_NAME)?;
+            Ok(ron::de::from_reader(file)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), SettingsError> {
+        let file = File::create(Self::FILE_NAME)?;
+        ron::ser::to_writer_pretty(file, self, PrettyConfig::default())?;
+        Ok(())
+    }
+
+    /// Returns index of a preset with exactly given properties.
+    pub fn find(&self, friction: f32, restitution: f32) -> Option<usize> {
+        self.materials
+            .iter()
+            .position(|m| m.friction == friction && m.restitution == restitution)
+    }
+}
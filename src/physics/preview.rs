@@ -0,0 +1,315 @@
+//! Physics preview runs simulation of the editor's physics in a temporary world and moves
+//! bound nodes according to it. Editor's physics model is never touched, transforms of the
+//! nodes are restored when preview is stopped.
+
+use crate::physics::Physics;
+use rg3d::{
+    core::{
+        algebra::{Isometry3, Point3, Translation3, UnitQuaternion, Vector3},
+        pool::Handle,
+    },
+    physics3d::{
+        desc::ColliderShapeDesc,
+        rapier::{
+            dynamics::{
+                CCDSolver, IntegrationParameters, IslandManager, JointSet, RigidBodyBuilder,
+                RigidBodyHandle, RigidBodySet,
+            },
+            geometry::{
+                BroadPhase, ColliderBuilder, ColliderSet, InteractionGroups, NarrowPhase,
+                SharedShape,
+            },
+            math::AngVector,
+            pipeline::PhysicsPipeline,
+        },
+        PhysicsWorld,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::collections::HashMap;
+
+/// Pose of a node in its parent's space.
+#[derive(Copy, Clone, Debug)]
+pub struct Pose {
+    pub node: Handle<Node>,
+    pub position: Vector3<f32>,
+    pub rotation: UnitQuaternion<f32>,
+}
+
+impl Pose {
+    fn of_node(graph: &Graph, node: Handle<Node>) -> Self {
+        let transform = graph[node].local_transform();
+        Self {
+            node,
+            position: **transform.position(),
+            rotation: **transform.rotation(),
+        }
+    }
+
+    fn apply(&self, graph: &mut Graph) {
+        graph[self.node]
+            .local_transform_mut()
+            .set_position(self.position)
+            .set_rotation(self.rotation);
+    }
+}
+
+pub struct PhysicsPreview {
+    pipeline: PhysicsPipeline,
+    gravity: Vector3<f32>,
+    integration_parameters: IntegrationParameters,
+    islands: IslandManager,
+    broad_phase: BroadPhase,
+    narrow_phase: NarrowPhase,
+    ccd_solver: CCDSolver,
+    bodies: RigidBodySet,
+    colliders: ColliderSet,
+    joints: JointSet,
+    /// Nodes that are driven by simulation.
+    nodes: Vec<(Handle<Node>, RigidBodyHandle)>,
+    /// Poses of nodes before simulation has started.
+    initial_poses: Vec<Pose>,
+    pub paused: bool,
+}
+
+impl PhysicsPreview {
+    /// Builds temporary world from editor's physics. Trimeshes and height fields take their
+    /// geometry from their source nodes, which are nodes bound to their bodies by default.
+    pub fn new(physics: &Physics, graph: &Graph) -> Self {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+
+        let mut body_map = HashMap::new();
+        for (handle, body) in physics.bodies.pair_iter() {
+            let mut builder = RigidBodyBuilder::new(body.status.into())
+                .position(body.local_transform())
+                .additional_mass(body.mass)
+                .linvel(body.lin_vel)
+                .angvel(AngVector::new(
+                    body.ang_vel.x,
+                    body.ang_vel.y,
+                    body.ang_vel.z,
+                ))
+                .restrict_rotations(
+                    body.x_rotation_locked,
+                    body.y_rotation_locked,
+                    body.z_rotation_locked,
+                );
+            if body.translation_locked {
+                builder = builder.lock_translations();
+            }
+            let rapier_body = bodies.insert(builder.build());
+            body_map.insert(handle, rapier_body);
+
+            let bound_node = physics.binder.key_of(&handle).cloned().unwrap_or_default();
+
+            for &collider_handle in body.colliders.iter() {
+                let collider = &physics.colliders[collider_handle.into()];
+                let node = physics.collider_source(collider_handle.into());
+
+                // Geometry is built in space of its source, which differs from the space of
+                // the body if the source is not the bound node.
+                let source_position = if node != bound_node && graph.is_valid_handle(node) {
+                    let (rotation, position) = graph.isometric_global_rotation_position(node);
+                    body.local_transform().inverse()
+                        * Isometry3 {
+                            rotation,
+                            translation: Translation3 { vector: position },
+                        }
+                } else {
+                    Isometry3::identity()
+                };
+
+                let mut position = Isometry3 {
+                    translation: Translation3 {
+                        vector: collider.translation,
+                    },
+                    rotation: collider.rotation,
+                };
+
+                let shape = match collider.shape {
+                    ColliderShapeDesc::Ball(ball) => SharedShape::ball(ball.radius),
+                    ColliderShapeDesc::Cylinder(cylinder) => {
+                        SharedShape::cylinder(cylinder.half_height, cylinder.radius)
+                    }
+                    ColliderShapeDesc::RoundCylinder(round_cylinder) => {
+                        SharedShape::round_cylinder(
+                            round_cylinder.half_height,
+                            round_cylinder.radius,
+                            round_cylinder.border_radius,
+                        )
+                    }
+                    ColliderShapeDesc::Cone(cone) => {
+                        SharedShape::cone(cone.half_height, cone.radius)
+                    }
+                    ColliderShapeDesc::Cuboid(cuboid) => SharedShape::cuboid(
+                        cuboid.half_extents.x,
+                        cuboid.half_extents.y,
+                        cuboid.half_extents.z,
+                    ),
+                    ColliderShapeDesc::Capsule(capsule) => SharedShape::capsule(
+                        Point3::from(capsule.begin),
+                        Point3::from(capsule.end),
+                        capsule.radius,
+                    ),
+                    ColliderShapeDesc::Segment(segment) => {
+                        SharedShape::segment(Point3::from(segment.begin), Point3::from(segment.end))
+                    }
+                    ColliderShapeDesc::Triangle(triangle) => SharedShape::triangle(
+                        Point3::from(triangle.a),
+                        Point3::from(triangle.b),
+                        Point3::from(triangle.c),
+                    ),
+                    ColliderShapeDesc::Trimesh(_) => {
+                        if graph.is_valid_handle(node) {
+                            position = source_position;
+                            PhysicsWorld::make_trimesh(node, graph)
+                        } else {
+                            continue;
+                        }
+                    }
+                    ColliderShapeDesc::Heightfield(_) => {
+                        match graph.is_valid_handle(node).then(|| &graph[node]) {
+                            Some(Node::Terrain(terrain)) => {
+                                // Height field is centered, while terrain starts at its origin.
+                                position = source_position
+                                    * Translation3::new(
+                                        terrain.width() * 0.5,
+                                        0.0,
+                                        terrain.length() * 0.5,
+                                    );
+                                PhysicsWorld::make_heightfield(terrain)
+                            }
+                            _ => continue,
+                        }
+                    }
+                };
+
+                let mut builder = ColliderBuilder::new(shape)
+                    .friction(collider.friction)
+                    .restitution(collider.restitution)
+                    .position(position)
+                    .collision_groups(InteractionGroups::new(
+                        collider.collision_groups.memberships,
+                        collider.collision_groups.filter,
+                    ))
+                    .solver_groups(InteractionGroups::new(
+                        collider.solver_groups.memberships,
+                        collider.solver_groups.filter,
+                    ))
+                    .sensor(collider.is_sensor);
+                if let Some(density) = collider.density {
+                    builder = builder.density(density);
+                }
+                colliders.insert_with_parent(builder.build(), rapier_body, &mut bodies);
+            }
+        }
+
+        for joint in physics.joints.iter() {
+            if let (Some(&body1), Some(&body2)) = (
+                body_map.get(&joint.body1.into()),
+                body_map.get(&joint.body2.into()),
+            ) {
+                joints.insert(body1, body2, joint.params.clone().into());
+            }
+        }
+
+        let nodes = physics
+            .binder
+            .forward_map()
+            .iter()
+            .filter_map(|(&node, body)| {
+                if graph.is_valid_handle(node) {
+                    body_map.get(body).map(|&body| (node, body))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let initial_poses = nodes
+            .iter()
+            .map(|&(node, _)| Pose::of_node(graph, node))
+            .collect();
+
+        Self {
+            pipeline: PhysicsPipeline::new(),
+            gravity: Vector3::new(0.0, -9.81, 0.0),
+            integration_parameters: Default::default(),
+            islands: IslandManager::new(),
+            broad_phase: BroadPhase::new(),
+            narrow_phase: NarrowPhase::new(),
+            ccd_solver: CCDSolver::new(),
+            bodies,
+            colliders,
+            joints,
+            nodes,
+            initial_poses,
+            paused: false,
+        }
+    }
+
+    /// Makes one simulation step and moves bound nodes to positions of their bodies.
+    pub fn update(&mut self, graph: &mut Graph) {
+        if self.paused {
+            return;
+        }
+
+        self.pipeline.step(
+            &self.gravity,
+            &self.integration_parameters,
+            &mut self.islands,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.joints,
+            &mut self.ccd_solver,
+            &(),
+            &(),
+        );
+
+        for &(node, body) in self.nodes.iter() {
+            if graph.is_valid_handle(node) {
+                let pose = self.bodies[body].position();
+
+                // Bodies live in world space, while nodes may be attached to other nodes.
+                let parent = graph[node].parent();
+                let (position, rotation) = if graph.is_valid_handle(parent) {
+                    let (parent_rotation, _) = graph.isometric_global_rotation_position(parent);
+                    let inv_parent_transform = graph[parent]
+                        .global_transform()
+                        .try_inverse()
+                        .unwrap_or_default();
+                    (
+                        inv_parent_transform
+                            .transform_point(&Point3::from(pose.translation.vector))
+                            .coords,
+                        parent_rotation.inverse() * pose.rotation,
+                    )
+                } else {
+                    (pose.translation.vector, pose.rotation)
+                };
+
+                graph[node]
+                    .local_transform_mut()
+                    .set_position(position)
+                    .set_rotation(rotation);
+            }
+        }
+    }
+
+    /// Restores every node to its pose before simulation. Returns poses the nodes had at
+    /// the moment of stop, so they can be applied by commands.
+    pub fn stop(self, graph: &mut Graph) -> Vec<Pose> {
+        let mut simulated_poses = Vec::new();
+        for pose in self.initial_poses.iter() {
+            if graph.is_valid_handle(pose.node) {
+                simulated_poses.push(Pose::of_node(graph, pose.node));
+                pose.apply(graph);
+            }
+        }
+        simulated_poses
+    }
+}
@@ -1,4 +1,4 @@
-use crate::GameEngine;
+use crate::{effect::load_effect, GameEngine};
 use rg3d::gui::image::Image;
 use rg3d::gui::message::UiMessage;
 use rg3d::gui::UiNode;
@@ -29,7 +29,13 @@ use rg3d::{
     },
     utils::into_gui_texture,
 };
-use std::path::Path;
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// Effects are restarted with this period, so the preview shows them over and over.
+const EFFECT_LOOP_PERIOD: Duration = Duration::from_secs(4);
 
 #[derive(Eq, PartialEq, Copy, Clone)]
 enum Mode {
@@ -53,6 +59,7 @@ pub struct PreviewPanel {
     mode: Mode,
     xz_position: Vector2<f32>,
     model: Handle<Node>,
+    effect_started: Option<Instant>,
 }
 
 impl PreviewPanel {
@@ -187,6 +194,7 @@ impl PreviewPanel {
             hinge,
             xz_position: Default::default(),
             model: Default::default(),
+            effect_started: None,
         }
     }
 
@@ -283,6 +291,7 @@ impl PreviewPanel {
             scene.remove_node(self.model);
             self.model = Handle::NONE;
         }
+        self.effect_started = None;
     }
 
     pub async fn load_model(&mut self, model: &Path, engine: &mut GameEngine) {
@@ -298,9 +307,32 @@ impl PreviewPanel {
         }
     }
 
+    pub async fn load_effect(&mut self, effect: &Path, engine: &mut GameEngine) {
+        self.clear(engine);
+        if let Ok(effect) = load_effect(effect, engine.resource_manager.clone()).await {
+            let scene = &mut engine.scenes[self.scene];
+            self.model = scene.graph.add_node(effect);
+            self.effect_started = Some(Instant::now());
+            // Particles have no bounds to fit to, just look at the origin.
+            self.yaw = 0.0;
+            self.pitch = -45.0;
+            self.xz_position = Default::default();
+            self.distance = 5.0;
+        }
+    }
+
     pub fn update(&mut self, engine: &mut GameEngine) {
         let scene = &mut engine.scenes[self.scene];
 
+        if let Some(effect_started) = self.effect_started {
+            if effect_started.elapsed() >= EFFECT_LOOP_PERIOD {
+                if let Node::ParticleSystem(particle_system) = &mut scene.graph[self.model] {
+                    particle_system.clear_particles();
+                }
+                self.effect_started = Some(Instant::now());
+            }
+        }
+
         // Create new render target if preview frame has changed its size.
         let (rt_width, rt_height) = if let TextureKind::Rectangle { width, height } =
             scene.render_target.clone().unwrap().data_ref().kind()
@@ -1,17 +1,21 @@
 use crate::{
     physics::{Collider, Joint, Physics, RigidBody},
     scene::GraphSelection,
+    sound::SoundBinder,
     GameEngine,
 };
 use rg3d::{
     core::pool::Handle,
     scene::{graph::Graph, node::Node, Scene},
+    sound::{context::SoundContext, source::SoundSource},
 };
 use std::collections::HashMap;
 
 pub struct Clipboard {
     graph: Graph,
     physics: Physics,
+    sound_context: SoundContext,
+    sound_binder: SoundBinder,
     empty: bool,
 }
 
@@ -20,6 +24,8 @@ impl Default for Clipboard {
         Self {
             graph: Graph::new(),
             physics: Default::default(),
+            sound_context: SoundContext::new(),
+            sound_binder: Default::default(),
             empty: true,
         }
     }
@@ -32,6 +38,7 @@ pub struct DeepCloneResult {
     pub bodies: Vec<Handle<RigidBody>>,
     pub joints: Vec<Handle<Joint>>,
     pub binder: HashMap<Handle<Node>, Handle<RigidBody>>,
+    pub sound_sources: Vec<Handle<SoundSource>>,
 }
 
 fn deep_clone_nodes(
@@ -40,7 +47,7 @@ fn deep_clone_nodes(
     source_physics: &Physics,
     dest_graph: &mut Graph,
     dest_physics: &mut Physics,
-) -> DeepCloneResult {
+) -> (DeepCloneResult, HashMap<Handle<Node>, Handle<Node>>) {
     let mut result = DeepCloneResult::default();
 
     let mut old_new_mapping = HashMap::new();
@@ -92,7 +99,29 @@ fn deep_clone_nodes(
     // TODO: Add joints.
     // Joint will be copied only if both of its associated bodies are copied too.
 
-    result
+    (result, old_new_mapping)
+}
+
+/// Copies sound sources bound to copied nodes and binds the copies to the copies of nodes.
+fn clone_bound_sound_sources(
+    old_new_mapping: &HashMap<Handle<Node>, Handle<Node>>,
+    source_sound_context: &SoundContext,
+    source_sound_binder: &SoundBinder,
+    dest_sound_context: &SoundContext,
+    dest_sound_binder: &mut SoundBinder,
+) -> Vec<Handle<SoundSource>> {
+    let source_state = source_sound_context.state();
+    let mut dest_state = dest_sound_context.state();
+
+    let mut sources = Vec::new();
+    for (&old, &new) in old_new_mapping.iter() {
+        for source in source_sound_binder.sources_of(old) {
+            let source_clone = dest_state.add_source(source_state.source(source).clone());
+            dest_sound_binder.bind(source_clone, new);
+            sources.push(source_clone);
+        }
+    }
+    sources
 }
 
 impl Clipboard {
@@ -101,6 +130,7 @@ impl Clipboard {
         selection: &GraphSelection,
         scene_handle: Handle<Scene>,
         physics: &Physics,
+        sound_binder: &SoundBinder,
         engine: &GameEngine,
     ) {
         self.clear();
@@ -109,7 +139,7 @@ impl Clipboard {
 
         let root_nodes = selection.root_nodes(&scene.graph);
 
-        deep_clone_nodes(
+        let (_, old_new_mapping) = deep_clone_nodes(
             &root_nodes,
             &scene.graph,
             physics,
@@ -117,19 +147,43 @@ impl Clipboard {
             &mut self.physics,
         );
 
+        clone_bound_sound_sources(
+            &old_new_mapping,
+            &scene.sound_context,
+            sound_binder,
+            &self.sound_context,
+            &mut self.sound_binder,
+        );
+
         self.empty = false;
     }
 
-    pub fn paste(&mut self, dest_graph: &mut Graph, dest_physics: &mut Physics) -> DeepCloneResult {
+    pub fn paste(
+        &mut self,
+        dest_graph: &mut Graph,
+        dest_physics: &mut Physics,
+        dest_sound_context: &SoundContext,
+        dest_sound_binder: &mut SoundBinder,
+    ) -> DeepCloneResult {
         assert!(!self.empty);
 
-        deep_clone_nodes(
+        let (mut result, old_new_mapping) = deep_clone_nodes(
             self.graph[self.graph.get_root()].children(),
             &self.graph,
             &self.physics,
             dest_graph,
             dest_physics,
-        )
+        );
+
+        result.sound_sources = clone_bound_sound_sources(
+            &old_new_mapping,
+            &self.sound_context,
+            &self.sound_binder,
+            dest_sound_context,
+            dest_sound_binder,
+        );
+
+        result
     }
 
     pub fn is_empty(&self) -> bool {
@@ -140,5 +194,7 @@ impl Clipboard {
         self.empty = true;
         self.graph = Graph::new();
         self.physics = Default::default();
+        self.sound_context = SoundContext::new();
+        self.sound_binder = Default::default();
     }
 }
@@ -9,6 +9,7 @@ use crate::{
                 DeleteBodyCommand, DeleteColliderCommand, DeleteJointCommand,
                 SetJointConnectedBodyCommand,
             },
+            sound::{DeleteSoundSourceCommand, SetSoundSourceNodeCommand},
         },
         EditorScene, GraphSelection, Selection,
     },
@@ -18,6 +19,7 @@ use rg3d::{
     core::pool::{ErasedHandle, Handle, Ticket},
     engine::resource_manager::ResourceManager,
     scene::{graph::SubGraph, node::Node, Scene},
+    sound::source::SoundSource,
 };
 use std::ops::{Deref, DerefMut};
 use std::{collections::HashMap, sync::mpsc::Sender};
@@ -132,6 +134,10 @@ impl Command for CommandGroup {
             cmd.finalize(context);
         }
     }
+
+    fn changes_scene(&self) -> bool {
+        self.commands.iter().any(|cmd| cmd.changes_scene())
+    }
 }
 
 /// Creates scene command (command group) which removes current selection in editor's scene.
@@ -171,10 +177,18 @@ pub fn make_delete_selection_command(
 
     let root_nodes = selection.root_nodes(graph);
 
-    // Delete all associated physics entities in the whole hierarchy starting from root nodes
-    // found above.
+    // Delete all associated physics entities and bound sound sources in the whole hierarchy
+    // starting from root nodes found above.
     let mut stack = root_nodes.clone();
     while let Some(node) = stack.pop() {
+        for source in editor_scene.sound_binder.sources_of(node) {
+            command_group.push(SceneCommand::new(SetSoundSourceNodeCommand::new(
+                source,
+                Handle::NONE,
+            )));
+            command_group.push(SceneCommand::new(DeleteSoundSourceCommand::new(source)));
+        }
+
         if let Some(&body) = editor_scene.physics.binder.value_of(&node) {
             for &collider in editor_scene.physics.bodies[body].colliders.iter() {
                 command_group.push(SceneCommand::new(DeleteColliderCommand::new(
@@ -226,6 +240,7 @@ impl ChangeSelectionCommand {
                 Selection::Graph(_) => "Change Selection: Graph",
                 Selection::Navmesh(_) => "Change Selection: Navmesh",
                 Selection::Sound(_) => "Change Selection: Sound",
+                Selection::JointAnchor(_) => "Change Selection: Joint Anchor",
             }
             .to_owned(),
             new_selection,
@@ -266,6 +281,10 @@ impl Command for ChangeSelectionCommand {
                 .unwrap();
         }
     }
+
+    fn changes_scene(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug)]
@@ -278,6 +297,7 @@ enum PasteCommandState {
         colliders: Vec<(Ticket<Collider>, Collider)>,
         joints: Vec<(Ticket<Joint>, Joint)>,
         binder: HashMap<Handle<Node>, Handle<RigidBody>>,
+        sound_sources: Vec<(Ticket<SoundSource>, SoundSource, Handle<Node>)>,
         selection: Selection,
     },
     Executed {
@@ -313,10 +333,12 @@ impl Command for PasteCommand {
     fn execute(&mut self, context: &mut SceneContext) {
         match std::mem::replace(&mut self.state, PasteCommandState::Undefined) {
             PasteCommandState::NonExecuted => {
-                let paste_result = context
-                    .editor_scene
-                    .clipboard
-                    .paste(&mut context.scene.graph, &mut context.editor_scene.physics);
+                let paste_result = context.editor_scene.clipboard.paste(
+                    &mut context.scene.graph,
+                    &mut context.editor_scene.physics,
+                    &context.scene.sound_context,
+                    &mut context.editor_scene.sound_binder,
+                );
 
                 let mut selection =
                     Selection::Graph(GraphSelection::from_list(paste_result.root_nodes.clone()));
@@ -333,6 +355,7 @@ impl Command for PasteCommand {
                 colliders,
                 joints,
                 binder,
+                sound_sources,
                 mut selection,
             } => {
                 let mut paste_result = DeepCloneResult {
@@ -372,6 +395,13 @@ impl Command for PasteCommand {
                     context.editor_scene.physics.binder.insert(node, body);
                 }
 
+                let mut state = context.scene.sound_context.state();
+                for (ticket, source, node) in sound_sources {
+                    let source = state.put_back(ticket, source);
+                    context.editor_scene.sound_binder.bind(source, node);
+                    paste_result.sound_sources.push(source);
+                }
+
                 std::mem::swap(&mut context.editor_scene.selection, &mut selection);
                 self.state = PasteCommandState::Executed {
                     paste_result,
@@ -418,6 +448,14 @@ impl Command for PasteCommand {
                 context.editor_scene.physics.binder.remove_by_key(node);
             }
 
+            let mut sound_sources = Vec::new();
+            let mut state = context.scene.sound_context.state();
+            for source in paste_result.sound_sources {
+                let node = context.editor_scene.sound_binder.bind(source, Handle::NONE);
+                let (ticket, source) = state.take_reserve(source);
+                sound_sources.push((ticket, source, node));
+            }
+
             std::mem::swap(&mut context.editor_scene.selection, &mut last_selection);
 
             self.state = PasteCommandState::Reverted {
@@ -426,6 +464,7 @@ impl Command for PasteCommand {
                 colliders,
                 joints,
                 binder: paste_result.binder,
+                sound_sources,
                 selection: last_selection,
             };
         }
@@ -437,6 +476,7 @@ impl Command for PasteCommand {
             bodies,
             colliders,
             joints,
+            sound_sources,
             ..
         } = std::mem::replace(&mut self.state, PasteCommandState::Undefined)
         {
@@ -455,6 +495,11 @@ impl Command for PasteCommand {
             for (ticket, _) in joints {
                 context.editor_scene.physics.joints.forget_ticket(ticket);
             }
+
+            let mut state = context.scene.sound_context.state();
+            for (ticket, _, _) in sound_sources {
+                state.forget_ticket(ticket);
+            }
         }
     }
 }
@@ -1,6 +1,8 @@
 use crate::{command::Command, define_node_command, get_set_swap, scene::commands::SceneContext};
 use rg3d::{
-    core::{algebra::Vector3, numeric_range::NumericRange, pool::Handle},
+    core::{
+        algebra::Vector3, color_gradient::ColorGradient, numeric_range::NumericRange, pool::Handle,
+    },
     resource::texture::Texture,
     scene::{
         graph::Graph,
@@ -313,6 +315,45 @@ define_node_command!(SetParticleSystemAccelerationCommand("Set Particle System A
     get_set_swap!(self, node.as_particle_system_mut(), acceleration, set_acceleration);
 });
 
+/// Sets color over lifetime gradient of a particle system, none gradient removes it.
+#[derive(Debug)]
+pub struct SetParticleSystemColorOverLifetimeCommand {
+    particle_system: Handle<Node>,
+    gradient: Option<ColorGradient>,
+}
+
+impl SetParticleSystemColorOverLifetimeCommand {
+    pub fn new(particle_system: Handle<Node>, gradient: Option<ColorGradient>) -> Self {
+        Self {
+            particle_system,
+            gradient,
+        }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        // Particle system may have no gradient at all, undo must bring exactly that state back.
+        self.gradient = context.editor_scene.particle_gradients.set(
+            &mut context.scene.graph,
+            self.particle_system,
+            self.gradient.take(),
+        );
+    }
+}
+
+impl Command for SetParticleSystemColorOverLifetimeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Particle System Color Over Lifetime".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
 macro_rules! define_emitter_variant_command {
     ($name:ident($human_readable_name:expr, $value_type:ty) where fn swap($self:ident, $emitter:ident, $variant:ident, $var:ident) $apply_method:block ) => {
         define_emitter_command!($name($human_readable_name, $value_type) where fn swap($self, $emitter) {
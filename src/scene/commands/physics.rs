@@ -1,6 +1,6 @@
 use crate::{
     command::Command,
-    physics::{Collider, Joint, RigidBody},
+    physics::{Collider, Joint, JointAnchor, RigidBody},
     scene::commands::SceneContext,
     Physics,
 };
@@ -9,7 +9,7 @@ use rg3d::{
         algebra::{UnitQuaternion, Vector3},
         pool::{ErasedHandle, Handle, Ticket},
     },
-    physics3d::desc::{ColliderShapeDesc, JointParamsDesc},
+    physics3d::desc::{ColliderShapeDesc, JointParamsDesc, RigidBodyTypeDesc},
     scene::node::Node,
 };
 
@@ -476,6 +476,38 @@ define_body_command!(SetBodyMassCommand("Set Body Mass", f32) where fn swap(self
     std::mem::swap(&mut body.mass, &mut self.value);
 });
 
+define_body_command!(SetBodyStatusCommand("Set Body Status", RigidBodyTypeDesc) where fn swap(self, physics, body) {
+    std::mem::swap(&mut body.status, &mut self.value);
+});
+
+define_body_command!(SetBodyLinVelCommand("Set Body Linear Velocity", Vector3<f32>) where fn swap(self, physics, body) {
+    std::mem::swap(&mut body.lin_vel, &mut self.value);
+});
+
+define_body_command!(SetBodyAngVelCommand("Set Body Angular Velocity", Vector3<f32>) where fn swap(self, physics, body) {
+    std::mem::swap(&mut body.ang_vel, &mut self.value);
+});
+
+define_body_command!(SetBodySleepingCommand("Set Body Sleeping", bool) where fn swap(self, physics, body) {
+    std::mem::swap(&mut body.sleeping, &mut self.value);
+});
+
+define_body_command!(SetBodyXRotationLockedCommand("Set Body X Rotation Locked", bool) where fn swap(self, physics, body) {
+    std::mem::swap(&mut body.x_rotation_locked, &mut self.value);
+});
+
+define_body_command!(SetBodyYRotationLockedCommand("Set Body Y Rotation Locked", bool) where fn swap(self, physics, body) {
+    std::mem::swap(&mut body.y_rotation_locked, &mut self.value);
+});
+
+define_body_command!(SetBodyZRotationLockedCommand("Set Body Z Rotation Locked", bool) where fn swap(self, physics, body) {
+    std::mem::swap(&mut body.z_rotation_locked, &mut self.value);
+});
+
+define_body_command!(SetBodyTranslationLockedCommand("Set Body Translation Locked", bool) where fn swap(self, physics, body) {
+    std::mem::swap(&mut body.translation_locked, &mut self.value);
+});
+
 define_collider_command!(SetColliderFrictionCommand("Set Collider Friction", f32) where fn swap(self, physics, collider) {
     std::mem::swap(&mut collider.friction, &mut self.value);
 });
@@ -512,6 +544,18 @@ define_collider_variant_command!(SetCylinderRadiusCommand("Set Cylinder Radius",
     std::mem::swap(&mut cylinder.radius, &mut self.value);
 });
 
+define_collider_variant_command!(SetRoundCylinderHalfHeightCommand("Set Round Cylinder Half Height", f32) where fn swap(self, physics, RoundCylinder, round_cylinder) {
+    std::mem::swap(&mut round_cylinder.half_height, &mut self.value);
+});
+
+define_collider_variant_command!(SetRoundCylinderRadiusCommand("Set Round Cylinder Radius", f32) where fn swap(self, physics, RoundCylinder, round_cylinder) {
+    std::mem::swap(&mut round_cylinder.radius, &mut self.value);
+});
+
+define_collider_variant_command!(SetRoundCylinderBorderRadiusCommand("Set Round Cylinder Border Radius", f32) where fn swap(self, physics, RoundCylinder, round_cylinder) {
+    std::mem::swap(&mut round_cylinder.border_radius, &mut self.value);
+});
+
 define_collider_variant_command!(SetConeHalfHeightCommand("Set Cone Half Height", f32) where fn swap(self, physics, Cone, cone) {
     std::mem::swap(&mut cone.half_height, &mut self.value);
 });
@@ -540,6 +584,26 @@ define_collider_variant_command!(SetBallRadiusCommand("Set Ball Radius", f32) wh
     std::mem::swap(&mut ball.radius, &mut self.value);
 });
 
+define_collider_variant_command!(SetSegmentBeginCommand("Set Segment Begin", Vector3<f32>) where fn swap(self, physics, Segment, segment) {
+    std::mem::swap(&mut segment.begin, &mut self.value);
+});
+
+define_collider_variant_command!(SetSegmentEndCommand("Set Segment End", Vector3<f32>) where fn swap(self, physics, Segment, segment) {
+    std::mem::swap(&mut segment.end, &mut self.value);
+});
+
+define_collider_variant_command!(SetTriangleACommand("Set Triangle A", Vector3<f32>) where fn swap(self, physics, Triangle, triangle) {
+    std::mem::swap(&mut triangle.a, &mut self.value);
+});
+
+define_collider_variant_command!(SetTriangleBCommand("Set Triangle B", Vector3<f32>) where fn swap(self, physics, Triangle, triangle) {
+    std::mem::swap(&mut triangle.b, &mut self.value);
+});
+
+define_collider_variant_command!(SetTriangleCCommand("Set Triangle C", Vector3<f32>) where fn swap(self, physics, Triangle, triangle) {
+    std::mem::swap(&mut triangle.c, &mut self.value);
+});
+
 define_joint_variant_command!(SetBallJointAnchor1Command("Set Ball Joint Anchor 1", Vector3<f32>) where fn swap(self, physics, BallJoint, ball) {
     std::mem::swap(&mut ball.local_anchor1, &mut self.value);
 });
@@ -599,3 +663,13 @@ define_joint_variant_command!(SetPrismaticJointAxis2Command("Set Prismatic Joint
 define_joint_command!(SetJointConnectedBodyCommand("Set Joint Connected Body", ErasedHandle) where fn swap(self, physics, joint) {
     std::mem::swap(&mut joint.body2, &mut self.value);
 });
+
+define_physics_command!(SetJointAnchorCommand("Set Joint Anchor", Joint, (JointAnchor, Vector3<f32>)) where fn swap(self, physics) {
+    let (anchor, position) = self.value;
+    self.value.1 = physics.joint_anchor(self.handle, anchor);
+    physics.set_joint_anchor(self.handle, anchor, position);
+});
+
+define_physics_command!(SetColliderSourceCommand("Set Collider Source", Collider, Handle<Node>) where fn swap(self, physics) {
+    self.value = physics.set_collider_source(self.handle, self.value);
+});
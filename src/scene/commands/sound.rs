@@ -3,10 +3,14 @@ use crate::{command::Command, scene::commands::SceneContext};
 use rg3d::core::algebra::Vector3;
 use rg3d::sound::buffer::SoundBufferResource;
 use rg3d::sound::context::SoundContext;
+use rg3d::sound::effects::Effect;
+use rg3d::sound::renderer::Renderer;
 use rg3d::{
-    core::pool::{Handle, Ticket},
+    core::pool::{Handle, Pool, Ticket},
+    scene::node::Node,
     sound::source::SoundSource,
 };
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct AddSoundSourceCommand {
@@ -116,6 +120,40 @@ impl Command for DeleteSoundSourceCommand {
     }
 }
 
+/// Binds sound source to a node, none node unbinds the source.
+#[derive(Debug)]
+pub struct SetSoundSourceNodeCommand {
+    source: Handle<SoundSource>,
+    node: Handle<Node>,
+}
+
+impl SetSoundSourceNodeCommand {
+    pub fn new(source: Handle<SoundSource>, node: Handle<Node>) -> Self {
+        Self { source, node }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        self.node = context
+            .editor_scene
+            .sound_binder
+            .bind(self.source, self.node);
+    }
+}
+
+impl Command for SetSoundSourceNodeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Sound Source Node".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
 #[derive(Debug)]
 pub struct MoveSpatialSoundSourceCommand {
     source: Handle<SoundSource>,
@@ -284,3 +322,197 @@ define_spatial_sound_source_command!(SetSpatialSoundSourceRolloffFactorCommand("
 define_spatial_sound_source_command!(SetSpatialSoundSourceMaxDistanceCommand("Set Spatial Sound Source Max Distance", f32) where fn swap(self, source) {
     get_set_swap!(self, source, max_distance, set_max_distance);
 });
+
+/// Returns handle of the reverb of a sound context - its first reverb effect. Reverb commands
+/// do not store the handle, because it changes when reverb is removed and added back.
+pub fn find_reverb(effects: &Pool<Effect>) -> Handle<Effect> {
+    effects
+        .pair_iter()
+        .find(|(_, effect)| matches!(effect, Effect::Reverb(_)))
+        .map(|(handle, _)| handle)
+        .unwrap_or_default()
+}
+
+#[derive(Debug)]
+pub struct AddReverbCommand {
+    handle: Handle<Effect>,
+    reverb: Option<Effect>,
+}
+
+impl AddReverbCommand {
+    pub fn new(reverb: Effect) -> Self {
+        Self {
+            handle: Default::default(),
+            reverb: Some(reverb),
+        }
+    }
+}
+
+impl Command for AddReverbCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Add Reverb".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.handle = context
+            .scene
+            .sound_context
+            .state()
+            .add_effect(self.reverb.take().unwrap());
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let mut state = context.scene.sound_context.state();
+        self.reverb = Some(state.effect(self.handle).clone());
+        state.remove_effect(self.handle);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RemoveReverbCommand {
+    reverb: Option<Effect>,
+}
+
+impl Command for RemoveReverbCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Remove Reverb".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let mut state = context.scene.sound_context.state();
+        let handle = find_reverb(state.effects());
+        if handle.is_some() {
+            self.reverb = Some(state.effect(handle).clone());
+            state.remove_effect(handle);
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let Some(reverb) = self.reverb.take() {
+            context.scene.sound_context.state().add_effect(reverb);
+        }
+    }
+}
+
+/// Replaces the reverb entirely, used when inputs of the reverb are changed.
+#[derive(Debug)]
+pub struct ReplaceReverbCommand {
+    reverb: Effect,
+}
+
+impl ReplaceReverbCommand {
+    pub fn new(reverb: Effect) -> Self {
+        Self { reverb }
+    }
+
+    fn swap(&mut self, sound_context: &SoundContext) {
+        let mut state = sound_context.state();
+        let handle = find_reverb(state.effects());
+        if handle.is_some() {
+            std::mem::swap(state.effect_mut(handle), &mut self.reverb);
+        }
+    }
+}
+
+impl Command for ReplaceReverbCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Route Sound Sources To Reverb".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(&context.scene.sound_context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(&context.scene.sound_context);
+    }
+}
+
+macro_rules! define_reverb_command {
+    ($name:ident($human_readable_name:expr, $value_type:ty) where fn swap($self:ident, $reverb:ident) $apply_method:block ) => {
+        #[derive(Debug)]
+        pub struct $name {
+            value: $value_type,
+        }
+
+        impl $name {
+            pub fn new(value: $value_type) -> Self {
+                Self { value }
+            }
+
+            fn swap(&mut $self, sound_context: &SoundContext) {
+                let mut state = sound_context.state();
+                let handle = find_reverb(state.effects());
+                if handle.is_some() {
+                    if let Effect::Reverb($reverb) = state.effect_mut(handle) {
+                        $apply_method
+                    }
+                }
+            }
+        }
+
+        impl Command for $name {
+            fn name(&mut self, _context: &SceneContext) -> String {
+                $human_readable_name.to_owned()
+            }
+
+            fn execute(&mut self, context: &mut SceneContext) {
+                self.swap(&context.scene.sound_context);
+            }
+
+            fn revert(&mut self, context: &mut SceneContext) {
+                self.swap(&context.scene.sound_context);
+            }
+        }
+    };
+}
+
+define_reverb_command!(SetReverbDecayTimeCommand("Set Reverb Decay Time", Duration) where fn swap(self, reverb) {
+    get_set_swap!(self, reverb, decay_time, set_decay_time);
+});
+
+define_reverb_command!(SetReverbDryCommand("Set Reverb Dry", f32) where fn swap(self, reverb) {
+    get_set_swap!(self, reverb, dry, set_dry);
+});
+
+define_reverb_command!(SetReverbWetCommand("Set Reverb Wet", f32) where fn swap(self, reverb) {
+    get_set_swap!(self, reverb, wet, set_wet);
+});
+
+define_reverb_command!(SetReverbGainCommand("Set Reverb Gain", f32) where fn swap(self, reverb) {
+    get_set_swap!(self, reverb, gain, set_gain);
+});
+
+#[derive(Debug)]
+pub struct SetSoundRendererCommand {
+    renderer: Option<Renderer>,
+}
+
+impl SetSoundRendererCommand {
+    pub fn new(renderer: Renderer) -> Self {
+        Self {
+            renderer: Some(renderer),
+        }
+    }
+
+    fn swap(&mut self, sound_context: &SoundContext) {
+        let old = sound_context
+            .state()
+            .set_renderer(self.renderer.take().unwrap());
+        self.renderer = Some(old);
+    }
+}
+
+impl Command for SetSoundRendererCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Sound Renderer".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(&context.scene.sound_context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(&context.scene.sound_context);
+    }
+}
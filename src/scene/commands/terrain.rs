@@ -190,6 +190,52 @@ impl Command for ModifyTerrainLayerMaskCommand {
     }
 }
 
+/// Replaces terrain node with a new one, keeping its handle and position in hierarchy.
+/// Used when terrain must be rebuilt from scratch, for example when it is resized.
+#[derive(Debug)]
+pub struct ReplaceTerrainCommand {
+    terrain: Handle<Node>,
+    node: Option<Node>,
+}
+
+impl ReplaceTerrainCommand {
+    pub fn new(terrain: Handle<Node>, node: Node) -> Self {
+        Self {
+            terrain,
+            node: Some(node),
+        }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        let graph = &mut context.scene.graph;
+
+        let parent = graph[self.terrain].parent();
+        let children = graph[self.terrain].children().to_vec();
+
+        let (ticket, old) = graph.take_reserve(self.terrain);
+        graph.put_back(ticket, self.node.replace(old).unwrap());
+
+        graph.link_nodes(self.terrain, parent);
+        for child in children {
+            graph.link_nodes(child, self.terrain);
+        }
+    }
+}
+
+impl Command for ReplaceTerrainCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Resize Terrain".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
 define_node_command!(SetTerrainDecalLayerIndexCommand("Set Terrain Decal Layer Index", u8) where fn swap(self, node) {
     get_set_swap!(self, node.as_terrain_mut(), decal_layer_index, set_decal_layer_index);
 });
@@ -1,9 +1,14 @@
 use crate::{
     camera::CameraController,
     interaction::navmesh::{data_model::Navmesh, selection::NavmeshSelection},
-    physics::Physics,
+    particle::ParticleGradients,
+    physics::{
+        preview::{PhysicsPreview, Pose},
+        ColliderSources, JointAnchorSelection, Physics,
+    },
     scene::clipboard::Clipboard,
-    sound::SoundSelection,
+    sound::{reset_playback, AuditionBackup, SoundBinder, SoundSelection},
+    terrain::generator::TerrainPreview,
     utils, GameEngine,
 };
 use rg3d::{
@@ -34,13 +39,46 @@ pub struct EditorScene {
     // Editor uses split data model - some parts of scene are editable directly,
     // but some parts are not because of incompatible data model.
     pub physics: Physics,
+    /// Simulation of physics that is running in the scene right now, if any.
+    pub physics_preview: Option<PhysicsPreview>,
+    /// Generated terrain heights that are shown in the scene, but not committed yet.
+    pub terrain_preview: Option<TerrainPreview>,
     pub navmeshes: Pool<Navmesh>,
+    pub sound_binder: SoundBinder,
+    pub particle_gradients: ParticleGradients,
+    /// Sound context values replaced by audition options of sound panel.
+    pub audition_backup: AuditionBackup,
 }
 
 impl EditorScene {
+    /// Stops physics preview and moves nodes back to their poses before simulation. Returns
+    /// poses the nodes had in the simulation.
+    pub fn stop_physics_preview(&mut self, graph: &mut Graph) -> Vec<Pose> {
+        self.physics_preview
+            .take()
+            .map(|preview| preview.stop(graph))
+            .unwrap_or_default()
+    }
+
+    /// Puts original heights back to the terrain that shows generated ones.
+    pub fn discard_terrain_preview(&mut self, graph: &mut Graph) {
+        if let Some(preview) = self.terrain_preview.take() {
+            preview.discard(graph);
+        }
+    }
+
     pub fn save(&mut self, path: PathBuf, engine: &mut GameEngine) -> Result<String, String> {
         let scene = &mut engine.scenes[self.scene];
 
+        // Simulated poses must never get into saved scene.
+        self.stop_physics_preview(&mut scene.graph);
+
+        // Same for uncommitted terrain heights.
+        self.discard_terrain_preview(&mut scene.graph);
+
+        // Same for sound audition.
+        reset_playback(&scene.sound_context, &mut self.audition_backup);
+
         // Validate first.
         let mut valid = true;
         let mut reason = "Scene is not saved, because validation failed:\n".to_owned();
@@ -115,7 +153,7 @@ impl EditorScene {
                     .add(rg3d::utils::navmesh::Navmesh::new(&triangles, &vertices));
             }
 
-            let (desc, binder) = self.physics.generate_engine_desc();
+            let (desc, binder, collider_sources) = self.physics.generate_engine_desc();
             pure_scene.physics.desc = Some(desc);
             pure_scene.physics_binder.enabled = true;
             pure_scene.physics_binder.clear();
@@ -124,8 +162,20 @@ impl EditorScene {
                     .physics_binder
                     .bind(*old_to_new.get(&node).unwrap(), body);
             }
+            let mut sound_binder = self.sound_binder.remap(&old_to_new);
+            let mut collider_sources = collider_sources.remap(&old_to_new);
+            let mut particle_gradients = self.particle_gradients.remap(&old_to_new);
             let mut visitor = Visitor::new();
             pure_scene.visit("Scene", &mut visitor).unwrap();
+            sound_binder
+                .visit(SoundBinder::FIELD_NAME, &mut visitor)
+                .unwrap();
+            collider_sources
+                .visit(ColliderSources::FIELD_NAME, &mut visitor)
+                .unwrap();
+            particle_gradients
+                .visit(ParticleGradients::FIELD_NAME, &mut visitor)
+                .unwrap();
             if let Err(e) = visitor.save_binary(&path) {
                 Err(format!("Failed to save scene! Reason: {}", e.to_string()))
             } else {
@@ -145,6 +195,7 @@ pub enum Selection {
     Graph(GraphSelection),
     Navmesh(NavmeshSelection),
     Sound(SoundSelection),
+    JointAnchor(JointAnchorSelection),
 }
 
 impl Default for Selection {
@@ -160,6 +211,7 @@ impl Selection {
             Selection::Graph(graph) => graph.is_empty(),
             Selection::Navmesh(navmesh) => navmesh.is_empty(),
             Selection::Sound(sound) => sound.sources().is_empty(),
+            Selection::JointAnchor(_) => false,
         }
     }
 
@@ -169,6 +221,7 @@ impl Selection {
             Selection::Graph(graph) => graph.is_single_selection(),
             Selection::Navmesh(navmesh) => navmesh.is_single_selection(),
             Selection::Sound(sound) => sound.is_single_selection(),
+            Selection::JointAnchor(_) => true,
         }
     }
 }
@@ -4,6 +4,8 @@ use crate::{
         debugging::{DebuggingSection, DebuggingSettings},
         graphics::{GraphicsSection, GraphicsSettings},
         move_mode::{MoveInteractionModeSettings, MoveModeSection},
+        navmesh::{NavmeshSection, NavmeshSettings},
+        physics::{PhysicsSection, PhysicsSettings},
     },
     GameEngine, Message, CONFIG_DIR,
 };
@@ -37,6 +39,8 @@ use std::{fs::File, path::PathBuf, sync::mpsc::Sender};
 pub mod debugging;
 pub mod graphics;
 pub mod move_mode;
+pub mod navmesh;
+pub mod physics;
 
 struct SwitchEntry {
     tree_item: Handle<UiNode>,
@@ -52,6 +56,8 @@ pub struct SettingsWindow {
     graphics_section: GraphicsSection,
     move_mode_section: MoveModeSection,
     debugging_section: DebuggingSection,
+    navmesh_section: NavmeshSection,
+    physics_section: PhysicsSection,
     section_switches: Vec<SwitchEntry>,
     sections_root: Handle<UiNode>,
 }
@@ -61,6 +67,8 @@ pub enum SettingsSectionKind {
     Graphics,
     Debugging,
     MoveModeSettings,
+    NavmeshSettings,
+    PhysicsSettings,
 }
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Default)]
@@ -68,6 +76,11 @@ pub struct Settings {
     pub graphics: GraphicsSettings,
     pub debugging: DebuggingSettings,
     pub move_mode_settings: MoveInteractionModeSettings,
+    #[serde(default)]
+    pub navmesh_settings: NavmeshSettings,
+    /// Project-wide, stored in the working directory instead.
+    #[serde(skip)]
+    pub physics_settings: PhysicsSettings,
 }
 
 #[derive(Debug)]
@@ -162,11 +175,15 @@ impl SettingsWindow {
         let graphics_section = GraphicsSection::new(ctx, &settings.graphics);
         let debugging_section = DebuggingSection::new(ctx, &settings.debugging);
         let move_mode_section = MoveModeSection::new(ctx, &settings.move_mode_settings);
+        let navmesh_section = NavmeshSection::new(ctx, &settings.navmesh_settings);
+        let physics_section = PhysicsSection::new(ctx, &settings.physics_settings);
 
         let sections_root;
         let graphics_section_item;
         let debugging_section_item;
         let move_mode_section_item;
+        let navmesh_section_item;
+        let physics_section_item;
         let section = GridBuilder::new(
             WidgetBuilder::new()
                 .on_row(1)
@@ -204,6 +221,26 @@ impl SettingsWindow {
                                         .build(ctx);
                                     move_mode_section_item
                                 },
+                                {
+                                    navmesh_section_item = TreeBuilder::new(WidgetBuilder::new())
+                                        .with_content(
+                                            TextBuilder::new(WidgetBuilder::new())
+                                                .with_text("Navmesh Edit Mode")
+                                                .build(ctx),
+                                        )
+                                        .build(ctx);
+                                    navmesh_section_item
+                                },
+                                {
+                                    physics_section_item = TreeBuilder::new(WidgetBuilder::new())
+                                        .with_content(
+                                            TextBuilder::new(WidgetBuilder::new())
+                                                .with_text("Physics Layers")
+                                                .build(ctx),
+                                        )
+                                        .build(ctx);
+                                    physics_section_item
+                                },
                             ])
                             .build(ctx);
                     sections_root
@@ -214,6 +251,8 @@ impl SettingsWindow {
                             graphics_section.section,
                             debugging_section.section,
                             move_mode_section.section,
+                            navmesh_section.section,
+                            physics_section.section,
                         ],
                     ))
                     .build(ctx),
@@ -240,6 +279,16 @@ impl SettingsWindow {
                 section: move_mode_section.section,
                 kind: SettingsSectionKind::MoveModeSettings,
             },
+            SwitchEntry {
+                tree_item: navmesh_section_item,
+                section: navmesh_section.section,
+                kind: SettingsSectionKind::NavmeshSettings,
+            },
+            SwitchEntry {
+                tree_item: physics_section_item,
+                section: physics_section.section,
+                kind: SettingsSectionKind::PhysicsSettings,
+            },
         ];
 
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(500.0).with_height(600.0))
@@ -307,6 +356,8 @@ impl SettingsWindow {
             graphics_section,
             move_mode_section,
             debugging_section,
+            navmesh_section,
+            physics_section,
         }
     }
 
@@ -343,6 +394,10 @@ impl SettingsWindow {
             .sync_to_model(ui, &settings.move_mode_settings);
         self.debugging_section
             .sync_to_model(ui, &settings.debugging);
+        self.navmesh_section
+            .sync_to_model(ui, &settings.navmesh_settings);
+        self.physics_section
+            .sync_to_model(ui, &settings.physics_settings);
     }
 
     pub fn handle_message(
@@ -362,6 +417,13 @@ impl SettingsWindow {
             .handle_message(message, &mut settings.debugging);
         self.move_mode_section
             .handle_message(message, &mut settings.move_mode_settings);
+        self.navmesh_section
+            .handle_message(message, &mut settings.navmesh_settings);
+        self.physics_section.handle_message(
+            message,
+            &engine.user_interface,
+            &mut settings.physics_settings,
+        );
 
         match message.data() {
             UiMessageData::Button(ButtonMessage::Click) => {
@@ -413,6 +475,19 @@ impl SettingsWindow {
                 }
             }
 
+            // Layer names and the matrix are shown in collider properties.
+            if settings.physics_settings != old_settings.physics_settings {
+                if let Err(e) = settings.physics_settings.save() {
+                    self.sender
+                        .send(Message::Log(format!(
+                            "Unable to save collision layers. Reason: {:?}",
+                            e
+                        )))
+                        .unwrap();
+                }
+                self.sender.send(Message::SyncToModel).unwrap();
+            }
+
             // Save config
             match settings.save() {
                 Ok(_) => {
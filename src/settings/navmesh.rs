@@ -0,0 +1,99 @@
+use crate::settings::{make_bool_input_field, make_f32_input_field, make_text_mark};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::pool::Handle,
+    gui::{
+        grid::{Column, GridBuilder, Row},
+        message::{CheckBoxMessage, MessageDirection, NumericUpDownMessage, UiMessageData},
+        widget::WidgetBuilder,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, PartialEq, Clone)]
+pub struct NavmeshSettings {
+    /// Projects dragged vertices to the ground on each mouse move.
+    pub project_while_dragging: bool,
+    /// Vertical offset from the ground that will be applied to projected vertices.
+    pub ground_offset: f32,
+}
+
+impl Default for NavmeshSettings {
+    fn default() -> Self {
+        Self {
+            project_while_dragging: false,
+            ground_offset: 0.05,
+        }
+    }
+}
+
+pub struct NavmeshSection {
+    pub section: Handle<UiNode>,
+    project_while_dragging: Handle<UiNode>,
+    ground_offset: Handle<UiNode>,
+}
+
+impl NavmeshSection {
+    pub fn new(ctx: &mut BuildContext, settings: &NavmeshSettings) -> Self {
+        let project_while_dragging;
+        let ground_offset;
+        let section = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_child(make_text_mark(ctx, "Project While Dragging", 0))
+                .with_child({
+                    project_while_dragging =
+                        make_bool_input_field(ctx, 0, settings.project_while_dragging);
+                    project_while_dragging
+                })
+                .with_child(make_text_mark(ctx, "Ground Offset", 1))
+                .with_child({
+                    ground_offset = make_f32_input_field(ctx, 1, settings.ground_offset, 0.0);
+                    ground_offset
+                }),
+        )
+        .add_row(Row::strict(25.0))
+        .add_row(Row::strict(25.0))
+        .add_row(Row::stretch())
+        .add_column(Column::strict(160.0))
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        Self {
+            section,
+            project_while_dragging,
+            ground_offset,
+        }
+    }
+
+    pub fn sync_to_model(&self, ui: &UserInterface, settings: &NavmeshSettings) {
+        ui.send_message(CheckBoxMessage::checked(
+            self.project_while_dragging,
+            MessageDirection::ToWidget,
+            Some(settings.project_while_dragging),
+        ));
+
+        ui.send_message(NumericUpDownMessage::value(
+            self.ground_offset,
+            MessageDirection::ToWidget,
+            settings.ground_offset,
+        ));
+    }
+
+    pub fn handle_message(&mut self, message: &UiMessage, settings: &mut NavmeshSettings) {
+        match *message.data() {
+            UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
+                if message.destination() == self.ground_offset {
+                    settings.ground_offset = value;
+                }
+            }
+            UiMessageData::CheckBox(CheckBoxMessage::Check(Some(value))) => {
+                if message.destination() == self.project_while_dragging {
+                    settings.project_while_dragging = value;
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use crate::settings::{make_text_mark, SettingsError};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::pool::Handle,
+    gui::{
+        check_box::CheckBoxBuilder,
+        grid::{Column, GridBuilder, Row},
+        message::{CheckBoxMessage, MessageDirection, TextBoxMessage, TextMessage, UiMessageData},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        text_box::TextBoxBuilder,
+        widget::WidgetBuilder,
+        HorizontalAlignment, Thickness, VerticalAlignment,
+    },
+};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, path::Path};
+
+/// Amount of named collision layers, each layer is a bit in collider's collision groups.
+pub const LAYER_COUNT: usize = 16;
+/// Bits of collision groups that are covered by named layers.
+pub const LAYERS_MASK: u32 = (1 << LAYER_COUNT) - 1;
+
+/// Collision layers of a project. Unlike the rest of settings they are stored in a RON file
+/// in the working directory, next to physics materials, so the whole team shares them.
+#[derive(Deserialize, Serialize, PartialEq, Clone)]
+pub struct PhysicsSettings {
+    /// Names of collision layers, index of a name is index of the layer bit.
+    pub layer_names: Vec<String>,
+    /// Bit `j` of entry `i` is set if layer `i` collides with layer `j`. Always symmetric.
+    pub collision_matrix: Vec<u32>,
+}
+
+impl Default for PhysicsSettings {
+    fn default() -> Self {
+        Self {
+            layer_names: (0..LAYER_COUNT)
+                .map(|i| {
+                    if i == 0 {
+                        "Default".to_owned()
+                    } else {
+                        format!("Layer {}", i)
+                    }
+                })
+                .collect(),
+            collision_matrix: vec![LAYERS_MASK; LAYER_COUNT],
+        }
+    }
+}
+
+impl PhysicsSettings {
+    /// Path relative to working directory.
+    pub const FILE_NAME: &'static str = "physics_layers.ron";
+
+    /// Loads layers of current working directory, or default ones if the project has none.
+    pub fn load() -> Result<Self, SettingsError> {
+        if Path::new(Self::FILE_NAME).exists() {
+            let file = File::open(Self::FILE_NAME)?;
+            Ok(ron::de::from_reader(file)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> Result<(), SettingsError> {
+        let file = File::create(Self::FILE_NAME)?;
+        ron::ser::to_writer_pretty(file, self, PrettyConfig::default())?;
+        Ok(())
+    }
+
+    pub fn layer_name(&self, layer: usize) -> &str {
+        self.layer_names.get(layer).map_or("", |name| name.as_str())
+    }
+
+    pub fn collides(&self, a: usize, b: usize) -> bool {
+        self.collision_matrix
+            .get(a)
+            .map_or(false, |row| row & (1 << b) != 0)
+    }
+
+    pub fn set_collides(&mut self, a: usize, b: usize, collides: bool) {
+        self.collision_matrix.resize(LAYER_COUNT, LAYERS_MASK);
+        for &(row, column) in &[(a, b), (b, a)] {
+            if collides {
+                self.collision_matrix[row] |= 1 << column;
+            } else {
+                self.collision_matrix[row] &= !(1 << column);
+            }
+        }
+    }
+
+    /// Returns mask of layers that collide with at least one of given member layers.
+    pub fn filter_of(&self, memberships: u32) -> u32 {
+        (0..LAYER_COUNT)
+            .filter(|&layer| memberships & (1 << layer) != 0)
+            .fold(0, |filter, layer| {
+                filter | self.collision_matrix.get(layer).cloned().unwrap_or(0)
+            })
+            & LAYERS_MASK
+    }
+}
+
+pub struct PhysicsSection {
+    pub section: Handle<UiNode>,
+    names: Vec<Handle<UiNode>>,
+    /// Names of layers in front of matrix rows.
+    row_names: Vec<Handle<UiNode>>,
+    /// Check boxes of lower triangle of the matrix together with their layers.
+    matrix: Vec<(Handle<UiNode>, usize, usize)>,
+}
+
+const CELL_SIZE: f32 = 20.0;
+
+impl PhysicsSection {
+    pub fn new(ctx: &mut BuildContext, settings: &PhysicsSettings) -> Self {
+        let mut names = Vec::new();
+        let mut names_grid = WidgetBuilder::new();
+        for layer in 0..LAYER_COUNT {
+            let name = TextBoxBuilder::new(
+                WidgetBuilder::new()
+                    .on_row(layer)
+                    .on_column(1)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_text(settings.layer_name(layer))
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .build(ctx);
+            names.push(name);
+            names_grid = names_grid
+                .with_child(make_text_mark(ctx, &format!("Layer {}", layer), layer))
+                .with_child(name);
+        }
+
+        let mut row_names = Vec::new();
+        let mut matrix = Vec::new();
+        let mut matrix_grid = WidgetBuilder::new();
+        for layer in 0..LAYER_COUNT {
+            matrix_grid = matrix_grid.with_child(
+                TextBuilder::new(WidgetBuilder::new().on_row(0).on_column(layer + 1))
+                    .with_text(layer.to_string())
+                    .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .build(ctx),
+            );
+        }
+        for a in 0..LAYER_COUNT {
+            let row_name = TextBuilder::new(
+                WidgetBuilder::new()
+                    .on_row(a + 1)
+                    .on_column(0)
+                    .with_margin(Thickness::left(4.0)),
+            )
+            .with_text(settings.layer_name(a))
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .build(ctx);
+            row_names.push(row_name);
+            matrix_grid = matrix_grid.with_child(row_name);
+
+            for b in 0..=a {
+                let cell = CheckBoxBuilder::new(
+                    WidgetBuilder::new()
+                        .on_row(a + 1)
+                        .on_column(b + 1)
+                        .with_margin(Thickness::uniform(2.0)),
+                )
+                .checked(Some(settings.collides(a, b)))
+                .build(ctx);
+                matrix.push((cell, a, b));
+                matrix_grid = matrix_grid.with_child(cell);
+            }
+        }
+
+        let mut matrix_grid = GridBuilder::new(matrix_grid.with_margin(Thickness::top(10.0)))
+            .add_column(Column::strict(120.0))
+            .add_row(Row::strict(CELL_SIZE));
+        for _ in 0..LAYER_COUNT {
+            matrix_grid = matrix_grid
+                .add_column(Column::strict(CELL_SIZE))
+                .add_row(Row::strict(CELL_SIZE));
+        }
+
+        let mut names_grid = GridBuilder::new(names_grid)
+            .add_column(Column::strict(120.0))
+            .add_column(Column::stretch());
+        for _ in 0..LAYER_COUNT {
+            names_grid = names_grid.add_row(Row::strict(25.0));
+        }
+
+        let section = ScrollViewerBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(names_grid.build(ctx))
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(4.0))
+                                    .with_height(25.0),
+                            )
+                            .with_text("Layer Collision Matrix")
+                            .with_vertical_text_alignment(VerticalAlignment::Bottom)
+                            .build(ctx),
+                        )
+                        .with_child(matrix_grid.build(ctx)),
+                )
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            section,
+            names,
+            row_names,
+            matrix,
+        }
+    }
+
+    pub fn sync_to_model(&self, ui: &UserInterface, settings: &PhysicsSettings) {
+        for (layer, (&name, &row_name)) in self.names.iter().zip(self.row_names.iter()).enumerate()
+        {
+            let text = settings.layer_name(layer).to_owned();
+            ui.send_message(TextBoxMessage::text(
+                name,
+                MessageDirection::ToWidget,
+                text.clone(),
+            ));
+            ui.send_message(TextMessage::text(
+                row_name,
+                MessageDirection::ToWidget,
+                text,
+            ));
+        }
+
+        for &(cell, a, b) in self.matrix.iter() {
+            ui.send_message(CheckBoxMessage::checked(
+                cell,
+                MessageDirection::ToWidget,
+                Some(settings.collides(a, b)),
+            ));
+        }
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+        settings: &mut PhysicsSettings,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        match message.data() {
+            UiMessageData::TextBox(TextBoxMessage::Text(text)) => {
+                if let Some(layer) = self
+                    .names
+                    .iter()
+                    .position(|&name| name == message.destination())
+                {
+                    settings.layer_names.resize(LAYER_COUNT, String::new());
+                    settings.layer_names[layer] = text.clone();
+                    ui.send_message(TextMessage::text(
+                        self.row_names[layer],
+                        MessageDirection::ToWidget,
+                        text.clone(),
+                    ));
+                }
+            }
+            &UiMessageData::CheckBox(CheckBoxMessage::Check(Some(value))) => {
+                if let Some(&(_, a, b)) = self
+                    .matrix
+                    .iter()
+                    .find(|(cell, _, _)| *cell == message.destination())
+                {
+                    settings.set_collides(a, b, value);
+                }
+            }
+            _ => {}
+        }
+    }
+}
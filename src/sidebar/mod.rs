@@ -1,6 +1,7 @@
 use crate::{
     scene::{EditorScene, Selection},
     send_sync_message,
+    settings::Settings,
     sidebar::{
         base::BaseSection, camera::CameraSection, decal::DecalSection, light::LightSection,
         lod::LodGroupEditor, mesh::MeshSection, particle::ParticleSystemSection,
@@ -58,7 +59,7 @@ pub struct SideBar {
     particle_system_section: ParticleSystemSection,
     sprite_section: SpriteSection,
     mesh_section: MeshSection,
-    physics_section: PhysicsSection,
+    pub physics_section: PhysicsSection,
     sound_section: SoundSection,
     decal_section: DecalSection,
     pub terrain_section: TerrainSection,
@@ -227,15 +228,28 @@ impl SideBar {
         }
     }
 
-    pub fn sync_to_model(&mut self, editor_scene: &EditorScene, engine: &mut GameEngine) {
+    pub fn sync_to_model(
+        &mut self,
+        editor_scene: &EditorScene,
+        engine: &mut GameEngine,
+        settings: &Settings,
+    ) {
         scope_profile!();
 
+        // Only physics section is able to edit many nodes at once.
+        let is_multi_node_selection = match &editor_scene.selection {
+            Selection::Graph(selection) => {
+                !selection.is_single_selection() && !selection.is_empty()
+            }
+            _ => false,
+        };
+
         send_sync_message(
             &engine.user_interface,
             WidgetMessage::visibility(
                 self.scroll_viewer,
                 MessageDirection::ToWidget,
-                editor_scene.selection.is_single_selection(),
+                editor_scene.selection.is_single_selection() || is_multi_node_selection,
             ),
         );
 
@@ -272,13 +286,53 @@ impl SideBar {
                         self.lod_editor.sync_to_model(node, scene, ui);
                         self.light_section.sync_to_model(node, ui);
                         self.camera_section.sync_to_model(node, ui);
-                        self.particle_system_section.sync_to_model(node, ui);
+                        self.particle_system_section.sync_to_model(
+                            node,
+                            editor_scene.particle_gradients.get(node_handle),
+                            ui,
+                        );
                         self.sprite_section.sync_to_model(node, ui);
                         self.decal_section.sync_to_model(node, ui);
                         self.mesh_section.sync_to_model(node, ui);
-                        self.terrain_section.sync_to_model(node, ui);
-                        self.physics_section.sync_to_model(editor_scene, engine);
+                        self.terrain_section.sync_to_model(node_handle, node, ui);
+                        self.physics_section.sync_to_model(
+                            editor_scene,
+                            engine,
+                            &settings.physics_settings,
+                        );
+                    }
+                } else if is_multi_node_selection {
+                    for &section in &[
+                        self.base_section.section,
+                        self.sprite_section.section,
+                        self.decal_section.section,
+                        self.light_section.section,
+                        self.camera_section.section,
+                        self.particle_system_section.section,
+                        self.mesh_section.section,
+                        self.terrain_section.section,
+                        self.sound_section.section,
+                    ] {
+                        send_sync_message(
+                            ui,
+                            WidgetMessage::visibility(section, MessageDirection::ToWidget, false),
+                        );
                     }
+
+                    send_sync_message(
+                        ui,
+                        WidgetMessage::visibility(
+                            self.physics_section.section,
+                            MessageDirection::ToWidget,
+                            true,
+                        ),
+                    );
+
+                    self.physics_section.sync_to_model(
+                        editor_scene,
+                        engine,
+                        &settings.physics_settings,
+                    );
                 }
             }
             Selection::Sound(selection) => {
@@ -326,17 +380,30 @@ impl SideBar {
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
-        editor_scene: &EditorScene,
+        editor_scene: &mut EditorScene,
         engine: &mut GameEngine,
+        settings: &Settings,
     ) {
         scope_profile!();
 
+        self.physics_section.handle_material_window_message(
+            message,
+            editor_scene,
+            &engine.user_interface,
+        );
+
         match &editor_scene.selection {
             Selection::Graph(selection) => {
-                if selection.is_single_selection() {
-                    self.physics_section
-                        .handle_ui_message(message, editor_scene, engine);
+                if !selection.is_empty() {
+                    self.physics_section.handle_ui_message(
+                        message,
+                        editor_scene,
+                        engine,
+                        &settings.physics_settings,
+                    );
+                }
 
+                if selection.is_single_selection() {
                     let scene = &mut engine.scenes[editor_scene.scene];
                     let graph = &mut scene.graph;
                     let node_handle = selection.nodes()[0];
@@ -356,6 +423,7 @@ impl SideBar {
                             message,
                             node,
                             node_handle,
+                            editor_scene.particle_gradients.get(node_handle),
                             &engine.user_interface,
                         );
                         self.sprite_section
@@ -383,6 +451,8 @@ impl SideBar {
                             &mut engine.user_interface,
                             graph,
                             node_handle,
+                            engine.resource_manager.clone(),
+                            &mut editor_scene.terrain_preview,
                             &self.sender,
                         );
 
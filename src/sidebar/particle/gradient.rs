@@ -0,0 +1,480 @@
+//! Color over lifetime of particles. Gradient is edited as a list of points, each point has
+//! location in `[0; 1]` range of particle's lifetime and a color, alpha of the color is used
+//! to fade particles, so there is no separate alpha curve.
+//!
+//! Size and velocity over lifetime curves are not supported: particle systems of the engine
+//! have only constant size modifier and acceleration, there is nothing to bind such curves to.
+
+use crate::{
+    scene::commands::particle_system::SetParticleSystemColorOverLifetimeCommand,
+    send_sync_message,
+    sidebar::{
+        make_color_input_field, make_f32_input_field, make_text_mark, COLUMN_WIDTH, ROW_HEIGHT,
+    },
+    Message,
+};
+use rg3d::{
+    core::{
+        color::Color,
+        color_gradient::{ColorGradient, GradientPoint},
+        math::Rect,
+        pool::Handle,
+    },
+    gui::{
+        brush::Brush,
+        button::ButtonBuilder,
+        draw::{CommandTexture, Draw, DrawingContext},
+        grid::{Column, GridBuilder, Row},
+        message::{
+            ButtonMessage, ColorFieldMessage, MessageDirection, NumericUpDownMessage, UiMessage,
+            UiMessageData, WidgetMessage,
+        },
+        stack_panel::StackPanelBuilder,
+        widget::{Widget, WidgetBuilder},
+        BuildContext, Control, Orientation, Thickness, UiNode, UserInterface,
+    },
+    scene::node::Node,
+};
+use std::{
+    any::Any,
+    ops::{Deref, DerefMut},
+    sync::mpsc::Sender,
+};
+
+/// Amount of strips the gradient is drawn with.
+const STRIP_COUNT: usize = 64;
+/// Maximum distance in pixels between cursor and a point to select the point.
+const PICK_DISTANCE: f32 = 6.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GradientFieldMessage {
+    /// Location and color of every point, sorted by location.
+    Points(Vec<(f32, Color)>),
+    /// Index of selected point.
+    Select(Option<usize>),
+}
+
+#[derive(Debug, Clone)]
+pub struct GradientField {
+    widget: Widget,
+    points: Vec<(f32, Color)>,
+    selected: Option<usize>,
+}
+
+impl Deref for GradientField {
+    type Target = Widget;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for GradientField {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t) as u8;
+    Color::from_rgba(
+        lerp(a.r, b.r),
+        lerp(a.g, b.g),
+        lerp(a.b, b.b),
+        lerp(a.a, b.a),
+    )
+}
+
+/// Returns color of sorted points at given location, the same way as engine does.
+fn color_at(points: &[(f32, Color)], location: f32) -> Color {
+    match points {
+        [] => Color::WHITE,
+        [(_, color)] => *color,
+        _ => {
+            if location <= points[0].0 {
+                return points[0].1;
+            }
+            for pair in points.windows(2) {
+                let (left, right) = (pair[0], pair[1]);
+                if location <= right.0 {
+                    let span = right.0 - left.0;
+                    let t = if span > 0.0 {
+                        (location - left.0) / span
+                    } else {
+                        0.0
+                    };
+                    return lerp_color(left.1, right.1, t);
+                }
+            }
+            points[points.len() - 1].1
+        }
+    }
+}
+
+impl Control for GradientField {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Control> {
+        Box::new(self.clone())
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.screen_bounds();
+        // Lower part of the field is for point markers.
+        let strip_height = bounds.size.y * 0.7;
+        let strip_width = bounds.size.x / STRIP_COUNT as f32;
+
+        for i in 0..STRIP_COUNT {
+            let location = (i as f32 + 0.5) / STRIP_COUNT as f32;
+            let strip = Rect::new(
+                bounds.position.x + i as f32 * strip_width,
+                bounds.position.y,
+                strip_width,
+                strip_height,
+            );
+            drawing_context.push_rect_filled(&strip, None);
+            drawing_context.commit(
+                strip,
+                Brush::Solid(color_at(&self.points, location)),
+                CommandTexture::None,
+                None,
+            );
+        }
+
+        for (i, (location, _)) in self.points.iter().enumerate() {
+            let marker = Rect::new(
+                bounds.position.x + location * bounds.size.x - 3.0,
+                bounds.position.y + strip_height,
+                6.0,
+                bounds.size.y - strip_height,
+            );
+            drawing_context.push_rect_filled(&marker, None);
+            drawing_context.commit(
+                marker,
+                if self.selected == Some(i) {
+                    Brush::Solid(Color::opaque(255, 200, 0))
+                } else {
+                    self.foreground()
+                },
+                CommandTexture::None,
+                None,
+            );
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        match message.data() {
+            &UiMessageData::Widget(WidgetMessage::MouseDown { pos, .. }) => {
+                if message.destination() == self.handle() {
+                    let bounds = self.screen_bounds();
+                    let selected = self.points.iter().position(|(location, _)| {
+                        (bounds.position.x + location * bounds.size.x - pos.x).abs()
+                            <= PICK_DISTANCE
+                    });
+                    if selected != self.selected {
+                        self.selected = selected;
+                        ui.send_message(UiMessage::user(
+                            self.handle(),
+                            MessageDirection::FromWidget,
+                            Box::new(GradientFieldMessage::Select(selected)),
+                        ));
+                    }
+                }
+            }
+            UiMessageData::User(msg) => {
+                if message.destination() == self.handle()
+                    && message.direction() == MessageDirection::ToWidget
+                {
+                    match msg.cast::<GradientFieldMessage>() {
+                        Some(GradientFieldMessage::Points(points)) => {
+                            self.points = points.clone();
+                            if self.selected.map_or(false, |i| i >= self.points.len()) {
+                                self.selected = None;
+                            }
+                        }
+                        Some(&GradientFieldMessage::Select(selected)) => {
+                            self.selected = selected;
+                        }
+                        None => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct GradientFieldBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl GradientFieldBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let field = GradientField {
+            widget: self
+                .widget_builder
+                .with_foreground(Brush::Solid(Color::opaque(200, 200, 200)))
+                .build(),
+            points: Default::default(),
+            selected: None,
+        };
+        ctx.add_node(UiNode::new(field))
+    }
+}
+
+fn gradient_points(gradient: Option<&ColorGradient>) -> Vec<(f32, Color)> {
+    gradient
+        .map(|gradient| {
+            gradient
+                .points()
+                .iter()
+                .map(|point| (point.location(), point.color()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn make_gradient(points: &[(f32, Color)]) -> ColorGradient {
+    let mut gradient = ColorGradient::new();
+    for &(location, color) in points {
+        gradient.add_point(GradientPoint::new(location, color));
+    }
+    gradient
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(60.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+pub struct ColorOverLifetimeSection {
+    pub section: Handle<UiNode>,
+    gradient: Handle<UiNode>,
+    add_point: Handle<UiNode>,
+    remove_point: Handle<UiNode>,
+    location: Handle<UiNode>,
+    color: Handle<UiNode>,
+    selected: Option<usize>,
+    sender: Sender<Message>,
+}
+
+impl ColorOverLifetimeSection {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let gradient;
+        let add_point;
+        let remove_point;
+        let location;
+        let color;
+        let section = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(make_text_mark(ctx, "Color Over Lifetime", 0))
+                .with_child({
+                    gradient = GradientFieldBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(0)
+                            .on_column(1)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .build(ctx);
+                    gradient
+                })
+                .with_child(
+                    StackPanelBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(1)
+                            .on_column(1)
+                            .with_child({
+                                add_point = make_button(ctx, "Add");
+                                add_point
+                            })
+                            .with_child({
+                                remove_point = make_button(ctx, "Remove");
+                                remove_point
+                            }),
+                    )
+                    .with_orientation(Orientation::Horizontal)
+                    .build(ctx),
+                )
+                .with_child(make_text_mark(ctx, "Point Location", 2))
+                .with_child({
+                    location = make_f32_input_field(ctx, 2, 0.0, 1.0, 0.01);
+                    location
+                })
+                .with_child(make_text_mark(ctx, "Point Color", 3))
+                .with_child({
+                    color = make_color_input_field(ctx, 3);
+                    color
+                }),
+        )
+        .add_column(Column::strict(COLUMN_WIDTH))
+        .add_column(Column::stretch())
+        .add_row(Row::strict(ROW_HEIGHT * 1.5))
+        .add_row(Row::strict(ROW_HEIGHT))
+        .add_row(Row::strict(ROW_HEIGHT))
+        .add_row(Row::strict(ROW_HEIGHT))
+        .build(ctx);
+
+        Self {
+            section,
+            gradient,
+            add_point,
+            remove_point,
+            location,
+            color,
+            selected: None,
+            sender,
+        }
+    }
+
+    pub fn sync_to_model(&mut self, gradient: Option<&ColorGradient>, ui: &mut UserInterface) {
+        let points = gradient_points(gradient);
+
+        if self.selected.map_or(false, |i| i >= points.len()) {
+            self.selected = None;
+        }
+
+        for &field in &[self.location, self.color, self.remove_point] {
+            send_sync_message(
+                ui,
+                WidgetMessage::enabled(field, MessageDirection::ToWidget, self.selected.is_some()),
+            );
+        }
+
+        if let Some(&(location, color)) = self.selected.and_then(|i| points.get(i)) {
+            send_sync_message(
+                ui,
+                NumericUpDownMessage::value(self.location, MessageDirection::ToWidget, location),
+            );
+            send_sync_message(
+                ui,
+                ColorFieldMessage::color(self.color, MessageDirection::ToWidget, color),
+            );
+        }
+
+        send_sync_message(
+            ui,
+            UiMessage::user(
+                self.gradient,
+                MessageDirection::ToWidget,
+                Box::new(GradientFieldMessage::Points(points)),
+            ),
+        );
+        send_sync_message(
+            ui,
+            UiMessage::user(
+                self.gradient,
+                MessageDirection::ToWidget,
+                Box::new(GradientFieldMessage::Select(self.selected)),
+            ),
+        );
+    }
+
+    /// Removing the last point removes the gradient, so particles become white again.
+    fn set_points(&mut self, handle: Handle<Node>, points: &[(f32, Color)]) {
+        let gradient = if points.is_empty() {
+            None
+        } else {
+            Some(make_gradient(points))
+        };
+        self.sender
+            .send(Message::do_scene_command(
+                SetParticleSystemColorOverLifetimeCommand::new(handle, gradient),
+            ))
+            .unwrap();
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        gradient: Option<&ColorGradient>,
+        handle: Handle<Node>,
+    ) {
+        let mut points = gradient_points(gradient);
+
+        match message.data() {
+            UiMessageData::User(msg) if message.destination() == self.gradient => {
+                if let Some(&GradientFieldMessage::Select(selected)) =
+                    msg.cast::<GradientFieldMessage>()
+                {
+                    if message.direction() == MessageDirection::FromWidget {
+                        self.selected = selected;
+                        self.sender.send(Message::SyncToModel).unwrap();
+                    }
+                }
+            }
+            UiMessageData::Button(ButtonMessage::Click) => {
+                if message.destination() == self.add_point {
+                    // New point goes in the middle between selected point and the next one, so
+                    // it does not change the gradient until it is edited.
+                    let index = self
+                        .selected
+                        .unwrap_or_else(|| points.len().saturating_sub(1));
+                    let left = points.get(index).map_or(0.0, |p| p.0);
+                    let right = points.get(index + 1).map_or(1.0, |p| p.0);
+                    let location = (left + right) * 0.5;
+                    let color = color_at(&points, location);
+                    let new_index = points
+                        .iter()
+                        .position(|p| p.0 > location)
+                        .unwrap_or_else(|| points.len());
+                    points.insert(new_index, (location, color));
+                    self.selected = Some(new_index);
+                    self.set_points(handle, &points);
+                } else if message.destination() == self.remove_point {
+                    if let Some(index) = self.selected.filter(|&i| i < points.len()) {
+                        points.remove(index);
+                        self.selected = None;
+                        self.set_points(handle, &points);
+                    }
+                }
+            }
+            &UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value))
+                if message.destination() == self.location
+                    && message.direction() == MessageDirection::FromWidget =>
+            {
+                if let Some(index) = self.selected.filter(|&i| i < points.len()) {
+                    if points[index].0.ne(&value) {
+                        let (_, color) = points.remove(index);
+                        // Keep points sorted, selection follows moved point.
+                        let new_index = points
+                            .iter()
+                            .position(|p| p.0 > value)
+                            .unwrap_or_else(|| points.len());
+                        points.insert(new_index, (value, color));
+                        self.selected = Some(new_index);
+                        self.set_points(handle, &points);
+                    }
+                }
+            }
+            &UiMessageData::ColorField(ColorFieldMessage::Color(color))
+                if message.destination() == self.color
+                    && message.direction() == MessageDirection::FromWidget =>
+            {
+                if let Some(index) = self.selected.filter(|&i| i < points.len()) {
+                    if points[index].1 != color {
+                        points[index].1 = color;
+                        self.set_points(handle, &points);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::gui::DeletableItem;
 use crate::sidebar::make_section;
 use crate::{
+    effect::{save_effect, EFFECT_EXTENSION},
     gui::{DeletableItemBuilder, DeletableItemMessage},
     load_image,
     scene::commands::particle_system::{
@@ -8,8 +9,9 @@ use crate::{
     },
     send_sync_message,
     sidebar::{
-        make_text_mark, make_vec3_input_field, particle::emitter::EmitterSection, COLUMN_WIDTH,
-        ROW_HEIGHT,
+        make_text_mark, make_vec3_input_field,
+        particle::{emitter::EmitterSection, gradient::ColorOverLifetimeSection},
+        COLUMN_WIDTH, ROW_HEIGHT,
     },
     Message,
 };
@@ -17,19 +19,21 @@ use rg3d::gui::dropdown_list::DropdownList;
 use rg3d::gui::message::UiMessage;
 use rg3d::gui::{BuildContext, UiNode, UserInterface};
 use rg3d::{
-    core::{pool::Handle, scope_profile},
+    core::{color_gradient::ColorGradient, pool::Handle, scope_profile},
     gui::{
         button::ButtonBuilder,
         dropdown_list::DropdownListBuilder,
+        file_browser::{FileBrowserMode, FileSelectorBuilder, Filter},
         grid::{Column, GridBuilder, Row},
         image::ImageBuilder,
         message::{
-            ButtonMessage, DropdownListMessage, MessageDirection, UiMessageData, Vec3EditorMessage,
-            WidgetMessage,
+            ButtonMessage, DropdownListMessage, FileSelectorMessage, MessageDirection,
+            UiMessageData, Vec3EditorMessage, WidgetMessage, WindowMessage,
         },
         stack_panel::StackPanelBuilder,
         text::TextBuilder,
         widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
         HorizontalAlignment, Orientation, Thickness, VerticalAlignment,
     },
     scene::{
@@ -40,11 +44,15 @@ use rg3d::{
         },
     },
 };
-use std::sync::mpsc::Sender;
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+};
 
 mod cuboid;
 mod cylinder;
 mod emitter;
+mod gradient;
 mod sphere;
 
 pub struct ParticleSystemSection {
@@ -57,9 +65,12 @@ pub struct ParticleSystemSection {
     sender: Sender<Message>,
     emitter_index: Option<usize>,
     emitter_section: EmitterSection,
+    color_over_lifetime_section: ColorOverLifetimeSection,
     play_pause: Handle<UiNode>,
     stop: Handle<UiNode>,
     restart: Handle<UiNode>,
+    save_effect: Handle<UiNode>,
+    save_effect_selector: Handle<UiNode>,
 }
 
 fn make_button_image(ctx: &mut BuildContext, image_data: &[u8]) -> Handle<UiNode> {
@@ -80,6 +91,7 @@ fn make_button_image(ctx: &mut BuildContext, image_data: &[u8]) -> Handle<UiNode
 impl ParticleSystemSection {
     pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
         let emitter_section = EmitterSection::new(ctx, sender.clone());
+        let color_over_lifetime_section = ColorOverLifetimeSection::new(ctx, sender.clone());
 
         let box_emitter_img = include_bytes!("../../../resources/embed/add_box_emitter.png");
         let sphere_emitter_img = include_bytes!("../../../resources/embed/add_sphere_emitter.png");
@@ -89,6 +101,7 @@ impl ParticleSystemSection {
         let play_pause;
         let stop;
         let restart;
+        let save_effect;
         let acceleration;
         let emitters;
         let add_box_emitter;
@@ -211,12 +224,41 @@ impl ParticleSystemSection {
                         .add_row(Row::strict(ROW_HEIGHT))
                         .build(ctx),
                     )
+                    .with_child({
+                        save_effect = ButtonBuilder::new(
+                            WidgetBuilder::new()
+                                .with_height(ROW_HEIGHT)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_text("Save Effect As...")
+                        .build(ctx);
+                        save_effect
+                    })
+                    .with_child(color_over_lifetime_section.section)
                     .with_child(emitter_section.section),
             )
             .build(ctx),
             ctx,
         );
 
+        let save_effect_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .with_title(WindowTitle::Text("Save Effect As".into()))
+                .open(false),
+        )
+        .with_mode(FileBrowserMode::Save {
+            default_file_name: PathBuf::from(format!("unnamed.{}", EFFECT_EXTENSION)),
+        })
+        .with_path("./")
+        .with_filter(Filter::new(|p: &Path| {
+            if let Some(ext) = p.extension() {
+                ext.to_string_lossy().as_ref() == EFFECT_EXTENSION
+            } else {
+                p.is_dir()
+            }
+        }))
+        .build(ctx);
+
         Self {
             section,
             acceleration,
@@ -227,13 +269,21 @@ impl ParticleSystemSection {
             sender,
             emitter_index: None,
             emitter_section,
+            color_over_lifetime_section,
             stop,
             play_pause,
             restart,
+            save_effect,
+            save_effect_selector,
         }
     }
 
-    pub fn sync_to_model(&mut self, node: &Node, ui: &mut UserInterface) {
+    pub fn sync_to_model(
+        &mut self,
+        node: &Node,
+        color_over_lifetime: Option<&ColorGradient>,
+        ui: &mut UserInterface,
+    ) {
         send_sync_message(
             ui,
             WidgetMessage::visibility(
@@ -253,6 +303,9 @@ impl ParticleSystemSection {
                 ),
             );
 
+            self.color_over_lifetime_section
+                .sync_to_model(color_over_lifetime, ui);
+
             let ctx = &mut ui.build_ctx();
             let emitters = particle_system
                 .emitters
@@ -313,6 +366,7 @@ impl ParticleSystemSection {
         message: &UiMessage,
         node: &mut Node,
         handle: Handle<Node>,
+        color_over_lifetime: Option<&ColorGradient>,
         ui: &UserInterface,
     ) {
         scope_profile!();
@@ -327,6 +381,9 @@ impl ParticleSystemSection {
                 );
             }
 
+            self.color_over_lifetime_section
+                .handle_message(message, color_over_lifetime, handle);
+
             match message.data() {
                 UiMessageData::Vec3Editor(Vec3EditorMessage::Value(value)) => {
                     if particle_system.acceleration() != *value
@@ -375,8 +432,23 @@ impl ParticleSystemSection {
                     } else if message.destination() == self.play_pause {
                         let new_state = !particle_system.is_enabled();
                         particle_system.set_enabled(new_state);
+                    } else if message.destination() == self.save_effect {
+                        ui.send_message(WindowMessage::open_modal(
+                            self.save_effect_selector,
+                            MessageDirection::ToWidget,
+                            true,
+                        ));
                     }
                 }
+                UiMessageData::FileSelector(FileSelectorMessage::Commit(path))
+                    if message.destination() == self.save_effect_selector =>
+                {
+                    let message = match save_effect(particle_system, path) {
+                        Ok(_) => format!("Effect saved to {}", path.display()),
+                        Err(reason) => reason,
+                    };
+                    self.sender.send(Message::Log(message)).unwrap();
+                }
                 UiMessageData::User(msg) => {
                     if let Some(DeletableItemMessage::Delete) = msg.cast::<DeletableItemMessage>() {
                         if ui
@@ -1,9 +1,16 @@
 use crate::sidebar::make_section;
 use crate::{
     physics::RigidBody,
-    scene::commands::physics::SetBodyMassCommand,
+    scene::commands::physics::{
+        SetBodyAngVelCommand, SetBodyLinVelCommand, SetBodyMassCommand, SetBodySleepingCommand,
+        SetBodyTranslationLockedCommand, SetBodyXRotationLockedCommand,
+        SetBodyYRotationLockedCommand, SetBodyZRotationLockedCommand,
+    },
     send_sync_message,
-    sidebar::{make_f32_input_field, make_text_mark, COLUMN_WIDTH, ROW_HEIGHT},
+    sidebar::{
+        make_bool_input_field, make_f32_input_field, make_text_mark, make_vec3_input_field,
+        COLUMN_WIDTH, ROW_HEIGHT,
+    },
     Message,
 };
 use rg3d::gui::message::UiMessage;
@@ -12,7 +19,10 @@ use rg3d::{
     core::pool::Handle,
     gui::{
         grid::{Column, GridBuilder, Row},
-        message::{MessageDirection, NumericUpDownMessage, UiMessageData},
+        message::{
+            CheckBoxMessage, MessageDirection, NumericUpDownMessage, UiMessageData,
+            Vec3EditorMessage,
+        },
         widget::WidgetBuilder,
     },
 };
@@ -21,12 +31,26 @@ use std::sync::mpsc::Sender;
 pub struct BodySection {
     pub section: Handle<UiNode>,
     mass: Handle<UiNode>,
+    lin_vel: Handle<UiNode>,
+    ang_vel: Handle<UiNode>,
+    sleeping: Handle<UiNode>,
+    x_rotation_locked: Handle<UiNode>,
+    y_rotation_locked: Handle<UiNode>,
+    z_rotation_locked: Handle<UiNode>,
+    translation_locked: Handle<UiNode>,
     sender: Sender<Message>,
 }
 
 impl BodySection {
     pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
         let mass;
+        let lin_vel;
+        let ang_vel;
+        let sleeping;
+        let x_rotation_locked;
+        let y_rotation_locked;
+        let z_rotation_locked;
+        let translation_locked;
         let section = make_section(
             "Body Properties",
             GridBuilder::new(
@@ -35,11 +59,53 @@ impl BodySection {
                     .with_child({
                         mass = make_f32_input_field(ctx, 0, 0.0, std::f32::MAX, 0.1);
                         mass
+                    })
+                    .with_child(make_text_mark(ctx, "Linear Velocity", 1))
+                    .with_child({
+                        lin_vel = make_vec3_input_field(ctx, 1);
+                        lin_vel
+                    })
+                    .with_child(make_text_mark(ctx, "Angular Velocity", 2))
+                    .with_child({
+                        ang_vel = make_vec3_input_field(ctx, 2);
+                        ang_vel
+                    })
+                    .with_child(make_text_mark(ctx, "Sleeping", 3))
+                    .with_child({
+                        sleeping = make_bool_input_field(ctx, 3);
+                        sleeping
+                    })
+                    .with_child(make_text_mark(ctx, "X Rotation Locked", 4))
+                    .with_child({
+                        x_rotation_locked = make_bool_input_field(ctx, 4);
+                        x_rotation_locked
+                    })
+                    .with_child(make_text_mark(ctx, "Y Rotation Locked", 5))
+                    .with_child({
+                        y_rotation_locked = make_bool_input_field(ctx, 5);
+                        y_rotation_locked
+                    })
+                    .with_child(make_text_mark(ctx, "Z Rotation Locked", 6))
+                    .with_child({
+                        z_rotation_locked = make_bool_input_field(ctx, 6);
+                        z_rotation_locked
+                    })
+                    .with_child(make_text_mark(ctx, "Translation Locked", 7))
+                    .with_child({
+                        translation_locked = make_bool_input_field(ctx, 7);
+                        translation_locked
                     }),
             )
             .add_column(Column::strict(COLUMN_WIDTH))
             .add_column(Column::stretch())
             .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
             .build(ctx),
             ctx,
         );
@@ -48,6 +114,13 @@ impl BodySection {
             section,
             sender,
             mass,
+            lin_vel,
+            ang_vel,
+            sleeping,
+            x_rotation_locked,
+            y_rotation_locked,
+            z_rotation_locked,
+            translation_locked,
         }
     }
 
@@ -56,6 +129,29 @@ impl BodySection {
             ui,
             NumericUpDownMessage::value(self.mass, MessageDirection::ToWidget, body.mass),
         );
+
+        send_sync_message(
+            ui,
+            Vec3EditorMessage::value(self.lin_vel, MessageDirection::ToWidget, body.lin_vel),
+        );
+
+        send_sync_message(
+            ui,
+            Vec3EditorMessage::value(self.ang_vel, MessageDirection::ToWidget, body.ang_vel),
+        );
+
+        for &(check_box, value) in &[
+            (self.sleeping, body.sleeping),
+            (self.x_rotation_locked, body.x_rotation_locked),
+            (self.y_rotation_locked, body.y_rotation_locked),
+            (self.z_rotation_locked, body.z_rotation_locked),
+            (self.translation_locked, body.translation_locked),
+        ] {
+            send_sync_message(
+                ui,
+                CheckBoxMessage::checked(check_box, MessageDirection::ToWidget, Some(value)),
+            );
+        }
     }
 
     pub fn handle_message(
@@ -64,17 +160,72 @@ impl BodySection {
         body: &RigidBody,
         handle: Handle<RigidBody>,
     ) {
-        if let UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) = *message.data() {
-            if message.direction() == MessageDirection::FromWidget
-                && message.destination() == self.mass
-                && body.mass.ne(&value)
-            {
-                self.sender
-                    .send(Message::do_scene_command(SetBodyMassCommand::new(
-                        handle, value,
-                    )))
-                    .unwrap();
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        match *message.data() {
+            UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
+                if message.destination() == self.mass && body.mass.ne(&value) {
+                    self.sender
+                        .send(Message::do_scene_command(SetBodyMassCommand::new(
+                            handle, value,
+                        )))
+                        .unwrap();
+                }
+            }
+            UiMessageData::Vec3Editor(Vec3EditorMessage::Value(value)) => {
+                if message.destination() == self.lin_vel && body.lin_vel.ne(&value) {
+                    self.sender
+                        .send(Message::do_scene_command(SetBodyLinVelCommand::new(
+                            handle, value,
+                        )))
+                        .unwrap();
+                } else if message.destination() == self.ang_vel && body.ang_vel.ne(&value) {
+                    self.sender
+                        .send(Message::do_scene_command(SetBodyAngVelCommand::new(
+                            handle, value,
+                        )))
+                        .unwrap();
+                }
+            }
+            UiMessageData::CheckBox(CheckBoxMessage::Check(checked)) => {
+                let value = checked.unwrap_or_default();
+                let destination = message.destination();
+                if destination == self.sleeping && body.sleeping != value {
+                    self.sender
+                        .send(Message::do_scene_command(SetBodySleepingCommand::new(
+                            handle, value,
+                        )))
+                        .unwrap();
+                } else if destination == self.x_rotation_locked && body.x_rotation_locked != value {
+                    self.sender
+                        .send(Message::do_scene_command(
+                            SetBodyXRotationLockedCommand::new(handle, value),
+                        ))
+                        .unwrap();
+                } else if destination == self.y_rotation_locked && body.y_rotation_locked != value {
+                    self.sender
+                        .send(Message::do_scene_command(
+                            SetBodyYRotationLockedCommand::new(handle, value),
+                        ))
+                        .unwrap();
+                } else if destination == self.z_rotation_locked && body.z_rotation_locked != value {
+                    self.sender
+                        .send(Message::do_scene_command(
+                            SetBodyZRotationLockedCommand::new(handle, value),
+                        ))
+                        .unwrap();
+                } else if destination == self.translation_locked && body.translation_locked != value
+                {
+                    self.sender
+                        .send(Message::do_scene_command(
+                            SetBodyTranslationLockedCommand::new(handle, value),
+                        ))
+                        .unwrap();
+                }
             }
+            _ => {}
         }
     }
 }
@@ -1,15 +1,17 @@
 use crate::sidebar::make_section;
 use crate::{
-    physics::Collider,
+    physics::{material::PhysicsMaterialLibrary, Collider},
     scene::commands::physics::{
         SetColliderCollisionGroupsFilterCommand, SetColliderCollisionGroupsMembershipsCommand,
         SetColliderFrictionCommand, SetColliderIsSensorCommand, SetColliderPositionCommand,
         SetColliderRestitutionCommand, SetColliderRotationCommand,
     },
+    scene::commands::CommandGroup,
     send_sync_message,
+    settings::physics::{PhysicsSettings, LAYERS_MASK, LAYER_COUNT},
     sidebar::{
-        make_bool_input_field, make_f32_input_field, make_int_input_field, make_text_mark,
-        make_vec3_input_field, COLUMN_WIDTH, ROW_HEIGHT,
+        make_bool_input_field, make_f32_input_field, make_text_mark, make_vec3_input_field,
+        physics::material::make_apply_commands, COLUMN_WIDTH, ROW_HEIGHT,
     },
     Message,
 };
@@ -22,24 +24,116 @@ use rg3d::{
         pool::Handle,
     },
     gui::{
+        button::ButtonBuilder,
+        check_box::CheckBoxBuilder,
+        dropdown_list::DropdownListBuilder,
         grid::{Column, GridBuilder, Row},
         message::{
-            CheckBoxMessage, MessageDirection, NumericUpDownMessage, UiMessageData,
-            Vec3EditorMessage,
+            ButtonMessage, CheckBoxMessage, DropdownListMessage, MessageDirection,
+            NumericUpDownMessage, TextMessage, UiMessageData, Vec3EditorMessage,
         },
+        text::TextBuilder,
         widget::WidgetBuilder,
+        wrap_panel::WrapPanelBuilder,
+        HorizontalAlignment, Orientation, Thickness, VerticalAlignment,
     },
 };
 use std::sync::mpsc::Sender;
 
+/// Check box per named collision layer, each one toggles a bit of a mask.
+struct LayerList {
+    panel: Handle<UiNode>,
+    check_boxes: Vec<Handle<UiNode>>,
+    names: Vec<Handle<UiNode>>,
+}
+
+impl LayerList {
+    fn new(ctx: &mut BuildContext, row: usize) -> Self {
+        let mut check_boxes = Vec::new();
+        let mut names = Vec::new();
+        let mut panel = WidgetBuilder::new()
+            .on_row(row)
+            .on_column(1)
+            .with_margin(Thickness::uniform(1.0));
+        for _ in 0..LAYER_COUNT {
+            let name = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(2.0)))
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .build(ctx);
+            let check_box = CheckBoxBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(90.0)
+                    .with_height(ROW_HEIGHT)
+                    .with_horizontal_alignment(HorizontalAlignment::Left),
+            )
+            .with_content(name)
+            .build(ctx);
+            check_boxes.push(check_box);
+            names.push(name);
+            panel = panel.with_child(check_box);
+        }
+
+        Self {
+            panel: WrapPanelBuilder::new(panel)
+                .with_orientation(Orientation::Horizontal)
+                .build(ctx),
+            check_boxes,
+            names,
+        }
+    }
+
+    fn sync_to_model(&self, mask: u32, settings: &PhysicsSettings, ui: &UserInterface) {
+        for (layer, (&check_box, &name)) in
+            self.check_boxes.iter().zip(self.names.iter()).enumerate()
+        {
+            send_sync_message(
+                ui,
+                CheckBoxMessage::checked(
+                    check_box,
+                    MessageDirection::ToWidget,
+                    Some(mask & (1 << layer) != 0),
+                ),
+            );
+            send_sync_message(
+                ui,
+                TextMessage::text(
+                    name,
+                    MessageDirection::ToWidget,
+                    settings.layer_name(layer).to_owned(),
+                ),
+            );
+        }
+    }
+
+    /// Returns mask with toggled bit if the message came from one of check boxes.
+    fn handle_message(&self, message: &UiMessage, mask: u32) -> Option<u32> {
+        if let UiMessageData::CheckBox(CheckBoxMessage::Check(checked)) = *message.data() {
+            let layer = self
+                .check_boxes
+                .iter()
+                .position(|&check_box| check_box == message.destination())?;
+            let new_mask = if checked.unwrap_or_default() {
+                mask | (1 << layer)
+            } else {
+                mask & !(1 << layer)
+            };
+            if new_mask != mask {
+                return Some(new_mask);
+            }
+        }
+        None
+    }
+}
+
 pub struct ColliderSection {
     pub section: Handle<UiNode>,
     friction: Handle<UiNode>,
     restitution: Handle<UiNode>,
+    pub material: Handle<UiNode>,
     position: Handle<UiNode>,
     rotation: Handle<UiNode>,
-    collision_groups: Handle<UiNode>,
-    collision_mask: Handle<UiNode>,
+    collision_groups: LayerList,
+    collision_mask: LayerList,
+    mask_from_matrix: Handle<UiNode>,
     is_sensor: Handle<UiNode>,
     sender: Sender<Message>,
 }
@@ -48,10 +142,12 @@ impl ColliderSection {
     pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
         let friction;
         let restitution;
+        let material;
         let position;
         let rotation;
-        let collision_groups;
-        let collision_mask;
+        let collision_groups = LayerList::new(ctx, 5);
+        let collision_mask = LayerList::new(ctx, 6);
+        let mask_from_matrix;
         let is_sensor;
         let section = make_section(
             "Collider Properties",
@@ -67,29 +163,46 @@ impl ColliderSection {
                         restitution = make_f32_input_field(ctx, 1, 0.0, std::f32::MAX, 0.1);
                         restitution
                     })
-                    .with_child(make_text_mark(ctx, "Collider Position", 2))
+                    .with_child(make_text_mark(ctx, "Material Preset", 2))
                     .with_child({
-                        position = make_vec3_input_field(ctx, 2);
-                        position
+                        material = DropdownListBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(2)
+                                .on_column(1)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_close_on_selection(true)
+                        .build(ctx);
+                        material
                     })
-                    .with_child(make_text_mark(ctx, "Collider Rotation", 3))
+                    .with_child(make_text_mark(ctx, "Collider Position", 3))
                     .with_child({
-                        rotation = make_vec3_input_field(ctx, 3);
-                        rotation
+                        position = make_vec3_input_field(ctx, 3);
+                        position
                     })
-                    .with_child(make_text_mark(ctx, "Collision Groups", 4))
+                    .with_child(make_text_mark(ctx, "Collider Rotation", 4))
                     .with_child({
-                        collision_groups = make_int_input_field(ctx, 4, 0, u16::MAX as i32, 1);
-                        collision_groups
+                        rotation = make_vec3_input_field(ctx, 4);
+                        rotation
                     })
-                    .with_child(make_text_mark(ctx, "Collision Mask", 5))
+                    .with_child(make_text_mark(ctx, "Collision Groups", 5))
+                    .with_child(collision_groups.panel)
+                    .with_child(make_text_mark(ctx, "Collision Mask", 6))
+                    .with_child(collision_mask.panel)
                     .with_child({
-                        collision_mask = make_int_input_field(ctx, 5, 0, u16::MAX as i32, 1);
-                        collision_mask
+                        mask_from_matrix = ButtonBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(7)
+                                .on_column(1)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_text("Mask From Layer Matrix")
+                        .build(ctx);
+                        mask_from_matrix
                     })
-                    .with_child(make_text_mark(ctx, "Is Sensor", 6))
+                    .with_child(make_text_mark(ctx, "Is Sensor", 8))
                     .with_child({
-                        is_sensor = make_bool_input_field(ctx, 6);
+                        is_sensor = make_bool_input_field(ctx, 8);
                         is_sensor
                     }),
             )
@@ -100,6 +213,8 @@ impl ColliderSection {
             .add_row(Row::strict(ROW_HEIGHT))
             .add_row(Row::strict(ROW_HEIGHT))
             .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::auto())
+            .add_row(Row::auto())
             .add_row(Row::strict(ROW_HEIGHT))
             .add_row(Row::strict(ROW_HEIGHT))
             .build(ctx),
@@ -111,15 +226,23 @@ impl ColliderSection {
             sender,
             friction,
             restitution,
+            material,
             position,
             rotation,
             is_sensor,
             collision_mask,
             collision_groups,
+            mask_from_matrix,
         }
     }
 
-    pub fn sync_to_model(&mut self, collider: &Collider, ui: &mut UserInterface) {
+    pub fn sync_to_model(
+        &mut self,
+        collider: &Collider,
+        settings: &PhysicsSettings,
+        materials: &PhysicsMaterialLibrary,
+        ui: &mut UserInterface,
+    ) {
         send_sync_message(
             ui,
             NumericUpDownMessage::value(
@@ -138,6 +261,15 @@ impl ColliderSection {
             ),
         );
 
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.material,
+                MessageDirection::ToWidget,
+                materials.find(collider.friction, collider.restitution),
+            ),
+        );
+
         send_sync_message(
             ui,
             Vec3EditorMessage::value(
@@ -167,23 +299,10 @@ impl ColliderSection {
             ),
         );
 
-        send_sync_message(
-            ui,
-            NumericUpDownMessage::value(
-                self.collision_groups,
-                MessageDirection::ToWidget,
-                collider.collision_groups.memberships as f32,
-            ),
-        );
-
-        send_sync_message(
-            ui,
-            NumericUpDownMessage::value(
-                self.collision_mask,
-                MessageDirection::ToWidget,
-                collider.collision_groups.filter as f32,
-            ),
-        );
+        self.collision_groups
+            .sync_to_model(collider.collision_groups.memberships, settings, ui);
+        self.collision_mask
+            .sync_to_model(collider.collision_groups.filter, settings, ui);
     }
 
     pub fn handle_message(
@@ -191,8 +310,30 @@ impl ColliderSection {
         message: &UiMessage,
         collider: &Collider,
         handle: Handle<Collider>,
+        settings: &PhysicsSettings,
+        materials: &PhysicsMaterialLibrary,
     ) {
         if message.direction() == MessageDirection::FromWidget {
+            if let Some(groups) = self
+                .collision_groups
+                .handle_message(message, collider.collision_groups.memberships)
+            {
+                self.sender
+                    .send(Message::do_scene_command(
+                        SetColliderCollisionGroupsMembershipsCommand::new(handle, groups),
+                    ))
+                    .unwrap();
+            } else if let Some(mask) = self
+                .collision_mask
+                .handle_message(message, collider.collision_groups.filter)
+            {
+                self.sender
+                    .send(Message::do_scene_command(
+                        SetColliderCollisionGroupsFilterCommand::new(handle, mask),
+                    ))
+                    .unwrap();
+            }
+
             match message.data() {
                 &UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
                     if message.destination() == self.friction && collider.friction.ne(&value) {
@@ -209,21 +350,33 @@ impl ColliderSection {
                                 SetColliderRestitutionCommand::new(handle, value),
                             ))
                             .unwrap();
-                    } else if message.destination() == self.collision_mask {
-                        let mask = collider.collision_groups.filter | (value as u32);
+                    }
+                }
+                &UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(Some(
+                    index,
+                ))) if message.destination() == self.material => {
+                    if let Some(material) = materials.materials.get(index) {
+                        let commands = make_apply_commands(collider, handle, material);
+                        if !commands.is_empty() {
+                            self.sender
+                                .send(Message::do_scene_command(CommandGroup::from(commands)))
+                                .unwrap();
+                        }
+                    }
+                }
+                UiMessageData::Button(ButtonMessage::Click)
+                    if message.destination() == self.mask_from_matrix =>
+                {
+                    // Bits above named layers are kept as is.
+                    let old_mask = collider.collision_groups.filter;
+                    let mask = (old_mask & !LAYERS_MASK)
+                        | settings.filter_of(collider.collision_groups.memberships);
+                    if mask != old_mask {
                         self.sender
                             .send(Message::do_scene_command(
                                 SetColliderCollisionGroupsFilterCommand::new(handle, mask),
                             ))
                             .unwrap();
-                    } else if message.destination() == self.collision_groups {
-                        let groups =
-                            (collider.collision_groups.memberships & 0x0000FFFF) | value as u32;
-                        self.sender
-                            .send(Message::do_scene_command(
-                                SetColliderCollisionGroupsMembershipsCommand::new(handle, groups),
-                            ))
-                            .unwrap();
                     }
                 }
                 UiMessageData::Vec3Editor(Vec3EditorMessage::Value(value)) => {
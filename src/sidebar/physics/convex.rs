@@ -0,0 +1,381 @@
+//! Window that generates colliders from convex hulls of meshes of a node. Result is shown
+//! in the scene before it is committed as a single command.
+//!
+//! Engine has no convex polyhedron shape, so every hull becomes a cuboid collider oriented
+//! along principal axes of the hull. With a single hull it is a tight box around the mesh,
+//! with many hulls the body becomes a compound of boxes that follows concave meshes.
+
+use crate::{
+    physics::{
+        convex::{self, DecompositionParameters, Hull, OrientedBox},
+        Collider,
+    },
+    scene::{
+        commands::{
+            physics::{DeleteColliderCommand, SetColliderCommand},
+            CommandGroup, SceneCommand,
+        },
+        EditorScene,
+    },
+    send_sync_message,
+    sidebar::{
+        make_bool_input_field, make_int_input_field, make_text_mark, COLUMN_WIDTH, ROW_HEIGHT,
+    },
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::{
+        algebra::{Isometry3, Matrix4, Point3, Translation3, Vector3},
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        scope_profile,
+    },
+    gui::{
+        button::ButtonBuilder,
+        grid::{Column, GridBuilder, Row},
+        message::{
+            ButtonMessage, CheckBoxMessage, MessageDirection, NumericUpDownMessage, UiMessageData,
+            WindowMessage,
+        },
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+        HorizontalAlignment, Orientation, Thickness,
+    },
+    physics3d::desc::{ColliderShapeDesc, CuboidDesc},
+    scene::{
+        debug::SceneDrawingContext,
+        graph::Graph,
+        mesh::buffer::{VertexAttributeUsage, VertexReadTrait},
+        node::Node,
+    },
+};
+use std::sync::mpsc::Sender;
+
+struct Preview {
+    node: Handle<Node>,
+    /// Hulls in local space of the node.
+    hulls: Vec<Hull>,
+    /// Boxes of hulls, these become colliders.
+    boxes: Vec<OrientedBox>,
+}
+
+pub struct ConvexGenerator {
+    pub window: Handle<UiNode>,
+    max_hulls: Handle<UiNode>,
+    resolution: Handle<UiNode>,
+    replace_field: Handle<UiNode>,
+    preview_button: Handle<UiNode>,
+    apply: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    parameters: DecompositionParameters,
+    replace: bool,
+    preview: Option<Preview>,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(60.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn node_transform(graph: &Graph, node: Handle<Node>) -> Matrix4<f32> {
+    let (rotation, position) = graph.isometric_global_rotation_position(node);
+    Isometry3 {
+        rotation,
+        translation: Translation3 { vector: position },
+    }
+    .to_homogeneous()
+}
+
+/// Collects vertices and triangles of every mesh in hierarchy of the node. Vertices are
+/// in local space of the node (without scale), which is local space of its body.
+fn collect_geometry(graph: &Graph, node: Handle<Node>) -> (Vec<Vector3<f32>>, Vec<[usize; 3]>) {
+    let inv_transform = node_transform(graph, node)
+        .try_inverse()
+        .unwrap_or_else(Matrix4::identity);
+
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+    for descendant in graph.traverse_handle_iter(node) {
+        if let Node::Mesh(mesh) = &graph[descendant] {
+            let transform = inv_transform * mesh.global_transform();
+            for surface in mesh.surfaces() {
+                let data = surface.data();
+                let data = data.read().unwrap();
+
+                let base = vertices.len();
+                for vertex in data.vertex_buffer.iter() {
+                    vertices.push(
+                        transform
+                            .transform_point(&Point3::from(
+                                vertex.read_3_f32(VertexAttributeUsage::Position).unwrap(),
+                            ))
+                            .coords,
+                    );
+                }
+                for triangle in data.geometry_buffer.triangles_ref() {
+                    triangles.push([
+                        base + triangle[0] as usize,
+                        base + triangle[1] as usize,
+                        base + triangle[2] as usize,
+                    ]);
+                }
+            }
+        }
+    }
+
+    (vertices, triangles)
+}
+
+impl ConvexGenerator {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let max_hulls;
+        let resolution;
+        let replace_field;
+        let preview_button;
+        let apply;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(160.0))
+            .with_title(WindowTitle::text("Convex Decomposition"))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(make_text_mark(ctx, "Max Hulls", 0))
+                        .with_child({
+                            max_hulls = make_int_input_field(ctx, 0, 1, 64, 1);
+                            max_hulls
+                        })
+                        .with_child(make_text_mark(ctx, "Resolution", 1))
+                        .with_child({
+                            resolution = make_int_input_field(ctx, 1, 4, 128, 1);
+                            resolution
+                        })
+                        .with_child(make_text_mark(ctx, "Replace Colliders", 2))
+                        .with_child({
+                            replace_field = make_bool_input_field(ctx, 2);
+                            replace_field
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(3)
+                                    .on_column(1)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        preview_button = make_button(ctx, "Preview");
+                                        preview_button
+                                    })
+                                    .with_child({
+                                        apply = make_button(ctx, "Apply");
+                                        apply
+                                    })
+                                    .with_child({
+                                        cancel = make_button(ctx, "Cancel");
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::strict(COLUMN_WIDTH))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(28.0))
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            max_hulls,
+            resolution,
+            replace_field,
+            preview_button,
+            apply,
+            cancel,
+            parameters: Default::default(),
+            replace: true,
+            preview: None,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(
+                self.max_hulls,
+                MessageDirection::ToWidget,
+                self.parameters.max_hulls as f32,
+            ),
+        );
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(
+                self.resolution,
+                MessageDirection::ToWidget,
+                self.parameters.resolution as f32,
+            ),
+        );
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(
+                self.replace_field,
+                MessageDirection::ToWidget,
+                Some(self.replace),
+            ),
+        );
+    }
+
+    fn update_preview(&mut self, graph: &Graph, node: Handle<Node>) {
+        let (vertices, triangles) = collect_geometry(graph, node);
+        let hulls = convex::decompose(&vertices, &triangles, &self.parameters);
+        self.preview = Some(Preview {
+            node,
+            boxes: hulls.iter().map(|h| h.oriented_box()).collect(),
+            hulls,
+        });
+    }
+
+    fn make_colliders(boxes: &[OrientedBox]) -> Vec<Collider> {
+        boxes
+            .iter()
+            .map(|b| Collider {
+                shape: ColliderShapeDesc::Cuboid(CuboidDesc {
+                    half_extents: b.half_extents,
+                }),
+                translation: b.center,
+                rotation: b.rotation,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Draws hulls and boxes that will become colliders.
+    pub fn draw(&self, context: &mut SceneDrawingContext, graph: &Graph) {
+        if let Some(preview) = self.preview.as_ref() {
+            if !graph.is_valid_handle(preview.node) {
+                return;
+            }
+
+            let transform = node_transform(graph, preview.node);
+
+            let hull_color = Color::opaque(120, 100, 0);
+            for hull in preview.hulls.iter() {
+                for face in hull.faces.iter() {
+                    let vertex = |i: usize| {
+                        transform
+                            .transform_point(&Point3::from(hull.vertices[face[i]]))
+                            .coords
+                    };
+                    context.draw_triangle(vertex(0), vertex(1), vertex(2), hull_color);
+                }
+            }
+
+            let box_color = Color::opaque(255, 200, 0);
+            for b in preview.boxes.iter() {
+                let box_transform = transform
+                    * Isometry3 {
+                        rotation: b.rotation,
+                        translation: Translation3 { vector: b.center },
+                    }
+                    .to_homogeneous();
+                context.draw_oob(
+                    &AxisAlignedBoundingBox::from_min_max(-b.half_extents, b.half_extents),
+                    box_transform,
+                    box_color,
+                );
+            }
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        graph: &Graph,
+        node: Handle<Node>,
+        ui: &UserInterface,
+        sender: &Sender<Message>,
+    ) {
+        scope_profile!();
+
+        match message.data() {
+            UiMessageData::Button(ButtonMessage::Click) => {
+                if message.destination() == self.preview_button {
+                    self.update_preview(graph, node);
+                } else if message.destination() == self.apply {
+                    if let Some(&body) = editor_scene.physics.binder.value_of(&node) {
+                        if self.preview.as_ref().map_or(true, |p| p.node != node) {
+                            self.update_preview(graph, node);
+                        }
+
+                        let boxes = self.preview.take().map(|p| p.boxes).unwrap_or_default();
+
+                        let mut commands = Vec::new();
+                        if self.replace {
+                            for &collider in editor_scene.physics.bodies[body].colliders.iter() {
+                                commands.push(SceneCommand::new(DeleteColliderCommand::new(
+                                    collider.into(),
+                                )));
+                            }
+                        }
+                        for collider in Self::make_colliders(&boxes) {
+                            commands
+                                .push(SceneCommand::new(SetColliderCommand::new(body, collider)));
+                        }
+
+                        if !commands.is_empty() {
+                            sender
+                                .send(Message::do_scene_command(CommandGroup::from(commands)))
+                                .unwrap();
+                        }
+                    }
+
+                    ui.send_message(WindowMessage::close(
+                        self.window,
+                        MessageDirection::ToWidget,
+                    ));
+                } else if message.destination() == self.cancel {
+                    ui.send_message(WindowMessage::close(
+                        self.window,
+                        MessageDirection::ToWidget,
+                    ));
+                }
+            }
+            UiMessageData::Window(WindowMessage::Close) if message.destination() == self.window => {
+                self.preview = None;
+            }
+            UiMessageData::CheckBox(CheckBoxMessage::Check(Some(value)))
+                if message.destination() == self.replace_field =>
+            {
+                self.replace = *value;
+            }
+            UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
+                if message.destination() == self.max_hulls {
+                    self.parameters.max_hulls = value.max(1.0) as usize;
+                } else if message.destination() == self.resolution {
+                    self.parameters.resolution = value.max(1.0) as usize;
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::sidebar::make_section;
+use crate::{
+    physics::{Collider, Physics},
+    send_sync_message,
+    sidebar::{make_text_mark, physics::source::SourceSelector, COLUMN_WIDTH, ROW_HEIGHT},
+    terrain::ChunkLayout,
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::pool::Handle,
+    gui::{
+        grid::{Column, GridBuilder, Row},
+        message::{DropdownListMessage, MessageDirection, TextMessage, UiMessageData},
+        text::TextBuilder,
+        widget::WidgetBuilder,
+        Thickness, VerticalAlignment,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::sync::mpsc::Sender;
+
+pub struct HeightfieldSection {
+    pub section: Handle<UiNode>,
+    source: SourceSelector,
+    size: Handle<UiNode>,
+    sender: Sender<Message>,
+}
+
+impl HeightfieldSection {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let source = SourceSelector::new(ctx, 0, Node::is_terrain);
+        let size;
+        let section = make_section(
+            "Heightfield Properties",
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(make_text_mark(ctx, "Build From Terrain", 0))
+                    .with_child(source.dropdown)
+                    .with_child(make_text_mark(ctx, "Height Map Size", 1))
+                    .with_child({
+                        size = TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_vertical_alignment(VerticalAlignment::Center)
+                                .with_margin(Thickness::left(4.0))
+                                .on_row(1)
+                                .on_column(1),
+                        )
+                        .build(ctx);
+                        size
+                    }),
+            )
+            .add_column(Column::strict(COLUMN_WIDTH))
+            .add_column(Column::stretch())
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .build(ctx),
+            ctx,
+        );
+
+        Self {
+            section,
+            source,
+            size,
+            sender,
+        }
+    }
+
+    pub fn sync_to_model(
+        &mut self,
+        graph: &Graph,
+        editor_root: Handle<Node>,
+        physics: &Physics,
+        collider: Handle<Collider>,
+        ui: &mut UserInterface,
+    ) {
+        self.source
+            .sync_to_model(graph, editor_root, physics, collider, ui);
+
+        let node = physics.collider_source(collider);
+
+        let text = if let Some(Node::Terrain(terrain)) = graph.try_get(node) {
+            let layout = ChunkLayout::of_terrain(terrain);
+            format!("{}x{}", layout.grid_width(), layout.grid_length())
+        } else {
+            "Node is not a terrain!".to_owned()
+        };
+
+        send_sync_message(
+            ui,
+            TextMessage::text(self.size, MessageDirection::ToWidget, text),
+        );
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        physics: &Physics,
+        collider: Handle<Collider>,
+    ) {
+        if let UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(Some(index))) =
+            message.data()
+        {
+            if message.destination() == self.source.dropdown {
+                self.source.select(*index, physics, collider, &self.sender);
+            }
+        }
+    }
+}
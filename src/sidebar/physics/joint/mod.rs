@@ -1,7 +1,11 @@
 use crate::sidebar::make_section;
 use crate::{
-    physics::{Joint, RigidBody},
-    scene::commands::physics::SetJointConnectedBodyCommand,
+    interaction::InteractionModeKind,
+    physics::{Joint, JointAnchor, JointAnchorSelection, RigidBody},
+    scene::{
+        commands::{physics::SetJointConnectedBodyCommand, ChangeSelectionCommand},
+        Selection,
+    },
     send_sync_message,
     sidebar::{
         make_text_mark,
@@ -21,14 +25,17 @@ use rg3d::{
     gui::{
         border::BorderBuilder,
         brush::Brush,
+        button::ButtonBuilder,
         decorator::DecoratorBuilder,
         dropdown_list::DropdownListBuilder,
         grid::{Column, GridBuilder, Row},
-        message::{DropdownListMessage, MessageDirection, UiMessageData, WidgetMessage},
+        message::{
+            ButtonMessage, DropdownListMessage, MessageDirection, UiMessageData, WidgetMessage,
+        },
         stack_panel::StackPanelBuilder,
         text::TextBuilder,
         widget::WidgetBuilder,
-        HorizontalAlignment, VerticalAlignment,
+        HorizontalAlignment, Orientation, Thickness, VerticalAlignment,
     },
     physics3d::desc::JointParamsDesc,
     scene::{graph::Graph, node::Node},
@@ -44,6 +51,8 @@ pub struct JointSection {
     pub section: Handle<UiNode>,
     connected_body: Handle<UiNode>,
     connected_body_text: Handle<UiNode>,
+    move_joint_anchor: Handle<UiNode>,
+    move_connected_anchor: Handle<UiNode>,
     sender: Sender<Message>,
     ball_section: BallJointSection,
     fixed_section: FixedJointSection,
@@ -56,6 +65,8 @@ impl JointSection {
     pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
         let connected_body;
         let connected_body_text;
+        let move_joint_anchor;
+        let move_connected_anchor;
         let ball_section = BallJointSection::new(ctx, sender.clone());
         let fixed_section = FixedJointSection::new(ctx, sender.clone());
         let revolute_section = RevoluteJointSection::new(ctx, sender.clone());
@@ -75,11 +86,40 @@ impl JointSection {
                                     DropdownListBuilder::new(WidgetBuilder::new().on_column(1))
                                         .build(ctx);
                                 connected_body
-                            }),
+                            })
+                            .with_child(make_text_mark(ctx, "Move Anchor", 1))
+                            .with_child(
+                                StackPanelBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_row(1)
+                                        .on_column(1)
+                                        .with_child({
+                                            move_joint_anchor = ButtonBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_margin(Thickness::uniform(1.0)),
+                                            )
+                                            .with_text("Joint")
+                                            .build(ctx);
+                                            move_joint_anchor
+                                        })
+                                        .with_child({
+                                            move_connected_anchor = ButtonBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_margin(Thickness::uniform(1.0)),
+                                            )
+                                            .with_text("Connected")
+                                            .build(ctx);
+                                            move_connected_anchor
+                                        }),
+                                )
+                                .with_orientation(Orientation::Horizontal)
+                                .build(ctx),
+                            ),
                     )
                     .add_column(Column::strict(COLUMN_WIDTH))
                     .add_column(Column::stretch())
                     .add_row(Row::strict(ROW_HEIGHT))
+                    .add_row(Row::strict(ROW_HEIGHT))
                     .build(ctx),
                     ball_section.section,
                     fixed_section.section,
@@ -96,6 +136,8 @@ impl JointSection {
             sender,
             connected_body_text,
             connected_body,
+            move_joint_anchor,
+            move_connected_anchor,
             ball_section,
             fixed_section,
             revolute_section,
@@ -201,7 +243,13 @@ impl JointSection {
         );
     }
 
-    pub fn handle_message(&mut self, message: &UiMessage, joint: &Joint, handle: Handle<Joint>) {
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        joint: &Joint,
+        handle: Handle<Joint>,
+        selection: &Selection,
+    ) {
         match &joint.params {
             JointParamsDesc::BallJoint(ball) => {
                 self.ball_section.handle_message(message, ball, handle);
@@ -219,6 +267,32 @@ impl JointSection {
             }
         }
 
+        if let UiMessageData::Button(ButtonMessage::Click) = message.data() {
+            let anchor = if message.destination() == self.move_joint_anchor {
+                Some(JointAnchor::First)
+            } else if message.destination() == self.move_connected_anchor && joint.body2.is_some() {
+                Some(JointAnchor::Second)
+            } else {
+                None
+            };
+
+            // Anchor is selected as standalone entity, so it can be dragged by move gizmo.
+            if let Some(anchor) = anchor {
+                self.sender
+                    .send(Message::do_scene_command(ChangeSelectionCommand::new(
+                        Selection::JointAnchor(JointAnchorSelection {
+                            joint: handle,
+                            anchor,
+                        }),
+                        selection.clone(),
+                    )))
+                    .unwrap();
+                self.sender
+                    .send(Message::SetInteractionMode(InteractionModeKind::Move))
+                    .unwrap();
+            }
+        }
+
         if let UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(Some(index))) =
             *message.data()
         {
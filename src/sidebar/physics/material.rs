@@ -0,0 +1,227 @@
+//! Window that applies a physics material preset to every collider of every selected node
+//! at once.
+
+use crate::gui::make_dropdown_list_option;
+use crate::{
+    physics::{
+        material::{PhysicsMaterial, PhysicsMaterialLibrary},
+        Collider, Physics,
+    },
+    scene::{
+        commands::{
+            physics::{SetColliderFrictionCommand, SetColliderRestitutionCommand},
+            CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+    send_sync_message,
+    sidebar::{make_text_mark, COLUMN_WIDTH, ROW_HEIGHT},
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::{pool::Handle, scope_profile},
+    gui::{
+        button::ButtonBuilder,
+        dropdown_list::DropdownListBuilder,
+        grid::{Column, GridBuilder, Row},
+        message::{
+            ButtonMessage, DropdownListMessage, MessageDirection, UiMessageData, WindowMessage,
+        },
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+        HorizontalAlignment, Orientation, Thickness,
+    },
+    scene::node::Node,
+};
+use std::sync::mpsc::Sender;
+
+/// Replaces items of a dropdown list with names of presets of the library.
+pub fn set_preset_items(
+    ui: &mut UserInterface,
+    dropdown: Handle<UiNode>,
+    library: &PhysicsMaterialLibrary,
+) {
+    let ctx = &mut ui.build_ctx();
+    let items = library
+        .materials
+        .iter()
+        .map(|material| make_dropdown_list_option(ctx, &material.name))
+        .collect();
+    send_sync_message(
+        ui,
+        DropdownListMessage::items(dropdown, MessageDirection::ToWidget, items),
+    );
+}
+
+/// Makes commands that set properties of the material to the collider. Properties that
+/// already match are skipped.
+pub fn make_apply_commands(
+    collider: &Collider,
+    handle: Handle<Collider>,
+    material: &PhysicsMaterial,
+) -> Vec<SceneCommand> {
+    let mut commands = Vec::new();
+    if collider.friction.ne(&material.friction) {
+        commands.push(SceneCommand::new(SetColliderFrictionCommand::new(
+            handle,
+            material.friction,
+        )));
+    }
+    if collider.restitution.ne(&material.restitution) {
+        commands.push(SceneCommand::new(SetColliderRestitutionCommand::new(
+            handle,
+            material.restitution,
+        )));
+    }
+    commands
+}
+
+pub struct MaterialPresetWindow {
+    pub window: Handle<UiNode>,
+    pub preset: Handle<UiNode>,
+    apply: Handle<UiNode>,
+    close: Handle<UiNode>,
+    selected: Option<usize>,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(80.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl MaterialPresetWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let preset;
+        let apply;
+        let close;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(90.0))
+            .with_title(WindowTitle::text("Apply Physics Material"))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(make_text_mark(ctx, "Material", 0))
+                        .with_child({
+                            preset = DropdownListBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_close_on_selection(true)
+                            .build(ctx);
+                            preset
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .on_column(1)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        apply = make_button(ctx, "Apply");
+                                        apply
+                                    })
+                                    .with_child({
+                                        close = make_button(ctx, "Close");
+                                        close
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::strict(COLUMN_WIDTH))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(28.0))
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            preset,
+            apply,
+            close,
+            selected: None,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        library: &PhysicsMaterialLibrary,
+        ui: &UserInterface,
+        sender: &Sender<Message>,
+    ) {
+        scope_profile!();
+
+        match message.data() {
+            UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(index))
+                if message.destination() == self.preset
+                    && message.direction() == MessageDirection::FromWidget =>
+            {
+                self.selected = *index;
+            }
+            UiMessageData::Button(ButtonMessage::Click) if message.destination() == self.apply => {
+                if let (Some(material), Selection::Graph(selection)) = (
+                    self.selected.and_then(|index| library.materials.get(index)),
+                    &editor_scene.selection,
+                ) {
+                    let commands =
+                        apply_to_nodes(&editor_scene.physics, selection.nodes(), material);
+                    if !commands.is_empty() {
+                        sender
+                            .send(Message::do_scene_command(CommandGroup::from(commands)))
+                            .unwrap();
+                    }
+                }
+            }
+            UiMessageData::Button(ButtonMessage::Click) if message.destination() == self.close => {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn apply_to_nodes(
+    physics: &Physics,
+    nodes: &[Handle<Node>],
+    material: &PhysicsMaterial,
+) -> Vec<SceneCommand> {
+    let mut commands = Vec::new();
+    for node in nodes {
+        if let Some(&body) = physics.binder.value_of(node) {
+            for &collider in physics.bodies[body].colliders.iter() {
+                commands.extend(make_apply_commands(
+                    &physics.colliders[collider.into()],
+                    collider.into(),
+                    material,
+                ));
+            }
+        }
+    }
+    commands
+}
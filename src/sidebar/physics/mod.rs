@@ -2,23 +2,38 @@ use crate::gui::make_dropdown_list_option;
 use crate::scene::commands::SceneCommand;
 use crate::sidebar::make_section;
 use crate::{
-    physics::{Collider, Joint, RigidBody},
+    physics::{material::PhysicsMaterialLibrary, Collider, Joint, RigidBody},
     scene::commands::CommandGroup,
     scene::{
         commands::physics::{
             AddJointCommand, DeleteBodyCommand, DeleteColliderCommand, DeleteJointCommand,
-            SetBallRadiusCommand, SetBodyCommand, SetColliderCommand, SetColliderPositionCommand,
-            SetCuboidHalfExtentsCommand, SetCylinderHalfHeightCommand, SetCylinderRadiusCommand,
+            SetBallRadiusCommand, SetBodyCommand, SetBodyStatusCommand, SetColliderCommand,
+            SetColliderPositionCommand, SetCuboidHalfExtentsCommand, SetCylinderHalfHeightCommand,
+            SetCylinderRadiusCommand, SetRoundCylinderHalfHeightCommand,
+            SetRoundCylinderRadiusCommand,
         },
         EditorScene, Selection,
     },
     send_sync_message,
+    settings::physics::PhysicsSettings,
     sidebar::{
         make_text_mark,
         physics::{
-            ball::BallSection, body::BodySection, capsule::CapsuleSection,
-            collider::ColliderSection, cone::ConeSection, cuboid::CuboidSection,
-            cylinder::CylinderSection, joint::JointSection,
+            ball::BallSection,
+            body::BodySection,
+            capsule::CapsuleSection,
+            collider::ColliderSection,
+            cone::ConeSection,
+            convex::ConvexGenerator,
+            cuboid::CuboidSection,
+            cylinder::CylinderSection,
+            heightfield::HeightfieldSection,
+            joint::JointSection,
+            material::{set_preset_items, MaterialPresetWindow},
+            round_cylinder::RoundCylinderSection,
+            segment::SegmentSection,
+            triangle::TriangleSection,
+            trimesh::TrimeshSection,
         },
         COLUMN_WIDTH, ROW_HEIGHT,
     },
@@ -28,17 +43,25 @@ use rg3d::gui::message::UiMessage;
 use rg3d::gui::{BuildContext, UiNode, UserInterface};
 use rg3d::{
     core::{
-        algebra::Matrix4, algebra::Vector3, math::aabb::AxisAlignedBoundingBox, pool::Handle,
+        algebra::Matrix4,
+        algebra::{Vector2, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
         scope_profile,
     },
     gui::{
+        border::BorderBuilder,
         button::ButtonBuilder,
+        decorator::DecoratorBuilder,
         dropdown_list::DropdownListBuilder,
         grid::{Column, GridBuilder, Row},
+        list_view::ListViewBuilder,
         message::{
-            ButtonMessage, DropdownListMessage, MessageDirection, UiMessageData, WidgetMessage,
+            ButtonMessage, DropdownListMessage, ListViewMessage, MessageDirection, UiMessageData,
+            WidgetMessage,
         },
         stack_panel::StackPanelBuilder,
+        text::TextBuilder,
         widget::WidgetBuilder,
         Orientation, Thickness,
     },
@@ -57,48 +80,115 @@ mod body;
 mod capsule;
 mod collider;
 mod cone;
+mod convex;
 mod cuboid;
 mod cylinder;
+mod heightfield;
 mod joint;
+mod material;
+mod round_cylinder;
 mod segment;
+mod source;
 mod triangle;
 mod trimesh;
 
 pub struct PhysicsSection {
     pub section: Handle<UiNode>,
     body: Handle<UiNode>,
+    colliders: Handle<UiNode>,
+    colliders_text: Handle<UiNode>,
+    collider_buttons: Handle<UiNode>,
+    add_collider: Handle<UiNode>,
+    remove_collider: Handle<UiNode>,
     collider: Handle<UiNode>,
     collider_text: Handle<UiNode>,
     joint: Handle<UiNode>,
     joint_text: Handle<UiNode>,
     fit: Handle<UiNode>,
+    convex: Handle<UiNode>,
     sender: Sender<Message>,
     pub body_section: BodySection,
     pub collider_section: ColliderSection,
     pub cylinder_section: CylinderSection,
+    pub round_cylinder_section: RoundCylinderSection,
     pub cone_section: ConeSection,
     pub cuboid_section: CuboidSection,
     pub capsule_section: CapsuleSection,
     pub ball_section: BallSection,
+    pub segment_section: SegmentSection,
+    pub triangle_section: TriangleSection,
+    pub trimesh_section: TrimeshSection,
+    pub heightfield_section: HeightfieldSection,
     pub joint_section: JointSection,
+    pub convex_generator: ConvexGenerator,
+    pub material_window: MaterialPresetWindow,
+    /// Physics material presets of current working directory.
+    pub materials: PhysicsMaterialLibrary,
+    /// Index of a collider of selected body that is being edited.
+    selected_collider: usize,
+}
+
+fn shape_name(shape: &ColliderShapeDesc) -> &'static str {
+    match shape {
+        ColliderShapeDesc::Ball(_) => "Ball",
+        ColliderShapeDesc::Cylinder(_) => "Cylinder",
+        ColliderShapeDesc::RoundCylinder(_) => "Round Cylinder",
+        ColliderShapeDesc::Cone(_) => "Cone",
+        ColliderShapeDesc::Cuboid(_) => "Cuboid",
+        ColliderShapeDesc::Capsule(_) => "Capsule",
+        ColliderShapeDesc::Segment(_) => "Segment",
+        ColliderShapeDesc::Triangle(_) => "Triangle",
+        ColliderShapeDesc::Trimesh(_) => "Trimesh",
+        ColliderShapeDesc::Heightfield(_) => "Heightfield",
+    }
+}
+
+/// Returns index of body's status in the body list, zero is reserved for "None".
+fn body_status_index(body: &RigidBody) -> usize {
+    match body.status {
+        RigidBodyTypeDesc::Dynamic => 1,
+        RigidBodyTypeDesc::Static => 2,
+        RigidBodyTypeDesc::KinematicPositionBased => 3,
+        RigidBodyTypeDesc::KinematicVelocityBased => 4,
+    }
+}
+
+fn toggle_visibility(ui: &mut UserInterface, destination: Handle<UiNode>, value: bool) {
+    send_sync_message(
+        ui,
+        WidgetMessage::visibility(destination, MessageDirection::ToWidget, value),
+    );
 }
 
 impl PhysicsSection {
     pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
         let body;
+        let colliders;
+        let colliders_text;
+        let collider_buttons;
+        let add_collider;
+        let remove_collider;
         let collider;
         let collider_text;
         let joint;
         let joint_text;
         let fit;
+        let convex;
         let body_section = BodySection::new(ctx, sender.clone());
         let collider_section = ColliderSection::new(ctx, sender.clone());
         let cylinder_section = CylinderSection::new(ctx, sender.clone());
+        let round_cylinder_section = RoundCylinderSection::new(ctx, sender.clone());
         let cone_section = ConeSection::new(ctx, sender.clone());
         let cuboid_section = CuboidSection::new(ctx, sender.clone());
         let capsule_section = CapsuleSection::new(ctx, sender.clone());
         let ball_section = BallSection::new(ctx, sender.clone());
+        let segment_section = SegmentSection::new(ctx, sender.clone());
+        let triangle_section = TriangleSection::new(ctx, sender.clone());
+        let trimesh_section = TrimeshSection::new(ctx, sender.clone());
+        let heightfield_section = HeightfieldSection::new(ctx, sender.clone());
         let joint_section = JointSection::new(ctx, sender.clone());
+        let material_window = MaterialPresetWindow::new(ctx);
+        let convex_generator = ConvexGenerator::new(ctx);
         let section = make_section(
             "Physics Properties",
             StackPanelBuilder::new(
@@ -126,13 +216,56 @@ impl PhysicsSection {
                                     body
                                 })
                                 .with_child({
-                                    collider_text = make_text_mark(ctx, "Collider", 1);
+                                    colliders_text = make_text_mark(ctx, "Colliders", 1);
+                                    colliders_text
+                                })
+                                .with_child({
+                                    collider_buttons = StackPanelBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_row(1)
+                                            .on_column(1)
+                                            .with_child({
+                                                add_collider = ButtonBuilder::new(
+                                                    WidgetBuilder::new()
+                                                        .with_margin(Thickness::uniform(1.0)),
+                                                )
+                                                .with_text("Add Collider")
+                                                .build(ctx);
+                                                add_collider
+                                            })
+                                            .with_child({
+                                                remove_collider = ButtonBuilder::new(
+                                                    WidgetBuilder::new()
+                                                        .with_margin(Thickness::uniform(1.0)),
+                                                )
+                                                .with_text("Remove Collider")
+                                                .build(ctx);
+                                                remove_collider
+                                            }),
+                                    )
+                                    .with_orientation(Orientation::Horizontal)
+                                    .build(ctx);
+                                    collider_buttons
+                                })
+                                .with_child({
+                                    colliders = ListViewBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_min_size(Vector2::new(0.0, ROW_HEIGHT * 3.0))
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .on_row(2)
+                                            .on_column(1),
+                                    )
+                                    .build(ctx);
+                                    colliders
+                                })
+                                .with_child({
+                                    collider_text = make_text_mark(ctx, "Shape", 3);
                                     collider_text
                                 })
                                 .with_child({
                                     collider = DropdownListBuilder::new(
                                         WidgetBuilder::new()
-                                            .on_row(1)
+                                            .on_row(3)
                                             .on_column(1)
                                             .with_margin(Thickness::uniform(1.0)),
                                     )
@@ -153,13 +286,13 @@ impl PhysicsSection {
                                     collider
                                 })
                                 .with_child({
-                                    joint_text = make_text_mark(ctx, "Joint", 2);
+                                    joint_text = make_text_mark(ctx, "Joint", 4);
                                     joint_text
                                 })
                                 .with_child({
                                     joint = DropdownListBuilder::new(
                                         WidgetBuilder::new()
-                                            .on_row(2)
+                                            .on_row(4)
                                             .on_column(1)
                                             .with_margin(Thickness::uniform(1.0)),
                                     )
@@ -178,18 +311,32 @@ impl PhysicsSection {
                                     fit = ButtonBuilder::new(
                                         WidgetBuilder::new()
                                             .with_margin(Thickness::uniform(1.0))
-                                            .on_row(3)
+                                            .on_row(5)
                                             .on_column(1),
                                     )
                                     .with_text("Fit Collider")
                                     .build(ctx);
                                     fit
+                                })
+                                .with_child({
+                                    convex = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .on_row(6)
+                                            .on_column(1),
+                                    )
+                                    .with_text("Convex Decomposition...")
+                                    .build(ctx);
+                                    convex
                                 }),
                         )
                         .add_column(Column::strict(COLUMN_WIDTH))
                         .add_column(Column::stretch())
                         .add_row(Row::strict(ROW_HEIGHT))
                         .add_row(Row::strict(ROW_HEIGHT))
+                        .add_row(Row::auto())
+                        .add_row(Row::strict(ROW_HEIGHT))
+                        .add_row(Row::strict(ROW_HEIGHT))
                         .add_row(Row::strict(ROW_HEIGHT))
                         .add_row(Row::strict(ROW_HEIGHT))
                         .build(ctx),
@@ -198,10 +345,15 @@ impl PhysicsSection {
                         body_section.section,
                         collider_section.section,
                         cylinder_section.section,
+                        round_cylinder_section.section,
                         cone_section.section,
                         cuboid_section.section,
                         capsule_section.section,
                         ball_section.section,
+                        segment_section.section,
+                        triangle_section.section,
+                        trimesh_section.section,
+                        heightfield_section.section,
                         joint_section.section,
                     ]),
             )
@@ -214,12 +366,22 @@ impl PhysicsSection {
             body_section,
             collider_section,
             cylinder_section,
+            round_cylinder_section,
             cone_section,
             cuboid_section,
             capsule_section,
             ball_section,
+            segment_section,
+            triangle_section,
+            trimesh_section,
+            heightfield_section,
             section,
             body,
+            colliders,
+            colliders_text,
+            collider_buttons,
+            add_collider,
+            remove_collider,
             collider,
             collider_text,
             sender,
@@ -227,10 +389,20 @@ impl PhysicsSection {
             joint,
             joint_text,
             fit,
+            convex,
+            convex_generator,
+            material_window,
+            materials: Default::default(),
+            selected_collider: 0,
         }
     }
 
-    pub fn sync_to_model(&mut self, editor_scene: &EditorScene, engine: &mut GameEngine) {
+    pub fn sync_to_model(
+        &mut self,
+        editor_scene: &EditorScene,
+        engine: &mut GameEngine,
+        settings: &PhysicsSettings,
+    ) {
         if let Selection::Graph(selection) = &editor_scene.selection {
             let scene = &engine.scenes[editor_scene.scene];
 
@@ -243,13 +415,7 @@ impl PhysicsSection {
                     let mut body_index = 0;
                     let mut joint = Handle::NONE;
                     if let Some(&body_handle) = editor_scene.physics.binder.value_of(&node_handle) {
-                        let body = &editor_scene.physics.bodies[body_handle];
-                        body_index = match body.status {
-                            RigidBodyTypeDesc::Dynamic => 1,
-                            RigidBodyTypeDesc::Static => 2,
-                            RigidBodyTypeDesc::KinematicPositionBased => 3,
-                            RigidBodyTypeDesc::KinematicVelocityBased => 4,
-                        };
+                        body_index = body_status_index(&editor_scene.physics.bodies[body_handle]);
                         for (h, j) in editor_scene.physics.joints.pair_iter() {
                             if j.body1 == body_handle.into() {
                                 joint = h;
@@ -267,32 +433,26 @@ impl PhysicsSection {
                         ),
                     );
 
-                    fn toggle_visibility(
-                        ui: &mut UserInterface,
-                        destination: Handle<UiNode>,
-                        value: bool,
-                    ) {
-                        send_sync_message(
-                            ui,
-                            WidgetMessage::visibility(
-                                destination,
-                                MessageDirection::ToWidget,
-                                value,
-                            ),
-                        );
-                    }
-
+                    toggle_visibility(ui, self.colliders, body_index != 0);
+                    toggle_visibility(ui, self.colliders_text, body_index != 0);
+                    toggle_visibility(ui, self.collider_buttons, body_index != 0);
                     toggle_visibility(ui, self.collider, body_index != 0);
                     toggle_visibility(ui, self.collider_text, body_index != 0);
                     toggle_visibility(ui, self.joint_text, body_index != 0);
                     toggle_visibility(ui, self.joint, body_index != 0);
+                    toggle_visibility(ui, self.convex, body_index != 0);
                     toggle_visibility(ui, self.joint_section.section, joint.is_some());
                     toggle_visibility(ui, self.collider_section.section, false);
                     toggle_visibility(ui, self.cylinder_section.section, false);
+                    toggle_visibility(ui, self.round_cylinder_section.section, false);
                     toggle_visibility(ui, self.cone_section.section, false);
                     toggle_visibility(ui, self.cuboid_section.section, false);
                     toggle_visibility(ui, self.capsule_section.section, false);
                     toggle_visibility(ui, self.ball_section.section, false);
+                    toggle_visibility(ui, self.segment_section.section, false);
+                    toggle_visibility(ui, self.triangle_section.section, false);
+                    toggle_visibility(ui, self.trimesh_section.section, false);
+                    toggle_visibility(ui, self.heightfield_section.section, false);
                     toggle_visibility(ui, self.body_section.section, false);
                     toggle_visibility(ui, self.fit, false);
 
@@ -334,62 +494,136 @@ impl PhysicsSection {
                     if let Some(&body_handle) = editor_scene.physics.binder.value_of(&node_handle) {
                         let body = &editor_scene.physics.bodies[body_handle];
 
-                        if let Some(&collider_handle) = body.colliders.first() {
+                        self.selected_collider = self
+                            .selected_collider
+                            .min(body.colliders.len().saturating_sub(1));
+
+                        let items = body
+                            .colliders
+                            .iter()
+                            .enumerate()
+                            .map(|(i, &collider)| {
+                                let shape = &editor_scene.physics.colliders[collider.into()].shape;
+                                DecoratorBuilder::new(BorderBuilder::new(
+                                    WidgetBuilder::new().with_child(
+                                        TextBuilder::new(WidgetBuilder::new())
+                                            .with_text(format!("{}: {}", i, shape_name(shape)))
+                                            .build(&mut ui.build_ctx()),
+                                    ),
+                                ))
+                                .build(&mut ui.build_ctx())
+                            })
+                            .collect::<Vec<_>>();
+
+                        send_sync_message(
+                            ui,
+                            ListViewMessage::items(
+                                self.colliders,
+                                MessageDirection::ToWidget,
+                                items,
+                            ),
+                        );
+                        send_sync_message(
+                            ui,
+                            ListViewMessage::selection(
+                                self.colliders,
+                                MessageDirection::ToWidget,
+                                if body.colliders.is_empty() {
+                                    None
+                                } else {
+                                    Some(self.selected_collider)
+                                },
+                            ),
+                        );
+
+                        if let Some(&collider_handle) = body.colliders.get(self.selected_collider) {
                             let collider = &editor_scene.physics.colliders[collider_handle.into()];
                             toggle_visibility(ui, self.collider_section.section, true);
                             toggle_visibility(ui, self.fit, true);
-                            self.collider_section.sync_to_model(collider, ui);
+                            self.collider_section.sync_to_model(
+                                collider,
+                                settings,
+                                &self.materials,
+                                ui,
+                            );
                         }
 
                         self.body_section.sync_to_model(body, ui);
                         toggle_visibility(ui, self.body_section.section, true);
 
-                        if let Some(&collider) = body.colliders.get(0) {
-                            let collider_index =
-                                match &editor_scene.physics.colliders[collider.into()].shape {
-                                    ColliderShapeDesc::Ball(ball) => {
-                                        toggle_visibility(ui, self.ball_section.section, true);
-                                        self.ball_section.sync_to_model(ball, ui);
-                                        0
-                                    }
-                                    ColliderShapeDesc::Cylinder(cylinder) => {
-                                        toggle_visibility(ui, self.cylinder_section.section, true);
-                                        self.cylinder_section.sync_to_model(cylinder, ui);
-                                        1
-                                    }
-                                    ColliderShapeDesc::RoundCylinder(_) => 2,
-                                    ColliderShapeDesc::Cone(cone) => {
-                                        toggle_visibility(ui, self.cone_section.section, true);
-                                        self.cone_section.sync_to_model(cone, ui);
-                                        3
-                                    }
-                                    ColliderShapeDesc::Cuboid(cuboid) => {
-                                        toggle_visibility(ui, self.cuboid_section.section, true);
-                                        self.cuboid_section.sync_to_model(cuboid, ui);
-                                        4
-                                    }
-                                    ColliderShapeDesc::Capsule(capsule) => {
-                                        toggle_visibility(ui, self.capsule_section.section, true);
-                                        self.capsule_section.sync_to_model(capsule, ui);
-                                        5
-                                    }
-                                    ColliderShapeDesc::Segment(_) => {
-                                        // TODO
-                                        6
-                                    }
-                                    ColliderShapeDesc::Triangle(_) => {
-                                        // TODO
-                                        7
-                                    }
-                                    ColliderShapeDesc::Trimesh(_) => {
-                                        // Nothing to edit.
-                                        8
-                                    }
-                                    ColliderShapeDesc::Heightfield(_) => {
-                                        // Nothing to edit.
-                                        9
-                                    }
-                                };
+                        if let Some(&collider) = body.colliders.get(self.selected_collider) {
+                            let collider_index = match &editor_scene.physics.colliders
+                                [collider.into()]
+                            .shape
+                            {
+                                ColliderShapeDesc::Ball(ball) => {
+                                    toggle_visibility(ui, self.ball_section.section, true);
+                                    self.ball_section.sync_to_model(ball, ui);
+                                    0
+                                }
+                                ColliderShapeDesc::Cylinder(cylinder) => {
+                                    toggle_visibility(ui, self.cylinder_section.section, true);
+                                    self.cylinder_section.sync_to_model(cylinder, ui);
+                                    1
+                                }
+                                ColliderShapeDesc::RoundCylinder(round_cylinder) => {
+                                    toggle_visibility(
+                                        ui,
+                                        self.round_cylinder_section.section,
+                                        true,
+                                    );
+                                    self.round_cylinder_section
+                                        .sync_to_model(round_cylinder, ui);
+                                    2
+                                }
+                                ColliderShapeDesc::Cone(cone) => {
+                                    toggle_visibility(ui, self.cone_section.section, true);
+                                    self.cone_section.sync_to_model(cone, ui);
+                                    3
+                                }
+                                ColliderShapeDesc::Cuboid(cuboid) => {
+                                    toggle_visibility(ui, self.cuboid_section.section, true);
+                                    self.cuboid_section.sync_to_model(cuboid, ui);
+                                    4
+                                }
+                                ColliderShapeDesc::Capsule(capsule) => {
+                                    toggle_visibility(ui, self.capsule_section.section, true);
+                                    self.capsule_section.sync_to_model(capsule, ui);
+                                    5
+                                }
+                                ColliderShapeDesc::Segment(segment) => {
+                                    toggle_visibility(ui, self.segment_section.section, true);
+                                    self.segment_section.sync_to_model(segment, ui);
+                                    6
+                                }
+                                ColliderShapeDesc::Triangle(triangle) => {
+                                    toggle_visibility(ui, self.triangle_section.section, true);
+                                    self.triangle_section.sync_to_model(triangle, ui);
+                                    7
+                                }
+                                ColliderShapeDesc::Trimesh(_) => {
+                                    toggle_visibility(ui, self.trimesh_section.section, true);
+                                    self.trimesh_section.sync_to_model(
+                                        &scene.graph,
+                                        editor_scene.root,
+                                        &editor_scene.physics,
+                                        collider.into(),
+                                        ui,
+                                    );
+                                    8
+                                }
+                                ColliderShapeDesc::Heightfield(_) => {
+                                    toggle_visibility(ui, self.heightfield_section.section, true);
+                                    self.heightfield_section.sync_to_model(
+                                        &scene.graph,
+                                        editor_scene.root,
+                                        &editor_scene.physics,
+                                        collider.into(),
+                                        ui,
+                                    );
+                                    9
+                                }
+                            };
                             send_sync_message(
                                 ui,
                                 DropdownListMessage::selection(
@@ -410,15 +644,140 @@ impl PhysicsSection {
                         }
                     }
                 }
+            } else if !selection.is_empty() {
+                self.sync_to_nodes(editor_scene, selection.nodes(), &mut engine.user_interface);
+            }
+        }
+    }
+
+    /// Shows only the controls that can be applied to many nodes at once: body kind, shape of
+    /// collider and fitting. A list shows a value only if it is the same for every node.
+    fn sync_to_nodes(
+        &mut self,
+        editor_scene: &EditorScene,
+        nodes: &[Handle<Node>],
+        ui: &mut UserInterface,
+    ) {
+        let physics = &editor_scene.physics;
+
+        let bodies = nodes
+            .iter()
+            .map(|node| {
+                physics
+                    .binder
+                    .value_of(node)
+                    .map(|&body| &physics.bodies[body])
+            })
+            .collect::<Vec<_>>();
+
+        let body_indices = bodies
+            .iter()
+            .map(|body| body.map_or(0, body_status_index))
+            .collect::<Vec<_>>();
+        let body_index = if body_indices.windows(2).all(|w| w[0] == w[1]) {
+            body_indices.first().cloned()
+        } else {
+            None
+        };
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(self.body, MessageDirection::ToWidget, body_index),
+        );
+
+        let collider_indices = bodies
+            .iter()
+            .map(|body| {
+                body.and_then(|body| body.colliders.get(self.selected_collider))
+                    .map(|&collider| physics.colliders[collider.into()].shape.id() as usize)
+            })
+            .collect::<Vec<_>>();
+        let collider_index = if collider_indices.windows(2).all(|w| w[0] == w[1]) {
+            collider_indices.first().cloned().flatten()
+        } else {
+            None
+        };
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.collider,
+                MessageDirection::ToWidget,
+                collider_index,
+            ),
+        );
+
+        let all_have_bodies = bodies.iter().all(|body| body.is_some());
+        for &widget in &[self.collider, self.collider_text, self.fit] {
+            toggle_visibility(ui, widget, all_have_bodies);
+        }
+
+        for &widget in &[
+            self.colliders,
+            self.colliders_text,
+            self.collider_buttons,
+            self.joint,
+            self.joint_text,
+            self.convex,
+            self.joint_section.section,
+            self.body_section.section,
+            self.collider_section.section,
+            self.cylinder_section.section,
+            self.round_cylinder_section.section,
+            self.cone_section.section,
+            self.cuboid_section.section,
+            self.capsule_section.section,
+            self.ball_section.section,
+            self.segment_section.section,
+            self.triangle_section.section,
+            self.trimesh_section.section,
+            self.heightfield_section.section,
+        ] {
+            toggle_visibility(ui, widget, false);
+        }
+    }
+
+    /// Reloads material presets from working directory and refreshes every preset list.
+    pub fn reload_materials(&mut self, ui: &mut UserInterface) {
+        self.materials = match PhysicsMaterialLibrary::load() {
+            Ok(materials) => materials,
+            Err(e) => {
+                self.sender
+                    .send(Message::Log(format!(
+                        "Unable to load physics material presets, fallback to default. Reason: {:?}",
+                        e
+                    )))
+                    .unwrap();
+                Default::default()
             }
+        };
+
+        for &dropdown in &[self.collider_section.material, self.material_window.preset] {
+            set_preset_items(ui, dropdown, &self.materials);
         }
     }
 
+    /// Material window works with any selection, so it is handled separately from the rest
+    /// of the section which needs selected nodes.
+    pub fn handle_material_window_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        ui: &UserInterface,
+    ) {
+        self.material_window.handle_ui_message(
+            message,
+            editor_scene,
+            &self.materials,
+            ui,
+            &self.sender,
+        );
+    }
+
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
         editor_scene: &EditorScene,
         engine: &GameEngine,
+        settings: &PhysicsSettings,
     ) {
         scope_profile!();
 
@@ -430,53 +789,144 @@ impl PhysicsSection {
                 let node_handle = selection.nodes()[0];
 
                 if message.direction() == MessageDirection::FromWidget {
-                    self.subsections_handle_ui_message(message, editor_scene, node_handle);
+                    self.subsections_handle_ui_message(
+                        message,
+                        editor_scene,
+                        node_handle,
+                        settings,
+                    );
                 }
 
+                self.convex_generator.handle_ui_message(
+                    message,
+                    editor_scene,
+                    graph,
+                    node_handle,
+                    &engine.user_interface,
+                    &self.sender,
+                );
+
                 match message.data() {
                     UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(index))
                         if message.direction() == MessageDirection::FromWidget =>
                     {
                         if message.destination() == self.collider {
-                            self.select_collider(editor_scene, node_handle, *index);
+                            if let Some((commands, selected_collider)) =
+                                Self::make_collider_commands(
+                                    editor_scene,
+                                    node_handle,
+                                    self.selected_collider,
+                                    *index,
+                                )
+                            {
+                                self.selected_collider = selected_collider;
+                                self.sender
+                                    .send(Message::do_scene_command(CommandGroup::from(commands)))
+                                    .unwrap();
+                            }
                         }
 
                         if let Some(index) = index {
                             if message.destination() == self.body {
-                                self.select_body(editor_scene, node_handle, graph, *index);
+                                self.send_batch(&[node_handle], |node| {
+                                    Self::make_body_commands(editor_scene, node, graph, *index)
+                                });
                             } else if message.destination() == self.joint {
                                 self.select_joint(editor_scene, node_handle, *index);
                             }
                         }
                     }
-                    UiMessageData::Button(ButtonMessage::Click)
-                        if message.destination() == self.fit =>
+                    UiMessageData::ListView(ListViewMessage::SelectionChanged(Some(index)))
+                        if message.destination() == self.colliders
+                            && message.direction() == MessageDirection::FromWidget =>
                     {
-                        self.fit_collider(editor_scene, node_handle, graph);
+                        if self.selected_collider != *index {
+                            self.selected_collider = *index;
+                            self.sender.send(Message::SyncToModel).unwrap();
+                        }
+                    }
+                    UiMessageData::Button(ButtonMessage::Click) => {
+                        if message.destination() == self.fit {
+                            let target = self.selected_collider;
+                            self.send_batch(&[node_handle], |node| {
+                                Self::make_fit_commands(editor_scene, node, graph, target)
+                            });
+                        } else if message.destination() == self.add_collider {
+                            self.add_collider(editor_scene, node_handle);
+                        } else if message.destination() == self.remove_collider {
+                            self.remove_collider(editor_scene, node_handle);
+                        } else if message.destination() == self.convex {
+                            self.convex_generator.open(&engine.user_interface);
+                        }
                     }
                     _ => {}
                 }
+            } else if !selection.is_empty() {
+                self.handle_nodes_message(message, editor_scene, selection.nodes(), graph);
             }
         }
     }
 
+    /// Applies body kind, shape of collider or fitting to every selected node as a single
+    /// command group.
+    fn handle_nodes_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        nodes: &[Handle<Node>],
+        graph: &Graph,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        let target = self.selected_collider;
+        match message.data() {
+            UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(Some(index))) => {
+                if message.destination() == self.body {
+                    self.send_batch(nodes, |node| {
+                        Self::make_body_commands(editor_scene, node, graph, *index)
+                    });
+                } else if message.destination() == self.collider {
+                    self.send_batch(nodes, |node| {
+                        Self::make_collider_commands(editor_scene, node, target, Some(*index))
+                            .map(|(commands, _)| commands)
+                            .unwrap_or_default()
+                    });
+                }
+            }
+            UiMessageData::Button(ButtonMessage::Click) if message.destination() == self.fit => {
+                self.send_batch(nodes, |node| {
+                    Self::make_fit_commands(editor_scene, node, graph, target)
+                });
+            }
+            _ => {}
+        }
+    }
+
     fn subsections_handle_ui_message(
         &mut self,
         message: &UiMessage,
         editor_scene: &EditorScene,
         node_handle: Handle<Node>,
+        settings: &PhysicsSettings,
     ) {
         if let Some(&body_handle) = editor_scene.physics.binder.value_of(&node_handle) {
             let body = &editor_scene.physics.bodies[body_handle];
             self.body_section.handle_message(message, body, body_handle);
 
-            if let Some(&collider_handle) = body.colliders.first() {
+            if let Some(&collider_handle) = body.colliders.get(self.selected_collider) {
                 let collider = &editor_scene.physics.colliders[collider_handle.into()];
-                self.collider_section
-                    .handle_message(message, collider, collider_handle.into());
+                self.collider_section.handle_message(
+                    message,
+                    collider,
+                    collider_handle.into(),
+                    settings,
+                    &self.materials,
+                );
             }
 
-            if let Some(&collider) = body.colliders.get(0) {
+            if let Some(&collider) = body.colliders.get(self.selected_collider) {
                 match &editor_scene.physics.colliders[collider.into()].shape {
                     ColliderShapeDesc::Ball(ball) => {
                         self.ball_section
@@ -486,8 +936,12 @@ impl PhysicsSection {
                         self.cylinder_section
                             .handle_message(message, cylinder, collider.into());
                     }
-                    ColliderShapeDesc::RoundCylinder(_) => {
-                        // TODO
+                    ColliderShapeDesc::RoundCylinder(round_cylinder) => {
+                        self.round_cylinder_section.handle_message(
+                            message,
+                            round_cylinder,
+                            collider.into(),
+                        );
                     }
                     ColliderShapeDesc::Cone(cone) => {
                         self.cone_section
@@ -501,17 +955,27 @@ impl PhysicsSection {
                         self.capsule_section
                             .handle_message(message, capsule, collider.into());
                     }
-                    ColliderShapeDesc::Segment(_) => {
-                        // TODO
+                    ColliderShapeDesc::Segment(segment) => {
+                        self.segment_section
+                            .handle_message(message, segment, collider.into());
                     }
-                    ColliderShapeDesc::Triangle(_) => {
-                        // TODO
+                    ColliderShapeDesc::Triangle(triangle) => {
+                        self.triangle_section
+                            .handle_message(message, triangle, collider.into());
                     }
                     ColliderShapeDesc::Trimesh(_) => {
-                        // Nothing to edit.
+                        self.trimesh_section.handle_message(
+                            message,
+                            &editor_scene.physics,
+                            collider.into(),
+                        );
                     }
                     ColliderShapeDesc::Heightfield(_) => {
-                        // Nothing to edit.
+                        self.heightfield_section.handle_message(
+                            message,
+                            &editor_scene.physics,
+                            collider.into(),
+                        );
                     }
                 };
             }
@@ -529,24 +993,26 @@ impl PhysicsSection {
                     message,
                     &editor_scene.physics.joints[joint],
                     joint,
+                    &editor_scene.selection,
                 );
             }
         }
     }
 
-    fn select_body(
-        &self,
+    /// Makes commands that give the node a body of given kind from the body list, or remove
+    /// its body with all colliders if the index is zero.
+    fn make_body_commands(
         editor_scene: &EditorScene,
         node_handle: Handle<Node>,
         graph: &Graph,
         index: usize,
-    ) {
+    ) -> Vec<SceneCommand> {
+        let mut commands = Vec::new();
+
         match index {
             0 => {
                 // Remove body.
                 if let Some(&body_handle) = editor_scene.physics.binder.value_of(&node_handle) {
-                    let mut commands = Vec::new();
-
                     for &collider in editor_scene.physics.bodies[body_handle].colliders.iter() {
                         commands.push(SceneCommand::new(DeleteColliderCommand::new(
                             collider.into(),
@@ -554,78 +1020,111 @@ impl PhysicsSection {
                     }
 
                     commands.push(SceneCommand::new(DeleteBodyCommand::new(body_handle)));
-
-                    self.sender
-                        .send(Message::do_scene_command(CommandGroup::from(commands)))
-                        .unwrap();
                 }
             }
             1 | 2 | 3 | 4 => {
                 let mut current_status = 0;
                 if let Some(&body) = editor_scene.physics.binder.value_of(&node_handle) {
-                    current_status = match editor_scene.physics.bodies[body].status {
-                        RigidBodyTypeDesc::Dynamic => 1,
-                        RigidBodyTypeDesc::Static => 2,
-                        RigidBodyTypeDesc::KinematicPositionBased => 3,
-                        RigidBodyTypeDesc::KinematicVelocityBased => 4,
-                    };
+                    current_status = body_status_index(&editor_scene.physics.bodies[body]);
                 }
 
                 if index != current_status {
-                    // Create body.
-                    let node = &graph[node_handle];
-                    let body = RigidBody {
-                        position: node.global_position(),
-                        rotation: **node.local_transform().rotation(),
-                        status: match index {
-                            1 => RigidBodyTypeDesc::Dynamic,
-                            2 => RigidBodyTypeDesc::Static,
-                            3 => RigidBodyTypeDesc::KinematicPositionBased,
-                            4 => RigidBodyTypeDesc::KinematicVelocityBased,
-                            _ => unreachable!(),
-                        },
-                        ..Default::default()
+                    let status = match index {
+                        1 => RigidBodyTypeDesc::Dynamic,
+                        2 => RigidBodyTypeDesc::Static,
+                        3 => RigidBodyTypeDesc::KinematicPositionBased,
+                        4 => RigidBodyTypeDesc::KinematicVelocityBased,
+                        _ => unreachable!(),
                     };
 
-                    let mut commands = Vec::new();
-
                     if let Some(&body) = editor_scene.physics.binder.value_of(&node_handle) {
-                        for &collider in editor_scene.physics.bodies[body].colliders.iter() {
-                            commands.push(SceneCommand::new(DeleteColliderCommand::new(
-                                collider.into(),
-                            )))
-                        }
+                        // Existing body keeps its colliders and joints, only status is changed.
+                        commands.push(SceneCommand::new(SetBodyStatusCommand::new(body, status)));
+                    } else {
+                        // Create body.
+                        let node = &graph[node_handle];
+                        let body = RigidBody {
+                            position: node.global_position(),
+                            rotation: **node.local_transform().rotation(),
+                            status,
+                            ..Default::default()
+                        };
 
-                        commands.push(SceneCommand::new(DeleteBodyCommand::new(body)));
+                        commands.push(SceneCommand::new(SetBodyCommand::new(node_handle, body)));
                     }
-
-                    commands.push(SceneCommand::new(SetBodyCommand::new(node_handle, body)));
-
-                    self.sender
-                        .send(Message::do_scene_command(CommandGroup::from(commands)))
-                        .unwrap();
                 }
             }
             _ => unreachable!(),
         };
+
+        commands
     }
 
-    fn select_collider(
+    /// Sends commands of every node as a single group, so a batch is undone at once.
+    fn send_batch(
         &self,
+        nodes: &[Handle<Node>],
+        mut make_commands: impl FnMut(Handle<Node>) -> Vec<SceneCommand>,
+    ) {
+        let commands = nodes
+            .iter()
+            .flat_map(|&node| make_commands(node))
+            .collect::<Vec<_>>();
+
+        if !commands.is_empty() {
+            self.sender
+                .send(Message::do_scene_command(CommandGroup::from(commands)))
+                .unwrap();
+        }
+    }
+
+    fn add_collider(&mut self, editor_scene: &EditorScene, node_handle: Handle<Node>) {
+        if let Some(&body) = editor_scene.physics.binder.value_of(&node_handle) {
+            // New collider will be added to the end of the list, select it.
+            self.selected_collider = editor_scene.physics.bodies[body].colliders.len();
+
+            self.sender
+                .send(Message::do_scene_command(SetColliderCommand::new(
+                    body,
+                    Collider {
+                        shape: ColliderShapeDesc::Ball(BallDesc { radius: 0.5 }),
+                        ..Default::default()
+                    },
+                )))
+                .unwrap();
+        }
+    }
+
+    fn remove_collider(&self, editor_scene: &EditorScene, node_handle: Handle<Node>) {
+        if let Some(&body) = editor_scene.physics.binder.value_of(&node_handle) {
+            if let Some(&collider) = editor_scene.physics.bodies[body]
+                .colliders
+                .get(self.selected_collider)
+            {
+                self.sender
+                    .send(Message::do_scene_command(DeleteColliderCommand::new(
+                        collider.into(),
+                    )))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Makes commands that replace collider at `target` position in the list of colliders of
+    /// node's body with a default collider of given shape, or add one if there is no such
+    /// collider. Returns commands together with new position of the collider.
+    fn make_collider_commands(
         editor_scene: &EditorScene,
         node_handle: Handle<Node>,
+        target: usize,
         index: Option<usize>,
-    ) {
+    ) -> Option<(Vec<SceneCommand>, usize)> {
         if let Some(&body) = editor_scene.physics.binder.value_of(&node_handle) {
-            let current_index =
-                editor_scene.physics.bodies[body]
-                    .colliders
-                    .first()
-                    .map(|&first_collider| {
-                        editor_scene.physics.colliders[first_collider.into()]
-                            .shape
-                            .id() as usize
-                    });
+            let current = editor_scene.physics.bodies[body]
+                .colliders
+                .get(target)
+                .map(|&collider| &editor_scene.physics.colliders[collider.into()]);
+            let current_index = current.map(|collider| collider.shape.id() as usize);
 
             let (can_switch, index) = match (current_index, index) {
                 (Some(current_index), Some(index)) if current_index != index => (true, index),
@@ -700,20 +1199,35 @@ impl PhysicsSection {
                     },
                     _ => unreachable!(),
                 };
+                // Keep placement of the collider that is being replaced, so shapes of
+                // compound body can be switched without moving them.
+                let collider = if let Some(current) = current {
+                    Collider {
+                        translation: current.translation,
+                        rotation: current.rotation,
+                        ..collider
+                    }
+                } else {
+                    collider
+                };
+
+                let colliders = &editor_scene.physics.bodies[body].colliders;
                 let mut commands = Vec::new();
-                // For now only one collider per body is supported.
-                // It is easy to add more.
-                if let Some(&first_collider) = editor_scene.physics.bodies[body].colliders.first() {
+                let new_position = if let Some(&selected) = colliders.get(target) {
                     commands.push(SceneCommand::new(DeleteColliderCommand::new(
-                        first_collider.into(),
-                    )))
-                }
+                        selected.into(),
+                    )));
+                    // Replaced collider will be moved to the end of the list.
+                    colliders.len() - 1
+                } else {
+                    colliders.len()
+                };
                 commands.push(SceneCommand::new(SetColliderCommand::new(body, collider)));
-                self.sender
-                    .send(Message::do_scene_command(CommandGroup::from(commands)))
-                    .unwrap();
+                return Some((commands, new_position));
             }
         }
+
+        None
     }
 
     fn select_joint(&self, editor_scene: &EditorScene, node_handle: Handle<Node>, index: usize) {
@@ -797,10 +1311,19 @@ impl PhysicsSection {
         }
     }
 
-    fn fit_collider(&self, editor_scene: &EditorScene, node_handle: Handle<Node>, graph: &Graph) {
+    /// Makes commands that fit collider at `target` position of node's body to bounds of
+    /// meshes of the node.
+    fn make_fit_commands(
+        editor_scene: &EditorScene,
+        node_handle: Handle<Node>,
+        graph: &Graph,
+        target: usize,
+    ) -> Vec<SceneCommand> {
+        let mut commands = Vec::new();
+
         if let Some(&body_handle) = editor_scene.physics.binder.value_of(&node_handle) {
             let body = &editor_scene.physics.bodies[body_handle];
-            if let Some(&collider_handle) = body.colliders.first() {
+            if let Some(&collider_handle) = body.colliders.get(target) {
                 let collider = &editor_scene.physics.colliders[collider_handle.into()];
 
                 let mut bounding_box = AxisAlignedBoundingBox::default();
@@ -817,12 +1340,10 @@ impl PhysicsSection {
 
                 let node_position = graph[node_handle].global_position();
 
-                self.sender
-                    .send(Message::do_scene_command(SetColliderPositionCommand::new(
-                        collider_handle.into(),
-                        bounding_box.center() - node_position,
-                    )))
-                    .unwrap();
+                commands.push(SceneCommand::new(SetColliderPositionCommand::new(
+                    collider_handle.into(),
+                    bounding_box.center() - node_position,
+                )));
 
                 match &collider.shape {
                     ColliderShapeDesc::Ball(_) => {
@@ -830,12 +1351,10 @@ impl PhysicsSection {
 
                         let radius = d.x.max(d.y).max(d.z);
 
-                        self.sender
-                            .send(Message::do_scene_command(SetBallRadiusCommand::new(
-                                collider_handle.into(),
-                                radius,
-                            )))
-                            .unwrap();
+                        commands.push(SceneCommand::new(SetBallRadiusCommand::new(
+                            collider_handle.into(),
+                            radius,
+                        )));
                     }
                     ColliderShapeDesc::Cylinder(_) => {
                         let d = (bounding_box.max - bounding_box.min).scale(0.5);
@@ -843,7 +1362,7 @@ impl PhysicsSection {
                         let radius = d.x.max(d.z);
                         let height = bounding_box.max.y - bounding_box.min.y;
 
-                        let commands = CommandGroup::from(vec![
+                        commands.extend(vec![
                             SceneCommand::new(SetCylinderRadiusCommand::new(
                                 collider_handle.into(),
                                 radius,
@@ -853,20 +1372,34 @@ impl PhysicsSection {
                                 height * 0.5,
                             )),
                         ]);
-                        self.sender
-                            .send(Message::do_scene_command(commands))
-                            .unwrap();
+                    }
+                    ColliderShapeDesc::RoundCylinder(round_cylinder) => {
+                        let d = (bounding_box.max - bounding_box.min).scale(0.5);
+
+                        // Border radius inflates inner cylinder, so it must be subtracted.
+                        let border_radius = round_cylinder.border_radius;
+                        let radius = (d.x.max(d.z) - border_radius).max(0.0);
+                        let half_height = (d.y - border_radius).max(0.0);
+
+                        commands.extend(vec![
+                            SceneCommand::new(SetRoundCylinderRadiusCommand::new(
+                                collider_handle.into(),
+                                radius,
+                            )),
+                            SceneCommand::new(SetRoundCylinderHalfHeightCommand::new(
+                                collider_handle.into(),
+                                half_height,
+                            )),
+                        ]);
                     }
                     ColliderShapeDesc::Cone(_) => {
                         // TODO
                     }
                     ColliderShapeDesc::Cuboid(_) => {
-                        self.sender
-                            .send(Message::do_scene_command(SetCuboidHalfExtentsCommand::new(
-                                collider_handle.into(),
-                                (bounding_box.max - bounding_box.min).scale(0.5),
-                            )))
-                            .unwrap();
+                        commands.push(SceneCommand::new(SetCuboidHalfExtentsCommand::new(
+                            collider_handle.into(),
+                            (bounding_box.max - bounding_box.min).scale(0.5),
+                        )));
                     }
                     ColliderShapeDesc::Capsule(_) => {
                         // TODO
@@ -877,5 +1410,7 @@ impl PhysicsSection {
                 }
             }
         }
+
+        commands
     }
 }
@@ -0,0 +1,139 @@
+use crate::sidebar::make_section;
+use crate::{
+    physics::Collider,
+    scene::commands::physics::{
+        SetRoundCylinderBorderRadiusCommand, SetRoundCylinderHalfHeightCommand,
+        SetRoundCylinderRadiusCommand,
+    },
+    send_sync_message,
+    sidebar::{make_f32_input_field, make_text_mark, COLUMN_WIDTH, ROW_HEIGHT},
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::pool::Handle,
+    gui::{
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, NumericUpDownMessage, UiMessageData},
+        widget::WidgetBuilder,
+    },
+    physics3d::desc::RoundCylinderDesc,
+};
+use std::sync::mpsc::Sender;
+
+pub struct RoundCylinderSection {
+    pub section: Handle<UiNode>,
+    half_height: Handle<UiNode>,
+    radius: Handle<UiNode>,
+    border_radius: Handle<UiNode>,
+    sender: Sender<Message>,
+}
+
+impl RoundCylinderSection {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let half_height;
+        let radius;
+        let border_radius;
+        let section = make_section(
+            "Round Cylinder Properties",
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(make_text_mark(ctx, "Half Height", 0))
+                    .with_child({
+                        half_height = make_f32_input_field(ctx, 0, 0.0, std::f32::MAX, 0.1);
+                        half_height
+                    })
+                    .with_child(make_text_mark(ctx, "Radius", 1))
+                    .with_child({
+                        radius = make_f32_input_field(ctx, 1, 0.0, std::f32::MAX, 0.1);
+                        radius
+                    })
+                    .with_child(make_text_mark(ctx, "Border Radius", 2))
+                    .with_child({
+                        border_radius = make_f32_input_field(ctx, 2, 0.0, std::f32::MAX, 0.01);
+                        border_radius
+                    }),
+            )
+            .add_column(Column::strict(COLUMN_WIDTH))
+            .add_column(Column::stretch())
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .build(ctx),
+            ctx,
+        );
+
+        Self {
+            section,
+            sender,
+            half_height,
+            radius,
+            border_radius,
+        }
+    }
+
+    pub fn sync_to_model(&mut self, round_cylinder: &RoundCylinderDesc, ui: &mut UserInterface) {
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(
+                self.half_height,
+                MessageDirection::ToWidget,
+                round_cylinder.half_height,
+            ),
+        );
+
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(
+                self.radius,
+                MessageDirection::ToWidget,
+                round_cylinder.radius,
+            ),
+        );
+
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(
+                self.border_radius,
+                MessageDirection::ToWidget,
+                round_cylinder.border_radius,
+            ),
+        );
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        round_cylinder: &RoundCylinderDesc,
+        handle: Handle<Collider>,
+    ) {
+        if let UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) = *message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.half_height
+                    && round_cylinder.half_height.ne(&value)
+                {
+                    self.sender
+                        .send(Message::do_scene_command(
+                            SetRoundCylinderHalfHeightCommand::new(handle, value),
+                        ))
+                        .unwrap();
+                } else if message.destination() == self.radius && round_cylinder.radius.ne(&value) {
+                    self.sender
+                        .send(Message::do_scene_command(
+                            SetRoundCylinderRadiusCommand::new(handle, value),
+                        ))
+                        .unwrap();
+                } else if message.destination() == self.border_radius
+                    && round_cylinder.border_radius.ne(&value)
+                {
+                    self.sender
+                        .send(Message::do_scene_command(
+                            SetRoundCylinderBorderRadiusCommand::new(handle, value),
+                        ))
+                        .unwrap();
+                }
+            }
+        }
+    }
+}
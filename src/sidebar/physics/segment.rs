@@ -1 +1,98 @@
-// TODO
+use crate::sidebar::make_section;
+use crate::{
+    physics::Collider,
+    scene::commands::physics::{SetSegmentBeginCommand, SetSegmentEndCommand},
+    send_sync_message,
+    sidebar::{make_text_mark, make_vec3_input_field, COLUMN_WIDTH, ROW_HEIGHT},
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::pool::Handle,
+    gui::{
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessageData, Vec3EditorMessage},
+        widget::WidgetBuilder,
+    },
+    physics3d::desc::SegmentDesc,
+};
+use std::sync::mpsc::Sender;
+
+pub struct SegmentSection {
+    pub section: Handle<UiNode>,
+    begin: Handle<UiNode>,
+    end: Handle<UiNode>,
+    sender: Sender<Message>,
+}
+
+impl SegmentSection {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let begin;
+        let end;
+        let section = make_section(
+            "Segment Properties",
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(make_text_mark(ctx, "Begin", 0))
+                    .with_child({
+                        begin = make_vec3_input_field(ctx, 0);
+                        begin
+                    })
+                    .with_child(make_text_mark(ctx, "End", 1))
+                    .with_child({
+                        end = make_vec3_input_field(ctx, 1);
+                        end
+                    }),
+            )
+            .add_column(Column::strict(COLUMN_WIDTH))
+            .add_column(Column::stretch())
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .build(ctx),
+            ctx,
+        );
+
+        Self {
+            section,
+            sender,
+            begin,
+            end,
+        }
+    }
+
+    pub fn sync_to_model(&mut self, segment: &SegmentDesc, ui: &mut UserInterface) {
+        send_sync_message(
+            ui,
+            Vec3EditorMessage::value(self.begin, MessageDirection::ToWidget, segment.begin),
+        );
+
+        send_sync_message(
+            ui,
+            Vec3EditorMessage::value(self.end, MessageDirection::ToWidget, segment.end),
+        );
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        segment: &SegmentDesc,
+        handle: Handle<Collider>,
+    ) {
+        if let UiMessageData::Vec3Editor(Vec3EditorMessage::Value(value)) = message.data() {
+            if message.destination() == self.begin && segment.begin.ne(value) {
+                self.sender
+                    .send(Message::do_scene_command(SetSegmentBeginCommand::new(
+                        handle, *value,
+                    )))
+                    .unwrap();
+            } else if message.destination() == self.end && segment.end.ne(value) {
+                self.sender
+                    .send(Message::do_scene_command(SetSegmentEndCommand::new(
+                        handle, *value,
+                    )))
+                    .unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Trimesh and heightfield colliders do not store any geometry, it is built from their source
+//! node. By default the source is the node bound to the body, this module provides a selector
+//! for any other node.
+
+use crate::gui::make_dropdown_list_option;
+use crate::{
+    physics::{Collider, Physics},
+    scene::commands::physics::SetColliderSourceCommand,
+    send_sync_message, Message,
+};
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::pool::Handle,
+    gui::{
+        dropdown_list::DropdownListBuilder,
+        message::{DropdownListMessage, MessageDirection},
+        widget::WidgetBuilder,
+        Thickness,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::sync::mpsc::Sender;
+
+pub struct SourceSelector {
+    pub dropdown: Handle<UiNode>,
+    /// First source is always none - it stands for the node bound to the body.
+    sources: Vec<Handle<Node>>,
+    filter: fn(&Node) -> bool,
+}
+
+impl SourceSelector {
+    pub fn new(ctx: &mut BuildContext, row: usize, filter: fn(&Node) -> bool) -> Self {
+        Self {
+            dropdown: DropdownListBuilder::new(
+                WidgetBuilder::new()
+                    .on_row(row)
+                    .on_column(1)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_close_on_selection(true)
+            .build(ctx),
+            sources: Default::default(),
+            filter,
+        }
+    }
+
+    /// Lists node of the body and every node of the scene that passes the filter, selects
+    /// current source of the collider.
+    pub fn sync_to_model(
+        &mut self,
+        graph: &Graph,
+        editor_root: Handle<Node>,
+        physics: &Physics,
+        collider: Handle<Collider>,
+        ui: &mut UserInterface,
+    ) {
+        let body_node = physics
+            .binder
+            .key_of(&physics.colliders[collider].parent.into())
+            .cloned()
+            .unwrap_or_default();
+
+        self.sources.clear();
+        self.sources.push(Handle::NONE);
+
+        let mut stack = vec![graph.get_root()];
+        while let Some(handle) = stack.pop() {
+            if handle == editor_root {
+                continue;
+            }
+            let candidate = &graph[handle];
+            if handle != body_node && (self.filter)(candidate) {
+                self.sources.push(handle);
+            }
+            stack.extend_from_slice(candidate.children());
+        }
+
+        let items = self
+            .sources
+            .iter()
+            .map(|&h| {
+                let name = if h.is_some() {
+                    graph[h].name().to_owned()
+                } else if graph.is_valid_handle(body_node) {
+                    format!("{} (Body Node)", graph[body_node].name())
+                } else {
+                    "Body Node".to_owned()
+                };
+                make_dropdown_list_option(&mut ui.build_ctx(), &name)
+            })
+            .collect::<Vec<_>>();
+
+        let current = physics
+            .collider_sources
+            .get(&collider)
+            .cloned()
+            .unwrap_or_default();
+        let selection = self.sources.iter().position(|&h| h == current).unwrap_or(0);
+
+        send_sync_message(
+            ui,
+            DropdownListMessage::items(self.dropdown, MessageDirection::ToWidget, items),
+        );
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.dropdown,
+                MessageDirection::ToWidget,
+                Some(selection),
+            ),
+        );
+    }
+
+    /// Makes selected node the source of the collider, the body is left where it is.
+    pub fn select(
+        &self,
+        index: usize,
+        physics: &Physics,
+        collider: Handle<Collider>,
+        sender: &Sender<Message>,
+    ) {
+        if let Some(&source) = self.sources.get(index) {
+            let current = physics
+                .collider_sources
+                .get(&collider)
+                .cloned()
+                .unwrap_or_default();
+            if source != current {
+                sender
+                    .send(Message::do_scene_command(SetColliderSourceCommand::new(
+                        collider, source,
+                    )))
+                    .unwrap();
+            }
+        }
+    }
+}
@@ -1 +1,119 @@
-// TODO
+use crate::sidebar::make_section;
+use crate::{
+    physics::Collider,
+    scene::commands::physics::{SetTriangleACommand, SetTriangleBCommand, SetTriangleCCommand},
+    send_sync_message,
+    sidebar::{make_text_mark, make_vec3_input_field, COLUMN_WIDTH, ROW_HEIGHT},
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::pool::Handle,
+    gui::{
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessageData, Vec3EditorMessage},
+        widget::WidgetBuilder,
+    },
+    physics3d::desc::TriangleDesc,
+};
+use std::sync::mpsc::Sender;
+
+pub struct TriangleSection {
+    pub section: Handle<UiNode>,
+    a: Handle<UiNode>,
+    b: Handle<UiNode>,
+    c: Handle<UiNode>,
+    sender: Sender<Message>,
+}
+
+impl TriangleSection {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let a;
+        let b;
+        let c;
+        let section = make_section(
+            "Triangle Properties",
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(make_text_mark(ctx, "A", 0))
+                    .with_child({
+                        a = make_vec3_input_field(ctx, 0);
+                        a
+                    })
+                    .with_child(make_text_mark(ctx, "B", 1))
+                    .with_child({
+                        b = make_vec3_input_field(ctx, 1);
+                        b
+                    })
+                    .with_child(make_text_mark(ctx, "C", 2))
+                    .with_child({
+                        c = make_vec3_input_field(ctx, 2);
+                        c
+                    }),
+            )
+            .add_column(Column::strict(COLUMN_WIDTH))
+            .add_column(Column::stretch())
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .build(ctx),
+            ctx,
+        );
+
+        Self {
+            section,
+            sender,
+            a,
+            b,
+            c,
+        }
+    }
+
+    pub fn sync_to_model(&mut self, triangle: &TriangleDesc, ui: &mut UserInterface) {
+        send_sync_message(
+            ui,
+            Vec3EditorMessage::value(self.a, MessageDirection::ToWidget, triangle.a),
+        );
+
+        send_sync_message(
+            ui,
+            Vec3EditorMessage::value(self.b, MessageDirection::ToWidget, triangle.b),
+        );
+
+        send_sync_message(
+            ui,
+            Vec3EditorMessage::value(self.c, MessageDirection::ToWidget, triangle.c),
+        );
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        triangle: &TriangleDesc,
+        handle: Handle<Collider>,
+    ) {
+        if let UiMessageData::Vec3Editor(Vec3EditorMessage::Value(value)) = message.data() {
+            let value = *value;
+            if message.destination() == self.a && triangle.a.ne(&value) {
+                self.sender
+                    .send(Message::do_scene_command(SetTriangleACommand::new(
+                        handle, value,
+                    )))
+                    .unwrap();
+            } else if message.destination() == self.b && triangle.b.ne(&value) {
+                self.sender
+                    .send(Message::do_scene_command(SetTriangleBCommand::new(
+                        handle, value,
+                    )))
+                    .unwrap();
+            } else if message.destination() == self.c && triangle.c.ne(&value) {
+                self.sender
+                    .send(Message::do_scene_command(SetTriangleCCommand::new(
+                        handle, value,
+                    )))
+                    .unwrap();
+            }
+        }
+    }
+}
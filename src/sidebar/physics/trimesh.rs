@@ -1 +1,127 @@
-// TODO
+use crate::sidebar::make_section;
+use crate::{
+    physics::{Collider, Physics},
+    send_sync_message,
+    sidebar::{make_text_mark, physics::source::SourceSelector, COLUMN_WIDTH, ROW_HEIGHT},
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::pool::Handle,
+    gui::{
+        grid::{Column, GridBuilder, Row},
+        message::{DropdownListMessage, MessageDirection, TextMessage, UiMessageData},
+        text::TextBuilder,
+        widget::WidgetBuilder,
+        Thickness, VerticalAlignment,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::sync::mpsc::Sender;
+
+pub struct TrimeshSection {
+    pub section: Handle<UiNode>,
+    source: SourceSelector,
+    triangles: Handle<UiNode>,
+    sender: Sender<Message>,
+}
+
+impl TrimeshSection {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let source = SourceSelector::new(ctx, 0, Node::is_mesh);
+        let triangles;
+        let section = make_section(
+            "Trimesh Properties",
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(make_text_mark(ctx, "Build From Mesh", 0))
+                    .with_child(source.dropdown)
+                    .with_child(make_text_mark(ctx, "Triangles", 1))
+                    .with_child({
+                        triangles = TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_vertical_alignment(VerticalAlignment::Center)
+                                .with_margin(Thickness::left(4.0))
+                                .on_row(1)
+                                .on_column(1),
+                        )
+                        .build(ctx);
+                        triangles
+                    }),
+            )
+            .add_column(Column::strict(COLUMN_WIDTH))
+            .add_column(Column::stretch())
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .build(ctx),
+            ctx,
+        );
+
+        Self {
+            section,
+            source,
+            triangles,
+            sender,
+        }
+    }
+
+    pub fn sync_to_model(
+        &mut self,
+        graph: &Graph,
+        editor_root: Handle<Node>,
+        physics: &Physics,
+        collider: Handle<Collider>,
+        ui: &mut UserInterface,
+    ) {
+        self.source
+            .sync_to_model(graph, editor_root, physics, collider, ui);
+
+        let node = physics.collider_source(collider);
+
+        // Trimesh is built from every mesh in the hierarchy of the node.
+        let mut count = 0;
+        let descendants = if graph.is_valid_handle(node) {
+            graph.traverse_handle_iter(node).collect()
+        } else {
+            Vec::new()
+        };
+        for descendant in descendants {
+            if let Node::Mesh(mesh) = &graph[descendant] {
+                for surface in mesh.surfaces() {
+                    count += surface
+                        .data()
+                        .read()
+                        .unwrap()
+                        .geometry_buffer
+                        .triangles_ref()
+                        .len();
+                }
+            }
+        }
+
+        send_sync_message(
+            ui,
+            TextMessage::text(
+                self.triangles,
+                MessageDirection::ToWidget,
+                count.to_string(),
+            ),
+        );
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        physics: &Physics,
+        collider: Handle<Collider>,
+    ) {
+        if let UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(Some(index))) =
+            message.data()
+        {
+            if message.destination() == self.source.dropdown {
+                self.source.select(*index, physics, collider, &self.sender);
+            }
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{
+    scene::commands::{terrain::ModifyTerrainLayerMaskCommand, CommandGroup, SceneCommand},
+    send_sync_message,
+    sidebar::{
+        make_bool_input_field, make_f32_input_field, make_section, make_text_mark, COLUMN_WIDTH,
+        ROW_HEIGHT,
+    },
+    terrain::{
+        auto_paint::{self, PaintRule},
+        mask::copy_layer_masks,
+    },
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::{pool::Handle, scope_profile},
+    gui::{
+        button::ButtonBuilder,
+        grid::{Column, GridBuilder, Row},
+        message::{
+            ButtonMessage, CheckBoxMessage, MessageDirection, NumericUpDownMessage, UiMessageData,
+        },
+        widget::WidgetBuilder,
+    },
+    material::Material,
+    scene::{
+        graph::Graph,
+        node::Node,
+        terrain::{Layer, Terrain},
+    },
+};
+use std::{
+    collections::HashMap,
+    sync::{mpsc::Sender, Arc, Mutex},
+};
+
+/// Rule together with the layer it belongs to. Layers are told apart by their materials,
+/// so rules stay with their layers when other layers are added or removed.
+struct LayerRule {
+    material: Arc<Mutex<Material>>,
+    rule: PaintRule,
+}
+
+pub struct AutoPaintSection {
+    pub section: Handle<UiNode>,
+    enabled: Handle<UiNode>,
+    min_height: Handle<UiNode>,
+    max_height: Handle<UiNode>,
+    height_blend: Handle<UiNode>,
+    min_slope: Handle<UiNode>,
+    max_slope: Handle<UiNode>,
+    slope_blend: Handle<UiNode>,
+    noise_amount: Handle<UiNode>,
+    noise_frequency: Handle<UiNode>,
+    generate: Handle<UiNode>,
+    /// Rules for layers of every edited terrain. Rules of deleted layers are kept, so undo
+    /// of the deletion brings them back.
+    rules: HashMap<Handle<Node>, Vec<LayerRule>>,
+}
+
+impl AutoPaintSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let enabled;
+        let min_height;
+        let max_height;
+        let height_blend;
+        let min_slope;
+        let max_slope;
+        let slope_blend;
+        let noise_amount;
+        let noise_frequency;
+        let generate;
+        let section = make_section(
+            "Auto Paint",
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(make_text_mark(ctx, "Paint Layer", 0))
+                    .with_child({
+                        enabled = make_bool_input_field(ctx, 0);
+                        enabled
+                    })
+                    .with_child(make_text_mark(ctx, "Min Height", 1))
+                    .with_child({
+                        min_height = make_f32_input_field(ctx, 1, f32::MIN, f32::MAX, 0.1);
+                        min_height
+                    })
+                    .with_child(make_text_mark(ctx, "Max Height", 2))
+                    .with_child({
+                        max_height = make_f32_input_field(ctx, 2, f32::MIN, f32::MAX, 0.1);
+                        max_height
+                    })
+                    .with_child(make_text_mark(ctx, "Height Blend", 3))
+                    .with_child({
+                        height_blend = make_f32_input_field(ctx, 3, 0.0, f32::MAX, 0.1);
+                        height_blend
+                    })
+                    .with_child(make_text_mark(ctx, "Min Slope", 4))
+                    .with_child({
+                        min_slope = make_f32_input_field(ctx, 4, 0.0, 90.0, 1.0);
+                        min_slope
+                    })
+                    .with_child(make_text_mark(ctx, "Max Slope", 5))
+                    .with_child({
+                        max_slope = make_f32_input_field(ctx, 5, 0.0, 90.0, 1.0);
+                        max_slope
+                    })
+                    .with_child(make_text_mark(ctx, "Slope Blend", 6))
+                    .with_child({
+                        slope_blend = make_f32_input_field(ctx, 6, 0.0, 90.0, 1.0);
+                        slope_blend
+                    })
+                    .with_child(make_text_mark(ctx, "Noise Amount", 7))
+                    .with_child({
+                        noise_amount = make_f32_input_field(ctx, 7, 0.0, f32::MAX, 0.1);
+                        noise_amount
+                    })
+                    .with_child(make_text_mark(ctx, "Noise Frequency", 8))
+                    .with_child({
+                        noise_frequency = make_f32_input_field(ctx, 8, 0.0, f32::MAX, 0.01);
+                        noise_frequency
+                    })
+                    .with_child({
+                        generate = ButtonBuilder::new(WidgetBuilder::new().on_row(9).on_column(1))
+                            .with_text("Paint All Layers")
+                            .build(ctx);
+                        generate
+                    }),
+            )
+            .add_column(Column::strict(COLUMN_WIDTH))
+            .add_column(Column::stretch())
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .build(ctx),
+            ctx,
+        );
+
+        Self {
+            section,
+            enabled,
+            min_height,
+            max_height,
+            height_blend,
+            min_slope,
+            max_slope,
+            slope_blend,
+            noise_amount,
+            noise_frequency,
+            generate,
+            rules: Default::default(),
+        }
+    }
+
+    fn rule(&self, terrain: Handle<Node>, layer: &Layer) -> Option<&PaintRule> {
+        self.rules
+            .get(&terrain)?
+            .iter()
+            .find(|r| Arc::ptr_eq(&r.material, &layer.material))
+            .map(|r| &r.rule)
+    }
+
+    fn rule_mut(&mut self, terrain: Handle<Node>, layer: &Layer) -> &mut PaintRule {
+        let rules = self.rules.entry(terrain).or_default();
+        let index = match rules
+            .iter()
+            .position(|r| Arc::ptr_eq(&r.material, &layer.material))
+        {
+            Some(index) => index,
+            None => {
+                rules.push(LayerRule {
+                    material: layer.material.clone(),
+                    rule: Default::default(),
+                });
+                rules.len() - 1
+            }
+        };
+        &mut rules[index].rule
+    }
+
+    /// Returns rule of every current layer of the terrain, layers without a rule get disabled
+    /// default rule.
+    fn layer_rules(&self, handle: Handle<Node>, terrain: &Terrain) -> Vec<PaintRule> {
+        terrain
+            .layers()
+            .iter()
+            .map(|layer| self.rule(handle, layer).cloned().unwrap_or_default())
+            .collect()
+    }
+
+    pub fn sync_to_model(
+        &mut self,
+        handle: Handle<Node>,
+        terrain: &Terrain,
+        layer: Option<usize>,
+        ui: &UserInterface,
+    ) {
+        let rule = layer
+            .and_then(|i| terrain.layers().get(i))
+            .and_then(|layer| self.rule(handle, layer))
+            .cloned()
+            .unwrap_or_default();
+
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(self.enabled, MessageDirection::ToWidget, Some(rule.enabled)),
+        );
+
+        for &(field, value) in &[
+            (self.min_height, rule.min_height),
+            (self.max_height, rule.max_height),
+            (self.height_blend, rule.height_blend),
+            (self.min_slope, rule.min_slope),
+            (self.max_slope, rule.max_slope),
+            (self.slope_blend, rule.slope_blend),
+            (self.noise_amount, rule.noise_amount),
+            (self.noise_frequency, rule.noise_frequency),
+        ] {
+            send_sync_message(
+                ui,
+                NumericUpDownMessage::value(field, MessageDirection::ToWidget, value),
+            );
+        }
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        graph: &Graph,
+        handle: Handle<Node>,
+        layer: Option<usize>,
+        sender: &Sender<Message>,
+    ) {
+        scope_profile!();
+
+        let terrain = if let Node::Terrain(terrain) = &graph[handle] {
+            terrain
+        } else {
+            return;
+        };
+
+        match message.data() {
+            UiMessageData::Button(ButtonMessage::Click)
+                if message.destination() == self.generate =>
+            {
+                let rules = self.layer_rules(handle, terrain);
+                let commands = auto_paint::generate_masks(terrain, &rules)
+                    .into_iter()
+                    .map(|(layer, masks)| {
+                        SceneCommand::new(ModifyTerrainLayerMaskCommand::new(
+                            handle,
+                            copy_layer_masks(terrain, layer),
+                            masks,
+                            layer,
+                        ))
+                    })
+                    .collect::<Vec<_>>();
+
+                if !commands.is_empty() {
+                    sender
+                        .send(Message::do_scene_command(CommandGroup::from(commands)))
+                        .unwrap();
+                }
+            }
+            UiMessageData::CheckBox(CheckBoxMessage::Check(Some(value)))
+                if message.destination() == self.enabled =>
+            {
+                if let Some(layer) = layer.and_then(|i| terrain.layers().get(i)) {
+                    self.rule_mut(handle, layer).enabled = *value;
+                }
+            }
+            UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
+                if let Some(layer) = layer.and_then(|i| terrain.layers().get(i)) {
+                    let rule = self.rule_mut(handle, layer);
+                    let destination = message.destination();
+                    if destination == self.min_height {
+                        rule.min_height = *value;
+                    } else if destination == self.max_height {
+                        rule.max_height = *value;
+                    } else if destination == self.height_blend {
+                        rule.height_blend = *value;
+                    } else if destination == self.min_slope {
+                        rule.min_slope = *value;
+                    } else if destination == self.max_slope {
+                        rule.max_slope = *value;
+                    } else if destination == self.slope_blend {
+                        rule.slope_blend = *value;
+                    } else if destination == self.noise_amount {
+                        rule.noise_amount = *value;
+                    } else if destination == self.noise_frequency {
+                        rule.noise_frequency = *value;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,22 +1,32 @@
+use crate::asset::AssetItem;
 use crate::gui::make_dropdown_list_option;
 use crate::sidebar::make_section;
 use crate::{
-    send_sync_message,
+    make_relative_path, send_sync_message,
     sidebar::{make_f32_input_field, make_text_mark, COLUMN_WIDTH, ROW_HEIGHT},
+    terrain::brush::{Brush, BrushFalloff, BrushMode, BrushShape, StampMask},
+    Message,
 };
 use rg3d::gui::message::UiMessage;
 use rg3d::gui::{BuildContext, UiNode, UserInterface};
 use rg3d::{
     core::{pool::Handle, scope_profile},
+    engine::resource_manager::ResourceManager,
     gui::{
         dropdown_list::DropdownListBuilder,
         grid::{Column, GridBuilder, Row},
-        message::{DropdownListMessage, MessageDirection, NumericUpDownMessage, UiMessageData},
+        image::ImageBuilder,
+        message::{
+            DropdownListMessage, ImageMessage, MessageDirection, NumericUpDownMessage,
+            TextBoxMessage, UiMessageData, WidgetMessage,
+        },
+        text_box::TextBoxBuilder,
         widget::WidgetBuilder,
+        Thickness,
     },
-    scene::terrain::{Brush, BrushMode, BrushShape},
+    utils::into_gui_texture,
 };
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc::Sender, Arc, Mutex};
 
 pub struct BrushSection {
     pub section: Handle<UiNode>,
@@ -25,6 +35,17 @@ pub struct BrushSection {
     width: Handle<UiNode>,
     length: Handle<UiNode>,
     radius: Handle<UiNode>,
+    strength: Handle<UiNode>,
+    frequency: Handle<UiNode>,
+    falloff: Handle<UiNode>,
+    curve: Handle<UiNode>,
+    stamp_texture: Handle<UiNode>,
+    stamp_size: Handle<UiNode>,
+    stamp_rotation: Handle<UiNode>,
+    /// Keys of custom falloff curve, kept separately to survive switching of falloff kind.
+    curve_keys: Vec<f32>,
+    /// Last loaded stamp, kept separately to survive switching of brush kind.
+    stamp_mask: StampMask,
     pub brush: Arc<Mutex<Brush>>,
 }
 
@@ -35,6 +56,13 @@ impl BrushSection {
         let width;
         let length;
         let radius;
+        let strength;
+        let frequency;
+        let falloff;
+        let curve;
+        let stamp_texture;
+        let stamp_size;
+        let stamp_rotation;
         let section = make_section(
             "Brush Properties",
             GridBuilder::new(
@@ -46,6 +74,7 @@ impl BrushSection {
                                 .with_items(vec![
                                     make_dropdown_list_option(ctx, "Circle"),
                                     make_dropdown_list_option(ctx, "Rectangle"),
+                                    make_dropdown_list_option(ctx, "Stamp"),
                                 ])
                                 .with_selected(0)
                                 .build(ctx);
@@ -58,6 +87,10 @@ impl BrushSection {
                                 .with_items(vec![
                                     make_dropdown_list_option(ctx, "Modify Height Map"),
                                     make_dropdown_list_option(ctx, "Draw On Mask"),
+                                    make_dropdown_list_option(ctx, "Smooth"),
+                                    make_dropdown_list_option(ctx, "Flatten"),
+                                    make_dropdown_list_option(ctx, "Noise"),
+                                    make_dropdown_list_option(ctx, "Ramp"),
                                 ])
                                 .with_selected(0)
                                 .build(ctx);
@@ -77,6 +110,64 @@ impl BrushSection {
                     .with_child({
                         radius = make_f32_input_field(ctx, 4, 0.0, f32::MAX, 0.1);
                         radius
+                    })
+                    .with_child(make_text_mark(ctx, "Brush Strength", 5))
+                    .with_child({
+                        strength = make_f32_input_field(ctx, 5, 0.0, f32::MAX, 0.01);
+                        strength
+                    })
+                    .with_child(make_text_mark(ctx, "Noise Frequency", 6))
+                    .with_child({
+                        frequency = make_f32_input_field(ctx, 6, 0.0, f32::MAX, 0.01);
+                        frequency
+                    })
+                    .with_child(make_text_mark(ctx, "Falloff", 7))
+                    .with_child({
+                        falloff =
+                            DropdownListBuilder::new(WidgetBuilder::new().on_row(7).on_column(1))
+                                .with_items(vec![
+                                    make_dropdown_list_option(ctx, "Hard"),
+                                    make_dropdown_list_option(ctx, "Linear"),
+                                    make_dropdown_list_option(ctx, "Smooth"),
+                                    make_dropdown_list_option(ctx, "Spherical"),
+                                    make_dropdown_list_option(ctx, "Custom Curve"),
+                                ])
+                                .with_selected(0)
+                                .build(ctx);
+                        falloff
+                    })
+                    .with_child(make_text_mark(ctx, "Falloff Curve", 8))
+                    .with_child({
+                        curve = TextBoxBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(8)
+                                .on_column(1)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .build(ctx);
+                        curve
+                    })
+                    .with_child(make_text_mark(ctx, "Stamp Texture", 9))
+                    .with_child({
+                        stamp_texture = ImageBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(9)
+                                .on_column(1)
+                                .with_allow_drop(true)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .build(ctx);
+                        stamp_texture
+                    })
+                    .with_child(make_text_mark(ctx, "Stamp Size", 10))
+                    .with_child({
+                        stamp_size = make_f32_input_field(ctx, 10, 0.0, f32::MAX, 0.1);
+                        stamp_size
+                    })
+                    .with_child(make_text_mark(ctx, "Stamp Rotation", 11))
+                    .with_child({
+                        stamp_rotation = make_f32_input_field(ctx, 11, -360.0, 360.0, 1.0);
+                        stamp_rotation
                     }),
             )
             .add_column(Column::strict(COLUMN_WIDTH))
@@ -86,6 +177,13 @@ impl BrushSection {
             .add_row(Row::strict(ROW_HEIGHT))
             .add_row(Row::strict(ROW_HEIGHT))
             .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
             .build(ctx),
             ctx,
         );
@@ -97,10 +195,20 @@ impl BrushSection {
             width,
             length,
             radius,
+            strength,
+            frequency,
+            falloff,
+            curve,
+            stamp_texture,
+            stamp_size,
+            stamp_rotation,
+            curve_keys: vec![1.0, 1.0, 0.5, 0.0],
+            stamp_mask: Default::default(),
             brush: Arc::new(Mutex::new(Brush {
                 center: Default::default(),
                 shape: BrushShape::Circle { radius: 1.0 },
                 mode: BrushMode::ModifyHeightMap { amount: 0.25 },
+                falloff: BrushFalloff::Hard,
             })),
         }
     }
@@ -136,28 +244,98 @@ impl BrushSection {
                     NumericUpDownMessage::value(self.length, MessageDirection::ToWidget, length),
                 );
             }
-        }
+            BrushShape::Stamp { size, rotation, .. } => {
+                send_sync_message(
+                    ui,
+                    DropdownListMessage::selection(self.kind, MessageDirection::ToWidget, Some(2)),
+                );
 
-        match brush.mode {
-            BrushMode::ModifyHeightMap { .. } => {
                 send_sync_message(
                     ui,
-                    DropdownListMessage::selection(self.mode, MessageDirection::ToWidget, Some(0)),
+                    NumericUpDownMessage::value(self.stamp_size, MessageDirection::ToWidget, size),
                 );
-            }
-            BrushMode::DrawOnMask { .. } => {
+
                 send_sync_message(
                     ui,
-                    DropdownListMessage::selection(self.mode, MessageDirection::ToWidget, Some(1)),
+                    NumericUpDownMessage::value(
+                        self.stamp_rotation,
+                        MessageDirection::ToWidget,
+                        rotation.to_degrees(),
+                    ),
                 );
             }
         }
+
+        let falloff = match brush.falloff {
+            BrushFalloff::Hard => 0,
+            BrushFalloff::Linear => 1,
+            BrushFalloff::Smooth => 2,
+            BrushFalloff::Spherical => 3,
+            BrushFalloff::Curve(_) => 4,
+        };
+
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(self.falloff, MessageDirection::ToWidget, Some(falloff)),
+        );
+
+        send_sync_message(
+            ui,
+            TextBoxMessage::text(
+                self.curve,
+                MessageDirection::ToWidget,
+                self.curve_keys
+                    .iter()
+                    .map(|k| k.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        );
+
+        let (index, strength) = match brush.mode {
+            BrushMode::ModifyHeightMap { amount } => (0, amount),
+            BrushMode::DrawOnMask { alpha, .. } => (1, alpha),
+            BrushMode::Smooth { strength } => (2, strength),
+            BrushMode::Flatten { strength, .. } => (3, strength),
+            BrushMode::Noise {
+                amount, frequency, ..
+            } => {
+                send_sync_message(
+                    ui,
+                    NumericUpDownMessage::value(
+                        self.frequency,
+                        MessageDirection::ToWidget,
+                        frequency,
+                    ),
+                );
+
+                (4, amount)
+            }
+            BrushMode::Ramp { .. } => (5, 0.0),
+        };
+
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(self.mode, MessageDirection::ToWidget, Some(index)),
+        );
+
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(self.strength, MessageDirection::ToWidget, strength),
+        );
     }
 
-    pub fn handle_message(&mut self, message: &UiMessage) {
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        resource_manager: ResourceManager,
+        sender: &Sender<Message>,
+    ) {
         scope_profile!();
 
         let mut brush = self.brush.lock().unwrap();
+        let mut needs_sync = false;
 
         match message.data() {
             UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(Some(selection))) => {
@@ -172,9 +350,27 @@ impl BrushSection {
                                 length: 0.5,
                             }
                         }
+                        2 => {
+                            brush.shape = BrushShape::Stamp {
+                                size: 2.0,
+                                rotation: 0.0,
+                                mask: self.stamp_mask.clone(),
+                            }
+                        }
                         _ => unreachable!(),
                     }
+                    needs_sync = true;
+                } else if message.destination() == self.falloff {
+                    brush.falloff = match selection {
+                        0 => BrushFalloff::Hard,
+                        1 => BrushFalloff::Linear,
+                        2 => BrushFalloff::Smooth,
+                        3 => BrushFalloff::Spherical,
+                        4 => BrushFalloff::Curve(self.curve_keys.clone()),
+                        _ => unreachable!(),
+                    };
                 } else if message.destination() == self.mode {
+                    needs_sync = true;
                     match selection {
                         0 => brush.mode = BrushMode::ModifyHeightMap { amount: 0.25 },
                         1 => {
@@ -183,10 +379,101 @@ impl BrushSection {
                                 alpha: 1.0,
                             }
                         }
+                        2 => brush.mode = BrushMode::Smooth { strength: 0.5 },
+                        3 => {
+                            // Actual height will be sampled on click.
+                            brush.mode = BrushMode::Flatten {
+                                height: 0.0,
+                                strength: 0.5,
+                            }
+                        }
+                        4 => {
+                            brush.mode = BrushMode::Noise {
+                                amount: 0.1,
+                                frequency: 0.5,
+                                seed: 0,
+                            }
+                        }
+                        5 => {
+                            // Beginning of the ramp will be sampled on click.
+                            brush.mode = BrushMode::Ramp {
+                                begin: Default::default(),
+                            }
+                        }
                         _ => unreachable!(),
                     }
                 }
             }
+            UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value))
+                if message.destination() == self.strength =>
+            {
+                match &mut brush.mode {
+                    BrushMode::ModifyHeightMap { amount } | BrushMode::Noise { amount, .. } => {
+                        *amount = *value
+                    }
+                    BrushMode::DrawOnMask { alpha, .. } => *alpha = *value,
+                    BrushMode::Smooth { strength } | BrushMode::Flatten { strength, .. } => {
+                        *strength = *value
+                    }
+                    BrushMode::Ramp { .. } => (),
+                }
+            }
+            UiMessageData::TextBox(TextBoxMessage::Text(text))
+                if message.destination() == self.curve =>
+            {
+                self.curve_keys = text
+                    .split(',')
+                    .filter_map(|k| k.trim().parse::<f32>().ok())
+                    .collect();
+                if let BrushFalloff::Curve(keys) = &mut brush.falloff {
+                    *keys = self.curve_keys.clone();
+                }
+            }
+            UiMessageData::Widget(WidgetMessage::Drop(dropped))
+                if message.destination() == self.stamp_texture =>
+            {
+                if let Some(item) = ui.node(*dropped).cast::<AssetItem>() {
+                    match StampMask::load(&item.path) {
+                        Ok(mask) => {
+                            self.stamp_mask = mask.clone();
+
+                            match &mut brush.shape {
+                                BrushShape::Stamp { mask: current, .. } => *current = mask,
+                                shape => {
+                                    *shape = BrushShape::Stamp {
+                                        size: 2.0,
+                                        rotation: 0.0,
+                                        mask,
+                                    };
+                                    needs_sync = true;
+                                }
+                            }
+
+                            let texture = resource_manager
+                                .request_texture(make_relative_path(&item.path), None);
+                            ui.send_message(ImageMessage::texture(
+                                self.stamp_texture,
+                                MessageDirection::ToWidget,
+                                Some(into_gui_texture(texture)),
+                            ));
+                        }
+                        Err(e) => sender
+                            .send(Message::Log(format!(
+                                "Failed to load stamp {}! Reason: {:?}",
+                                item.path.display(),
+                                e
+                            )))
+                            .unwrap(),
+                    }
+                }
+            }
+            UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value))
+                if message.destination() == self.frequency =>
+            {
+                if let BrushMode::Noise { frequency, .. } = &mut brush.mode {
+                    *frequency = *value;
+                }
+            }
             UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
                 match &mut brush.shape {
                     BrushShape::Circle { radius } => {
@@ -201,9 +488,23 @@ impl BrushSection {
                             *width = *value;
                         }
                     }
+                    BrushShape::Stamp { size, rotation, .. } => {
+                        if message.destination() == self.stamp_size {
+                            *size = *value;
+                        } else if message.destination() == self.stamp_rotation {
+                            *rotation = value.to_radians();
+                        }
+                    }
                 }
             }
             _ => (),
         }
+
+        drop(brush);
+
+        // Each mode and shape has its own parameters, so fields must show new values.
+        if needs_sync {
+            self.sync_to_model(ui);
+        }
     }
 }
@@ -0,0 +1,462 @@
+//! Window that generates heights of a terrain procedurally and runs erosion over them.
+//! Results are previewed directly on the terrain and committed as a single command.
+
+use crate::gui::make_dropdown_list_option;
+use crate::{
+    scene::commands::terrain::ModifyTerrainHeightCommand,
+    send_sync_message,
+    sidebar::{
+        make_bool_input_field, make_f32_input_field, make_int_input_field, make_text_mark,
+        COLUMN_WIDTH, ROW_HEIGHT,
+    },
+    terrain::{
+        erosion::{HydraulicErosion, ThermalErosion},
+        generator::{set_heightmaps, NoiseKind, NoiseParameters, TerrainPreview},
+        ChunkLayout, Grid,
+    },
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::{pool::Handle, scope_profile},
+    gui::{
+        button::ButtonBuilder,
+        dropdown_list::DropdownListBuilder,
+        grid::{Column, GridBuilder, Row},
+        message::{
+            ButtonMessage, CheckBoxMessage, DropdownListMessage, MessageDirection,
+            NumericUpDownMessage, UiMessageData, WindowMessage,
+        },
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+        HorizontalAlignment, Orientation, Thickness,
+    },
+    scene::{graph::Graph, node::Node, terrain::Terrain},
+};
+use std::sync::mpsc::Sender;
+
+pub struct TerrainGenerator {
+    pub window: Handle<UiNode>,
+    use_noise_field: Handle<UiNode>,
+    noise_kind: Handle<UiNode>,
+    seed: Handle<UiNode>,
+    octaves: Handle<UiNode>,
+    frequency: Handle<UiNode>,
+    amplitude: Handle<UiNode>,
+    thermal_iterations: Handle<UiNode>,
+    talus: Handle<UiNode>,
+    droplets: Handle<UiNode>,
+    erosion_rate: Handle<UiNode>,
+    deposition_rate: Handle<UiNode>,
+    show_original: Handle<UiNode>,
+    preview_button: Handle<UiNode>,
+    apply: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    use_noise: bool,
+    noise: NoiseParameters,
+    thermal: ThermalErosion,
+    hydraulic: HydraulicErosion,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(60.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl TerrainGenerator {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let use_noise_field;
+        let noise_kind;
+        let seed;
+        let octaves;
+        let frequency;
+        let amplitude;
+        let thermal_iterations;
+        let talus;
+        let droplets;
+        let erosion_rate;
+        let deposition_rate;
+        let show_original;
+        let preview_button;
+        let apply;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(320.0).with_height(400.0))
+            .with_title(WindowTitle::text("Terrain Generator"))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(make_text_mark(ctx, "Generate Noise", 0))
+                        .with_child({
+                            use_noise_field = make_bool_input_field(ctx, 0);
+                            use_noise_field
+                        })
+                        .with_child(make_text_mark(ctx, "Noise Kind", 1))
+                        .with_child({
+                            noise_kind = DropdownListBuilder::new(
+                                WidgetBuilder::new().on_row(1).on_column(1),
+                            )
+                            .with_items(vec![
+                                make_dropdown_list_option(ctx, "fBm"),
+                                make_dropdown_list_option(ctx, "Ridged"),
+                            ])
+                            .with_selected(0)
+                            .build(ctx);
+                            noise_kind
+                        })
+                        .with_child(make_text_mark(ctx, "Seed", 2))
+                        .with_child({
+                            seed = make_int_input_field(ctx, 2, 0, i32::MAX, 1);
+                            seed
+                        })
+                        .with_child(make_text_mark(ctx, "Octaves", 3))
+                        .with_child({
+                            octaves = make_int_input_field(ctx, 3, 1, 16, 1);
+                            octaves
+                        })
+                        .with_child(make_text_mark(ctx, "Frequency", 4))
+                        .with_child({
+                            frequency = make_f32_input_field(ctx, 4, 0.0, f32::MAX, 0.001);
+                            frequency
+                        })
+                        .with_child(make_text_mark(ctx, "Amplitude", 5))
+                        .with_child({
+                            amplitude = make_f32_input_field(ctx, 5, 0.0, f32::MAX, 0.1);
+                            amplitude
+                        })
+                        .with_child(make_text_mark(ctx, "Thermal Iterations", 6))
+                        .with_child({
+                            thermal_iterations = make_int_input_field(ctx, 6, 0, 1000, 1);
+                            thermal_iterations
+                        })
+                        .with_child(make_text_mark(ctx, "Talus", 7))
+                        .with_child({
+                            talus = make_f32_input_field(ctx, 7, 0.0, f32::MAX, 0.01);
+                            talus
+                        })
+                        .with_child(make_text_mark(ctx, "Rain Droplets", 8))
+                        .with_child({
+                            droplets = make_int_input_field(ctx, 8, 0, 1_000_000, 1000);
+                            droplets
+                        })
+                        .with_child(make_text_mark(ctx, "Erosion Rate", 9))
+                        .with_child({
+                            erosion_rate = make_f32_input_field(ctx, 9, 0.0, 1.0, 0.01);
+                            erosion_rate
+                        })
+                        .with_child(make_text_mark(ctx, "Deposition Rate", 10))
+                        .with_child({
+                            deposition_rate = make_f32_input_field(ctx, 10, 0.0, 1.0, 0.01);
+                            deposition_rate
+                        })
+                        .with_child(make_text_mark(ctx, "Show Original", 11))
+                        .with_child({
+                            show_original = make_bool_input_field(ctx, 11);
+                            show_original
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(12)
+                                    .on_column(1)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        preview_button = make_button(ctx, "Preview");
+                                        preview_button
+                                    })
+                                    .with_child({
+                                        apply = make_button(ctx, "Apply");
+                                        apply
+                                    })
+                                    .with_child({
+                                        cancel = make_button(ctx, "Cancel");
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::strict(COLUMN_WIDTH))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(28.0))
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            use_noise_field,
+            noise_kind,
+            seed,
+            octaves,
+            frequency,
+            amplitude,
+            thermal_iterations,
+            talus,
+            droplets,
+            erosion_rate,
+            deposition_rate,
+            show_original,
+            preview_button,
+            apply,
+            cancel,
+            use_noise: true,
+            noise: Default::default(),
+            thermal: Default::default(),
+            hydraulic: Default::default(),
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+
+        self.sync_to_model(ui);
+    }
+
+    fn sync_to_model(&self, ui: &UserInterface) {
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(
+                self.use_noise_field,
+                MessageDirection::ToWidget,
+                Some(self.use_noise),
+            ),
+        );
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.noise_kind,
+                MessageDirection::ToWidget,
+                Some(match self.noise.kind {
+                    NoiseKind::Fbm => 0,
+                    NoiseKind::Ridged => 1,
+                }),
+            ),
+        );
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(self.show_original, MessageDirection::ToWidget, Some(false)),
+        );
+
+        for &(field, value) in &[
+            (self.seed, self.noise.seed as f32),
+            (self.octaves, self.noise.octaves as f32),
+            (self.frequency, self.noise.frequency),
+            (self.amplitude, self.noise.amplitude),
+            (self.thermal_iterations, self.thermal.iterations as f32),
+            (self.talus, self.thermal.talus),
+            (self.droplets, self.hydraulic.droplets as f32),
+            (self.erosion_rate, self.hydraulic.erosion_rate),
+            (self.deposition_rate, self.hydraulic.deposition_rate),
+        ] {
+            send_sync_message(
+                ui,
+                NumericUpDownMessage::value(field, MessageDirection::ToWidget, value),
+            );
+        }
+    }
+
+    fn generate(&self, terrain: &Terrain, original: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let layout = ChunkLayout::of_terrain(terrain);
+
+        let mut grid = if self.use_noise {
+            let mut grid = Grid::new(layout.grid_width(), layout.grid_length());
+            self.noise
+                .generate(&mut grid, terrain.width(), terrain.length());
+            grid
+        } else {
+            Grid::from_heightmaps(&layout, original)
+        };
+
+        self.thermal.apply(&mut grid);
+        HydraulicErosion {
+            seed: self.noise.seed,
+            ..self.hydraulic.clone()
+        }
+        .apply(&mut grid);
+
+        grid.to_heightmaps(&layout)
+    }
+
+    fn update_preview(
+        &self,
+        preview: &mut Option<TerrainPreview>,
+        graph: &mut Graph,
+        handle: Handle<Node>,
+        ui: &UserInterface,
+    ) {
+        if preview
+            .as_ref()
+            .map_or(false, |preview| preview.terrain != handle)
+        {
+            if let Some(preview) = preview.take() {
+                preview.discard(graph);
+            }
+        }
+
+        if let Node::Terrain(terrain) = &graph[handle] {
+            // Always generate from the original heights, otherwise erosion would be applied
+            // on top of previous preview.
+            let original = match preview.take() {
+                Some(preview) => preview.original,
+                None => terrain
+                    .chunks_ref()
+                    .iter()
+                    .map(|c| c.heightmap().to_vec())
+                    .collect(),
+            };
+
+            let generated = self.generate(terrain, &original);
+
+            set_heightmaps(graph, handle, &generated);
+
+            *preview = Some(TerrainPreview {
+                terrain: handle,
+                original,
+                generated,
+            });
+
+            send_sync_message(
+                ui,
+                CheckBoxMessage::checked(
+                    self.show_original,
+                    MessageDirection::ToWidget,
+                    Some(false),
+                ),
+            );
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+        graph: &mut Graph,
+        handle: Handle<Node>,
+        preview: &mut Option<TerrainPreview>,
+        sender: &Sender<Message>,
+    ) {
+        scope_profile!();
+
+        match message.data() {
+            UiMessageData::Button(ButtonMessage::Click) => {
+                if message.destination() == self.preview_button {
+                    self.update_preview(preview, graph, handle, ui);
+                } else if message.destination() == self.apply {
+                    if preview
+                        .as_ref()
+                        .map_or(true, |preview| preview.terrain != handle)
+                    {
+                        self.update_preview(preview, graph, handle, ui);
+                    }
+
+                    if let Some(preview) = preview.take() {
+                        // Command expects terrain to be in its original state.
+                        set_heightmaps(graph, preview.terrain, &preview.original);
+
+                        sender
+                            .send(Message::do_scene_command(ModifyTerrainHeightCommand::new(
+                                preview.terrain,
+                                preview.original,
+                                preview.generated,
+                            )))
+                            .unwrap();
+                    }
+
+                    ui.send_message(WindowMessage::close(
+                        self.window,
+                        MessageDirection::ToWidget,
+                    ));
+                } else if message.destination() == self.cancel {
+                    if let Some(preview) = preview.take() {
+                        preview.discard(graph);
+                    }
+
+                    ui.send_message(WindowMessage::close(
+                        self.window,
+                        MessageDirection::ToWidget,
+                    ));
+                }
+            }
+            UiMessageData::Window(WindowMessage::Close) if message.destination() == self.window => {
+                if let Some(preview) = preview.take() {
+                    preview.discard(graph);
+                }
+            }
+            UiMessageData::CheckBox(CheckBoxMessage::Check(Some(value))) => {
+                if message.destination() == self.use_noise_field {
+                    self.use_noise = *value;
+                } else if message.destination() == self.show_original {
+                    if let Some(preview) = preview.as_ref() {
+                        set_heightmaps(
+                            graph,
+                            preview.terrain,
+                            if *value {
+                                &preview.original
+                            } else {
+                                &preview.generated
+                            },
+                        );
+                    }
+                }
+            }
+            UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(Some(index)))
+                if message.destination() == self.noise_kind =>
+            {
+                self.noise.kind = if *index == 0 {
+                    NoiseKind::Fbm
+                } else {
+                    NoiseKind::Ridged
+                };
+            }
+            UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
+                let value = *value;
+                let destination = message.destination();
+                if destination == self.seed {
+                    self.noise.seed = value.max(0.0) as u32;
+                } else if destination == self.octaves {
+                    self.noise.octaves = value.max(1.0) as usize;
+                } else if destination == self.frequency {
+                    self.noise.frequency = value;
+                } else if destination == self.amplitude {
+                    self.noise.amplitude = value;
+                } else if destination == self.thermal_iterations {
+                    self.thermal.iterations = value.max(0.0) as usize;
+                } else if destination == self.talus {
+                    self.thermal.talus = value;
+                } else if destination == self.droplets {
+                    self.hydraulic.droplets = value.max(0.0) as usize;
+                } else if destination == self.erosion_rate {
+                    self.hydraulic.erosion_rate = value;
+                } else if destination == self.deposition_rate {
+                    self.hydraulic.deposition_rate = value;
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{
+    scene::commands::terrain::ModifyTerrainHeightCommand,
+    send_sync_message,
+    sidebar::{
+        make_f32_input_field, make_section, make_text_mark, terrain::generator::TerrainGenerator,
+        COLUMN_WIDTH, ROW_HEIGHT,
+    },
+    terrain::{
+        generator::TerrainPreview,
+        heightmap::{self, HeightRange},
+        ChunkLayout, Grid,
+    },
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::{pool::Handle, scope_profile},
+    gui::{
+        button::ButtonBuilder,
+        file_browser::{FileBrowserMode, FileSelectorBuilder, Filter},
+        grid::{Column, GridBuilder, Row},
+        message::{
+            ButtonMessage, FileSelectorMessage, MessageDirection, NumericUpDownMessage,
+            UiMessageData, WindowMessage,
+        },
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+};
+
+pub struct HeightmapSection {
+    pub section: Handle<UiNode>,
+    height_offset: Handle<UiNode>,
+    height_scale: Handle<UiNode>,
+    import: Handle<UiNode>,
+    export: Handle<UiNode>,
+    import_selector: Handle<UiNode>,
+    export_selector: Handle<UiNode>,
+    generate: Handle<UiNode>,
+    generator: TerrainGenerator,
+    range: HeightRange,
+}
+
+fn make_heightmap_file_filter() -> Filter {
+    Filter::new(|p: &Path| {
+        if p.extension().is_some() {
+            heightmap::HeightmapFormat::from_path(p).is_some()
+        } else {
+            p.is_dir()
+        }
+    })
+}
+
+impl HeightmapSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let range = HeightRange {
+            offset: 0.0,
+            scale: 10.0,
+        };
+
+        let height_offset;
+        let height_scale;
+        let import;
+        let export;
+        let generate;
+        let section = make_section(
+            "Height Map",
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(make_text_mark(ctx, "Height Offset", 0))
+                    .with_child({
+                        height_offset = make_f32_input_field(ctx, 0, f32::MIN, f32::MAX, 0.1);
+                        height_offset
+                    })
+                    .with_child(make_text_mark(ctx, "Height Scale", 1))
+                    .with_child({
+                        height_scale = make_f32_input_field(ctx, 1, 0.0, f32::MAX, 0.1);
+                        height_scale
+                    })
+                    .with_child({
+                        import = ButtonBuilder::new(WidgetBuilder::new().on_row(2).on_column(1))
+                            .with_text("Import...")
+                            .build(ctx);
+                        import
+                    })
+                    .with_child({
+                        export = ButtonBuilder::new(WidgetBuilder::new().on_row(3).on_column(1))
+                            .with_text("Export...")
+                            .build(ctx);
+                        export
+                    })
+                    .with_child({
+                        generate = ButtonBuilder::new(WidgetBuilder::new().on_row(4).on_column(1))
+                            .with_text("Generate...")
+                            .build(ctx);
+                        generate
+                    }),
+            )
+            .add_column(Column::strict(COLUMN_WIDTH))
+            .add_column(Column::stretch())
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .build(ctx),
+            ctx,
+        );
+
+        let import_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::Text("Import Height Map".into())),
+        )
+        .with_filter(make_heightmap_file_filter())
+        .build(ctx);
+
+        let export_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::Text("Export Height Map".into())),
+        )
+        .with_mode(FileBrowserMode::Save {
+            default_file_name: PathBuf::from("heightmap.png"),
+        })
+        .with_filter(make_heightmap_file_filter())
+        .build(ctx);
+
+        Self {
+            section,
+            height_offset,
+            height_scale,
+            import,
+            export,
+            import_selector,
+            export_selector,
+            generate,
+            generator: TerrainGenerator::new(ctx),
+            range,
+        }
+    }
+
+    pub fn sync_to_model(&mut self, ui: &mut UserInterface) {
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(
+                self.height_offset,
+                MessageDirection::ToWidget,
+                self.range.offset,
+            ),
+        );
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(
+                self.height_scale,
+                MessageDirection::ToWidget,
+                self.range.scale,
+            ),
+        );
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        graph: &mut Graph,
+        handle: Handle<Node>,
+        preview: &mut Option<TerrainPreview>,
+        sender: &Sender<Message>,
+    ) {
+        scope_profile!();
+
+        self.generator
+            .handle_ui_message(message, ui, graph, handle, preview, sender);
+
+        let terrain = if let Node::Terrain(terrain) = &graph[handle] {
+            terrain
+        } else {
+            return;
+        };
+
+        match message.data() {
+            UiMessageData::Button(ButtonMessage::Click) => {
+                if message.destination() == self.generate {
+                    self.generator.open(ui);
+                }
+
+                for &(button, selector) in &[
+                    (self.import, self.import_selector),
+                    (self.export, self.export_selector),
+                ] {
+                    if message.destination() == button {
+                        ui.send_message(WindowMessage::open_modal(
+                            selector,
+                            MessageDirection::ToWidget,
+                            true,
+                        ));
+                        ui.send_message(FileSelectorMessage::root(
+                            selector,
+                            MessageDirection::ToWidget,
+                            Some(std::env::current_dir().unwrap()),
+                        ));
+                    }
+                }
+            }
+            UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
+                if message.destination() == self.height_offset {
+                    self.range.offset = *value;
+                } else if message.destination() == self.height_scale {
+                    self.range.scale = *value;
+                }
+            }
+            UiMessageData::FileSelector(FileSelectorMessage::Commit(path)) => {
+                let layout = ChunkLayout::of_terrain(terrain);
+
+                if message.destination() == self.import_selector {
+                    match heightmap::load(path, self.range) {
+                        Ok(grid) => {
+                            let old_heightmaps = terrain
+                                .chunks_ref()
+                                .iter()
+                                .map(|c| c.heightmap().to_vec())
+                                .collect();
+                            let new_heightmaps = grid
+                                .resample(layout.grid_width(), layout.grid_length())
+                                .to_heightmaps(&layout);
+
+                            sender
+                                .send(Message::do_scene_command(ModifyTerrainHeightCommand::new(
+                                    handle,
+                                    old_heightmaps,
+                                    new_heightmaps,
+                                )))
+                                .unwrap();
+                        }
+                        Err(e) => sender
+                            .send(Message::Log(format!(
+                                "Failed to import height map {}! Reason: {:?}",
+                                path.display(),
+                                e
+                            )))
+                            .unwrap(),
+                    }
+                } else if message.destination() == self.export_selector {
+                    // Heights are exported with the same offset and scale as they are imported,
+                    // so the file can be imported back without any loss.
+                    let grid = Grid::from_terrain(terrain);
+
+                    let text = match heightmap::save(&grid, path, self.range) {
+                        Ok(_) => {
+                            let mut text = format!(
+                                "Height map was exported to {} with height offset {} and scale {}",
+                                path.display(),
+                                self.range.offset,
+                                self.range.scale
+                            );
+
+                            let actual = HeightRange::of_grid(&grid);
+                            let (min, max) = (
+                                self.range.offset.min(self.range.offset + self.range.scale),
+                                self.range.offset.max(self.range.offset + self.range.scale),
+                            );
+                            if actual.offset < min || actual.offset + actual.scale > max {
+                                text += &format!(
+                                    ". Heights from {} to {} do not fit into the range and were \
+                                    clamped, use height offset {} and scale {} to keep all of them.",
+                                    actual.offset,
+                                    actual.offset + actual.scale,
+                                    actual.offset,
+                                    actual.scale
+                                );
+                            }
+
+                            text
+                        }
+                        Err(e) => format!(
+                            "Failed to export height map to {}! Reason: {:?}",
+                            path.display(),
+                            e
+                        ),
+                    };
+                    sender.send(Message::Log(text)).unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+}
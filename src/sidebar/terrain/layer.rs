@@ -1,6 +1,8 @@
 use crate::{
+    scene::commands::terrain::ModifyTerrainLayerMaskCommand,
     send_sync_message,
     sidebar::{make_section, make_text_mark, COLUMN_WIDTH, ROW_HEIGHT},
+    terrain::mask::{self, MaskLayout},
     Message,
 };
 use rg3d::gui::message::UiMessage;
@@ -9,22 +11,46 @@ use rg3d::{
     core::{pool::Handle, scope_profile},
     gui::{
         button::ButtonBuilder,
+        file_browser::{FileBrowserMode, FileSelectorBuilder, Filter},
         grid::{Column, GridBuilder, Row},
-        message::{ButtonMessage, MessageDirection, UiMessageData, WidgetMessage},
+        message::{
+            ButtonMessage, FileSelectorMessage, MessageDirection, UiMessageData, WidgetMessage,
+            WindowMessage,
+        },
         widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
     },
     scene::{graph::Graph, node::Node, terrain::Layer},
 };
-use std::sync::mpsc::Sender;
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+};
 
 pub struct LayerSection {
     pub section: Handle<UiNode>,
     material: Handle<UiNode>,
+    import_mask: Handle<UiNode>,
+    export_mask: Handle<UiNode>,
+    import_selector: Handle<UiNode>,
+    export_selector: Handle<UiNode>,
+}
+
+fn make_png_file_filter() -> Filter {
+    Filter::new(|p: &Path| {
+        if let Some(ext) = p.extension() {
+            ext.to_string_lossy().to_lowercase() == "png"
+        } else {
+            p.is_dir()
+        }
+    })
 }
 
 impl LayerSection {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let material;
+        let import_mask;
+        let export_mask;
         let section = make_section(
             "Layer Properties",
             GridBuilder::new(
@@ -35,16 +61,59 @@ impl LayerSection {
                             .with_text("...")
                             .build(ctx);
                         material
+                    })
+                    .with_child(make_text_mark(ctx, "Mask", 1))
+                    .with_child({
+                        import_mask =
+                            ButtonBuilder::new(WidgetBuilder::new().on_row(1).on_column(1))
+                                .with_text("Import...")
+                                .build(ctx);
+                        import_mask
+                    })
+                    .with_child({
+                        export_mask =
+                            ButtonBuilder::new(WidgetBuilder::new().on_row(2).on_column(1))
+                                .with_text("Export...")
+                                .build(ctx);
+                        export_mask
                     }),
             )
             .add_column(Column::strict(COLUMN_WIDTH))
             .add_column(Column::stretch())
             .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
+            .add_row(Row::strict(ROW_HEIGHT))
             .build(ctx),
             ctx,
         );
 
-        Self { section, material }
+        let import_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::Text("Import Layer Mask".into())),
+        )
+        .with_filter(make_png_file_filter())
+        .build(ctx);
+
+        let export_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::Text("Export Layer Mask".into())),
+        )
+        .with_mode(FileBrowserMode::Save {
+            default_file_name: PathBuf::from("mask.png"),
+        })
+        .with_filter(make_png_file_filter())
+        .build(ctx);
+
+        Self {
+            section,
+            material,
+            import_mask,
+            export_mask,
+            import_selector,
+            export_selector,
+        }
     }
 
     pub fn sync_to_model(&mut self, layer: Option<&Layer>, ui: &mut UserInterface) {
@@ -57,21 +126,94 @@ impl LayerSection {
     pub fn handle_message(
         &mut self,
         message: &UiMessage,
+        ui: &UserInterface,
         graph: &Graph,
         node_handle: Handle<Node>,
         layer_index: usize,
         sender: &Sender<Message>,
     ) {
         scope_profile!();
-        if let UiMessageData::Button(ButtonMessage::Click) = message.data() {}
-        if message.destination() == self.material {
-            sender
-                .send(Message::OpenMaterialEditor(
-                    graph[node_handle].as_terrain().layers()[layer_index]
-                        .material
-                        .clone(),
-                ))
-                .unwrap();
+
+        match message.data() {
+            UiMessageData::Button(ButtonMessage::Click) => {
+                if message.destination() == self.material {
+                    sender
+                        .send(Message::OpenMaterialEditor(
+                            graph[node_handle].as_terrain().layers()[layer_index]
+                                .material
+                                .clone(),
+                        ))
+                        .unwrap();
+                }
+
+                for &(button, selector) in &[
+                    (self.import_mask, self.import_selector),
+                    (self.export_mask, self.export_selector),
+                ] {
+                    if message.destination() == button {
+                        ui.send_message(WindowMessage::open_modal(
+                            selector,
+                            MessageDirection::ToWidget,
+                            true,
+                        ));
+                        ui.send_message(FileSelectorMessage::root(
+                            selector,
+                            MessageDirection::ToWidget,
+                            Some(std::env::current_dir().unwrap()),
+                        ));
+                    }
+                }
+            }
+            UiMessageData::FileSelector(FileSelectorMessage::Commit(path)) => {
+                let terrain = if let Node::Terrain(terrain) = &graph[node_handle] {
+                    terrain
+                } else {
+                    return;
+                };
+
+                let layout = if let Some(layout) = MaskLayout::of_terrain(terrain) {
+                    layout
+                } else {
+                    return;
+                };
+
+                if message.destination() == self.import_selector {
+                    match mask::load(path, &layout) {
+                        Ok(pixels) => sender
+                            .send(Message::do_scene_command(
+                                ModifyTerrainLayerMaskCommand::new(
+                                    node_handle,
+                                    mask::copy_layer_masks(terrain, layer_index),
+                                    mask::split(&layout, &pixels),
+                                    layer_index,
+                                ),
+                            ))
+                            .unwrap(),
+                        Err(e) => sender
+                            .send(Message::Log(format!(
+                                "Failed to import layer mask {}! Reason: {:?}",
+                                path.display(),
+                                e
+                            )))
+                            .unwrap(),
+                    }
+                } else if message.destination() == self.export_selector {
+                    let pixels =
+                        mask::stitch(&layout, &mask::copy_layer_masks(terrain, layer_index));
+                    let text = match mask::save(path, &layout, pixels) {
+                        Ok(_) => {
+                            format!("Layer mask was successfully exported to {}", path.display())
+                        }
+                        Err(e) => format!(
+                            "Failed to export layer mask to {}! Reason: {:?}",
+                            path.display(),
+                            e
+                        ),
+                    };
+                    sender.send(Message::Log(text)).unwrap();
+                }
+            }
+            _ => {}
         }
     }
 }
@@ -5,15 +5,20 @@ use crate::{
     send_sync_message,
     sidebar::{
         make_int_input_field, make_section, make_text_mark,
-        terrain::{brush::BrushSection, layer::LayerSection},
+        terrain::{
+            auto_paint::AutoPaintSection, brush::BrushSection, heightmap::HeightmapSection,
+            layer::LayerSection, resize::TerrainResizer,
+        },
         COLUMN_WIDTH, ROW_HEIGHT,
     },
+    terrain::{brush::BrushMode, generator::TerrainPreview},
     Message,
 };
 use rg3d::gui::message::UiMessage;
 use rg3d::gui::{BuildContext, UiNode, UserInterface};
 use rg3d::{
     core::{algebra::Vector2, pool::Handle, scope_profile},
+    engine::resource_manager::ResourceManager,
     gui::{
         border::BorderBuilder,
         button::ButtonBuilder,
@@ -29,33 +34,44 @@ use rg3d::{
         widget::WidgetBuilder,
         Orientation,
     },
-    scene::{graph::Graph, node::Node, terrain::BrushMode},
+    scene::{graph::Graph, node::Node},
 };
 use std::sync::mpsc::Sender;
 
+mod auto_paint;
 mod brush;
+mod generator;
+mod heightmap;
 mod layer;
+mod resize;
 
 pub struct TerrainSection {
     pub section: Handle<UiNode>,
     pub brush_section: BrushSection,
+    heightmap_section: HeightmapSection,
     layers: Handle<UiNode>,
     add_layer: Handle<UiNode>,
     remove_layer: Handle<UiNode>,
     current_layer: Option<usize>,
     layer_section: LayerSection,
+    auto_paint_section: AutoPaintSection,
     decal_layer_index: Handle<UiNode>,
+    resize: Handle<UiNode>,
+    resizer: TerrainResizer,
 }
 
 impl TerrainSection {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let brush_section = BrushSection::new(ctx);
+        let heightmap_section = HeightmapSection::new(ctx);
         let layer_section = LayerSection::new(ctx);
+        let auto_paint_section = AutoPaintSection::new(ctx);
 
         let layers;
         let add_layer;
         let remove_layer;
         let decal_layer_index;
+        let resize;
         let section = make_section(
             "Terrain Properties",
             StackPanelBuilder::new(
@@ -107,15 +123,26 @@ impl TerrainSection {
                                 .with_child({
                                     decal_layer_index = make_int_input_field(ctx, 0, 0, 255, 1);
                                     decal_layer_index
+                                })
+                                .with_child({
+                                    resize = ButtonBuilder::new(
+                                        WidgetBuilder::new().on_row(1).on_column(1),
+                                    )
+                                    .with_text("Resize...")
+                                    .build(ctx);
+                                    resize
                                 }),
                         )
                         .add_row(Row::strict(ROW_HEIGHT))
+                        .add_row(Row::strict(ROW_HEIGHT))
                         .add_column(Column::strict(COLUMN_WIDTH))
                         .add_column(Column::stretch())
                         .build(ctx),
                     )
                     .with_child(brush_section.section)
-                    .with_child(layer_section.section),
+                    .with_child(heightmap_section.section)
+                    .with_child(layer_section.section)
+                    .with_child(auto_paint_section.section),
             )
             .with_orientation(Orientation::Vertical)
             .build(ctx),
@@ -127,14 +154,18 @@ impl TerrainSection {
             layers,
             add_layer,
             brush_section,
+            heightmap_section,
             remove_layer,
             layer_section,
+            auto_paint_section,
             decal_layer_index,
+            resize,
+            resizer: TerrainResizer::new(ctx),
             current_layer: None,
         }
     }
 
-    pub fn sync_to_model(&mut self, node: &Node, ui: &mut UserInterface) {
+    pub fn sync_to_model(&mut self, handle: Handle<Node>, node: &Node, ui: &mut UserInterface) {
         send_sync_message(
             ui,
             WidgetMessage::visibility(self.section, MessageDirection::ToWidget, node.is_terrain()),
@@ -173,29 +204,41 @@ impl TerrainSection {
 
             self.layer_section
                 .sync_to_model(self.current_layer.and_then(|i| terrain.layers().get(i)), ui);
+            self.auto_paint_section
+                .sync_to_model(handle, terrain, self.current_layer, ui);
         }
 
         self.brush_section.sync_to_model(ui);
+        self.heightmap_section.sync_to_model(ui);
     }
 
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
         ui: &mut UserInterface,
-        graph: &Graph,
+        graph: &mut Graph,
         handle: Handle<Node>,
+        resource_manager: ResourceManager,
+        preview: &mut Option<TerrainPreview>,
         sender: &Sender<Message>,
     ) {
         scope_profile!();
 
-        let node = &graph[handle];
-
         if let Some(index) = self.current_layer {
             self.layer_section
-                .handle_message(message, graph, handle, index, sender);
+                .handle_message(message, ui, graph, handle, index, sender);
         }
 
-        self.brush_section.handle_message(message);
+        self.brush_section
+            .handle_message(message, ui, resource_manager, sender);
+        self.heightmap_section
+            .handle_message(message, ui, graph, handle, preview, sender);
+        self.auto_paint_section
+            .handle_message(message, graph, handle, self.current_layer, sender);
+        self.resizer
+            .handle_ui_message(message, ui, graph, handle, sender);
+
+        let node = &graph[handle];
 
         let mut brush = self.brush_section.brush.lock().unwrap();
         if let BrushMode::DrawOnMask { layer, .. } = &mut brush.mode {
@@ -220,12 +263,14 @@ impl TerrainSection {
                                 )))
                                 .unwrap()
                         }
+                    } else if message.destination() == self.resize {
+                        self.resizer.open(terrain, ui);
                     }
                 }
                 UiMessageData::ListView(ListViewMessage::SelectionChanged(layer_index)) => {
                     if message.destination() == self.layers && self.current_layer != layer_index {
                         self.current_layer = layer_index;
-                        self.sync_to_model(node, ui);
+                        self.sync_to_model(handle, node, ui);
                     }
                 }
                 UiMessageData::NumericUpDown(NumericUpDownMessage::Value(index))
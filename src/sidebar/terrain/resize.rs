@@ -0,0 +1,239 @@
+//! Window that changes size and resolution of a terrain. Terrain is rebuilt with resampled
+//! heights and masks and swapped with the old one by a single command.
+
+use crate::{
+    scene::commands::terrain::ReplaceTerrainCommand,
+    send_sync_message,
+    sidebar::{
+        make_f32_input_field, make_int_input_field, make_text_mark, COLUMN_WIDTH, ROW_HEIGHT,
+    },
+    terrain::resize::{self, TerrainSize},
+    Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::{pool::Handle, scope_profile},
+    gui::{
+        button::ButtonBuilder,
+        grid::{Column, GridBuilder, Row},
+        message::{
+            ButtonMessage, MessageDirection, NumericUpDownMessage, UiMessageData, WindowMessage,
+        },
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+        HorizontalAlignment, Orientation, Thickness,
+    },
+    scene::{graph::Graph, node::Node, terrain::Terrain},
+};
+use std::sync::mpsc::Sender;
+
+pub struct TerrainResizer {
+    pub window: Handle<UiNode>,
+    width: Handle<UiNode>,
+    length: Handle<UiNode>,
+    width_chunks: Handle<UiNode>,
+    length_chunks: Handle<UiNode>,
+    height_map_resolution: Handle<UiNode>,
+    mask_resolution: Handle<UiNode>,
+    apply: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    size: Option<TerrainSize>,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(60.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl TerrainResizer {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let width;
+        let length;
+        let width_chunks;
+        let length_chunks;
+        let height_map_resolution;
+        let mask_resolution;
+        let apply;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(320.0).with_height(230.0))
+            .with_title(WindowTitle::text("Resize Terrain"))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(make_text_mark(ctx, "Width", 0))
+                        .with_child({
+                            width = make_f32_input_field(ctx, 0, 0.1, f32::MAX, 1.0);
+                            width
+                        })
+                        .with_child(make_text_mark(ctx, "Length", 1))
+                        .with_child({
+                            length = make_f32_input_field(ctx, 1, 0.1, f32::MAX, 1.0);
+                            length
+                        })
+                        .with_child(make_text_mark(ctx, "Width Chunks", 2))
+                        .with_child({
+                            width_chunks = make_int_input_field(ctx, 2, 1, 256, 1);
+                            width_chunks
+                        })
+                        .with_child(make_text_mark(ctx, "Length Chunks", 3))
+                        .with_child({
+                            length_chunks = make_int_input_field(ctx, 3, 1, 256, 1);
+                            length_chunks
+                        })
+                        .with_child(make_text_mark(ctx, "Height Map Resolution", 4))
+                        .with_child({
+                            height_map_resolution = make_f32_input_field(ctx, 4, 0.1, 64.0, 0.5);
+                            height_map_resolution
+                        })
+                        .with_child(make_text_mark(ctx, "Mask Resolution", 5))
+                        .with_child({
+                            mask_resolution = make_f32_input_field(ctx, 5, 0.1, 256.0, 1.0);
+                            mask_resolution
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(6)
+                                    .on_column(1)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        apply = make_button(ctx, "Apply");
+                                        apply
+                                    })
+                                    .with_child({
+                                        cancel = make_button(ctx, "Cancel");
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::strict(COLUMN_WIDTH))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(28.0))
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            width,
+            length,
+            width_chunks,
+            length_chunks,
+            height_map_resolution,
+            mask_resolution,
+            apply,
+            cancel,
+            size: None,
+        }
+    }
+
+    pub fn open(&mut self, terrain: &Terrain, ui: &UserInterface) {
+        self.size = Some(TerrainSize::of_terrain(terrain));
+
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+
+        self.sync_to_model(ui);
+    }
+
+    fn sync_to_model(&self, ui: &UserInterface) {
+        if let Some(size) = self.size.as_ref() {
+            for &(field, value) in &[
+                (self.width, size.width),
+                (self.length, size.length),
+                (self.width_chunks, size.width_chunks as f32),
+                (self.length_chunks, size.length_chunks as f32),
+                (self.height_map_resolution, size.height_map_resolution),
+                (self.mask_resolution, size.mask_resolution),
+            ] {
+                send_sync_message(
+                    ui,
+                    NumericUpDownMessage::value(field, MessageDirection::ToWidget, value),
+                );
+            }
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+        graph: &Graph,
+        handle: Handle<Node>,
+        sender: &Sender<Message>,
+    ) {
+        scope_profile!();
+
+        match message.data() {
+            UiMessageData::Button(ButtonMessage::Click) => {
+                if message.destination() == self.apply {
+                    if let (Some(size), Node::Terrain(terrain)) = (self.size, &graph[handle]) {
+                        // Keep the window open, so the size can be corrected.
+                        if let Err(reason) = size.validate() {
+                            sender.send(Message::Log(reason)).unwrap();
+                            return;
+                        }
+
+                        self.size = None;
+                        sender
+                            .send(Message::do_scene_command(ReplaceTerrainCommand::new(
+                                handle,
+                                resize::resize(terrain, &size),
+                            )))
+                            .unwrap();
+                    }
+
+                    ui.send_message(WindowMessage::close(
+                        self.window,
+                        MessageDirection::ToWidget,
+                    ));
+                } else if message.destination() == self.cancel {
+                    ui.send_message(WindowMessage::close(
+                        self.window,
+                        MessageDirection::ToWidget,
+                    ));
+                }
+            }
+            UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
+                if let Some(size) = self.size.as_mut() {
+                    let value = *value;
+                    let destination = message.destination();
+                    if destination == self.width {
+                        size.width = value;
+                    } else if destination == self.length {
+                        size.length = value;
+                    } else if destination == self.width_chunks {
+                        size.width_chunks = value.max(1.0) as usize;
+                    } else if destination == self.length_chunks {
+                        size.length_chunks = value.max(1.0) as usize;
+                    } else if destination == self.height_map_resolution {
+                        size.height_map_resolution = value;
+                    } else if destination == self.mask_resolution {
+                        size.mask_resolution = value;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,31 +1,56 @@
 use crate::{
-    scene::{commands::ChangeSelectionCommand, EditorScene, Selection},
+    scene::{
+        commands::{
+            sound::SetSoundSourceNodeCommand, ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
     send_sync_message, utils, GameEngine, Message,
 };
-use rg3d::core::algebra::Vector3;
+use rg3d::core::algebra::{Matrix3, Vector3};
 use rg3d::gui::list_view::ListView;
 use rg3d::gui::message::UiMessage;
 use rg3d::gui::widget::Widget;
 use rg3d::gui::BuildContext;
-use rg3d::sound::context::SoundContext;
+use rg3d::sound::context::{SoundContext, State};
 use rg3d::{
-    core::pool::Handle,
+    core::{
+        futures::executor::block_on,
+        math::Matrix4Ext,
+        pool::Handle,
+        visitor::{Visit, VisitResult, Visitor},
+    },
+    engine::resource_manager::ResourceManager,
     gui::{
         border::BorderBuilder,
+        button::ButtonBuilder,
+        check_box::CheckBoxBuilder,
         decorator::DecoratorBuilder,
+        grid::{Column, GridBuilder, Row},
         list_view::ListViewBuilder,
-        message::{ListViewMessage, MessageDirection, TextMessage, UiMessageData},
+        message::{
+            ButtonMessage, CheckBoxMessage, ListViewMessage, MessageDirection, TextMessage,
+            UiMessageData,
+        },
+        stack_panel::StackPanelBuilder,
         text::TextBuilder,
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowTitle},
-        Control, NodeHandleMapping, UiNode, UserInterface,
+        Control, NodeHandleMapping, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::{node::Node, Scene},
+    sound::{
+        listener::Listener,
+        source::{generic::GenericSourceBuilder, spatial::SpatialSourceBuilder, SoundSource},
     },
-    sound::source::SoundSource,
 };
 use std::any::Any;
 use std::cmp::Ordering;
 use std::{
+    collections::HashMap,
     ops::{Deref, DerefMut},
+    path::Path,
     sync::mpsc::Sender,
 };
 
@@ -35,6 +60,10 @@ pub struct SoundSelection {
 }
 
 impl SoundSelection {
+    pub fn new(sources: Vec<Handle<SoundSource>>) -> Self {
+        Self { sources }
+    }
+
     pub fn sources(&self) -> &[Handle<SoundSource>] {
         &self.sources
     }
@@ -77,6 +106,88 @@ impl PartialEq for SoundSelection {
 
 impl Eq for SoundSelection {}
 
+/// Editor-level binding of sound sources to scene nodes, a bound spatial source follows
+/// global position of its node. Engine has no such binding, so it is saved in scene file
+/// next to the scene itself.
+#[derive(Default, Debug, Clone)]
+pub struct SoundBinder {
+    map: HashMap<Handle<SoundSource>, Handle<Node>>,
+}
+
+impl SoundBinder {
+    /// Name of the field of scene file.
+    pub const FIELD_NAME: &'static str = "SoundBinder";
+
+    /// Loads binding saved with a scene. Scenes without the field have no bound sources.
+    pub fn load(path: &Path) -> Self {
+        let mut binder = Self::default();
+        if let Ok(mut visitor) = block_on(Visitor::load_binary(path)) {
+            if binder.visit(Self::FIELD_NAME, &mut visitor).is_err() {
+                binder = Self::default();
+            }
+        }
+        binder
+    }
+
+    /// Binds source to the node, or unbinds it if the node is none. Returns previous node of
+    /// the source.
+    pub fn bind(&mut self, source: Handle<SoundSource>, node: Handle<Node>) -> Handle<Node> {
+        if node.is_some() {
+            self.map.insert(source, node)
+        } else {
+            self.map.remove(&source)
+        }
+        .unwrap_or_default()
+    }
+
+    pub fn node_of(&self, source: Handle<SoundSource>) -> Handle<Node> {
+        self.map.get(&source).cloned().unwrap_or_default()
+    }
+
+    pub fn sources_of(&self, node: Handle<Node>) -> impl Iterator<Item = Handle<SoundSource>> + '_ {
+        self.map
+            .iter()
+            .filter(move |(_, &n)| n == node)
+            .map(|(&source, _)| source)
+    }
+
+    /// Makes binding with nodes replaced by their pairs from the mapping. Sources of nodes
+    /// that are not in the mapping are dropped.
+    pub fn remap(&self, old_to_new: &HashMap<Handle<Node>, Handle<Node>>) -> Self {
+        Self {
+            map: self
+                .map
+                .iter()
+                .filter_map(|(&source, node)| old_to_new.get(node).map(|&node| (source, node)))
+                .collect(),
+        }
+    }
+
+    /// Moves bound spatial sources to their nodes, must be called every frame since nodes can
+    /// move at any time.
+    pub fn update(&self, scene: &Scene) {
+        let mut state = scene.sound_context.state();
+        for (&source, &node) in self.map.iter() {
+            if !state.is_valid_handle(source) || !scene.graph.is_valid_handle(node) {
+                continue;
+            }
+            if let SoundSource::Spatial(spatial) = state.source_mut(source) {
+                spatial.set_position(scene.graph[node].global_position());
+            }
+        }
+    }
+}
+
+impl Visit for SoundBinder {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        visitor.enter_region(name)?;
+
+        self.map.visit("Map", visitor)?;
+
+        visitor.leave_region()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SoundItem {
     widget: Widget,
@@ -133,6 +244,12 @@ impl Control for SoundItem {
     }
 }
 
+impl SoundItem {
+    pub fn sound_source(&self) -> Handle<SoundSource> {
+        self.sound_source
+    }
+}
+
 pub struct SoundItemBuilder {
     widget_builder: WidgetBuilder,
     name: String,
@@ -186,6 +303,17 @@ impl SoundItemBuilder {
 pub struct SoundPanel {
     pub window: Handle<UiNode>,
     sounds: Handle<UiNode>,
+    play: Handle<UiNode>,
+    pause: Handle<UiNode>,
+    stop: Handle<UiNode>,
+    detach: Handle<UiNode>,
+    mute: Handle<UiNode>,
+    listener_at_camera: Handle<UiNode>,
+    /// Master gain of scene is zero while muted, previous gain is restored on unmute.
+    muted: bool,
+    /// Listener of sound context follows editor camera so spatial sources can be heard the way
+    /// they would be heard from camera's position.
+    bind_listener: bool,
 }
 
 fn fetch_source(handle: Handle<UiNode>, ui: &UserInterface) -> Handle<SoundSource> {
@@ -196,17 +324,192 @@ fn fetch_source(handle: Handle<UiNode>, ui: &UserInterface) -> Handle<SoundSourc
     }
 }
 
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(50.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_check_box(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    CheckBoxBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+        .with_content(
+            TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_vertical_alignment(VerticalAlignment::Center),
+            )
+            .with_text(text)
+            .build(ctx),
+        )
+        .checked(Some(false))
+        .build(ctx)
+}
+
+/// Values of sound context that were replaced by audition options, they are put back when
+/// an option is turned off or when the scene is saved.
+#[derive(Default)]
+pub struct AuditionBackup {
+    master_gain: Option<f32>,
+    listener: Option<(Vector3<f32>, Matrix3<f32>)>,
+}
+
+impl AuditionBackup {
+    fn restore_master_gain(&mut self, state: &mut State) {
+        if let Some(gain) = self.master_gain.take() {
+            state.set_master_gain(gain);
+        }
+    }
+
+    fn restore_listener(&mut self, listener: &mut Listener) {
+        if let Some((position, basis)) = self.listener.take() {
+            listener.set_position(position);
+            listener.set_basis(basis);
+        }
+    }
+}
+
+/// Stops every source and puts master gain and listener back to the values they had before
+/// audition, so nothing of audition gets into a saved scene.
+pub fn reset_playback(sound_context: &SoundContext, backup: &mut AuditionBackup) {
+    let mut state = sound_context.state();
+    for source in state.sources_mut().iter_mut() {
+        // Stopping can fail only for streaming buffers that are failed to rewind, such source
+        // will be just silent.
+        let _ = source.stop();
+    }
+    backup.restore_master_gain(&mut state);
+    backup.restore_listener(state.listener_mut());
+}
+
+/// Makes spatial source at given position that plays sound from the file, source is named
+/// after the file.
+pub fn make_spatial_source(
+    path: &Path,
+    position: Vector3<f32>,
+    resource_manager: ResourceManager,
+) -> Result<SoundSource, String> {
+    let buffer = block_on(resource_manager.request_sound_buffer(path, false))
+        .map_err(|_| format!("Unable to load sound buffer {}!", path.display()))?;
+
+    let generic = GenericSourceBuilder::new()
+        .with_buffer(buffer)
+        .with_name(
+            path.file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+        )
+        .build()
+        .map_err(|e| format!("Unable to create sound source. Reason: {:?}", e))?;
+
+    Ok(SpatialSourceBuilder::new(generic)
+        .with_position(position)
+        .build_source())
+}
+
 impl SoundPanel {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let sounds;
+        let play;
+        let pause;
+        let stop;
+        let detach;
+        let mute;
+        let listener_at_camera;
         let window = WindowBuilder::new(WidgetBuilder::new())
             .with_title(WindowTitle::text("Sounds"))
-            .with_content({
-                sounds = ListViewBuilder::new(WidgetBuilder::new()).build(ctx);
-                sounds
-            })
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_child({
+                                        play = make_button(ctx, "Play");
+                                        play
+                                    })
+                                    .with_child({
+                                        pause = make_button(ctx, "Pause");
+                                        pause
+                                    })
+                                    .with_child({
+                                        stop = make_button(ctx, "Stop");
+                                        stop
+                                    })
+                                    .with_child({
+                                        detach = make_button(ctx, "Detach");
+                                        detach
+                                    })
+                                    .with_child({
+                                        mute = make_check_box(ctx, "Mute");
+                                        mute
+                                    })
+                                    .with_child({
+                                        listener_at_camera =
+                                            make_check_box(ctx, "Listener At Camera");
+                                        listener_at_camera
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            sounds =
+                                ListViewBuilder::new(WidgetBuilder::new().on_row(1)).build(ctx);
+                            sounds
+                        }),
+                )
+                .add_row(Row::strict(26.0))
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
             .build(ctx);
-        Self { window, sounds }
+        Self {
+            window,
+            sounds,
+            play,
+            pause,
+            stop,
+            detach,
+            mute,
+            listener_at_camera,
+            muted: false,
+            bind_listener: false,
+        }
+    }
+
+    /// Keeps scene's sound context in sync with audition options, must be called every frame
+    /// since camera can move at any time.
+    pub fn update(&self, editor_scene: &mut EditorScene, scene: &Scene) {
+        let mut state = scene.sound_context.state();
+        let backup = &mut editor_scene.audition_backup;
+
+        if self.muted {
+            if backup.master_gain.is_none() {
+                backup.master_gain = Some(state.master_gain());
+                state.set_master_gain(0.0);
+            }
+        } else {
+            backup.restore_master_gain(&mut state);
+        }
+
+        let listener = state.listener_mut();
+        if self.bind_listener {
+            if backup.listener.is_none() {
+                backup.listener = Some((listener.position(), *listener.basis()));
+            }
+            let camera = &scene.graph[editor_scene.camera_controller.camera];
+            listener.set_position(camera.global_position());
+            listener.set_basis(camera.global_transform().basis());
+        } else {
+            backup.restore_listener(listener);
+        }
     }
 
     pub fn sync_to_model(&mut self, editor_scene: &EditorScene, engine: &mut GameEngine) {
@@ -246,10 +549,12 @@ impl SoundPanel {
                         .iter()
                         .all(|i| fetch_source(*i, ui) != handle)
                     {
-                        let item = SoundItemBuilder::new(WidgetBuilder::new())
-                            .with_name(source.name_owned())
-                            .with_sound_source(handle)
-                            .build(&mut ui.build_ctx());
+                        // Items can be dragged onto nodes in world outliner to bind sources.
+                        let item =
+                            SoundItemBuilder::new(WidgetBuilder::new().with_allow_drag(true))
+                                .with_name(source.name_owned())
+                                .with_sound_source(handle)
+                                .build(&mut ui.build_ctx());
                         send_sync_message(
                             ui,
                             ListViewMessage::add_item(
@@ -310,19 +615,19 @@ impl SoundPanel {
         let ui = &engine.user_interface;
         let list_view_items = ui.node(self.sounds).cast::<ListView>().unwrap().items();
 
-        if let UiMessageData::ListView(ListViewMessage::SelectionChanged(selection)) =
-            message.data()
-        {
-            if message.destination() == self.sounds
-                && message.direction() == MessageDirection::FromWidget
+        match message.data() {
+            UiMessageData::ListView(ListViewMessage::SelectionChanged(selection))
+                if message.destination() == self.sounds
+                    && message.direction() == MessageDirection::FromWidget =>
             {
                 let new_selection = match selection {
                     None => Default::default(),
                     Some(index) => {
                         // TODO: Implement multi-selection when ListView will have multi-selection support.
-                        Selection::Sound(SoundSelection {
-                            sources: vec![fetch_source(list_view_items[*index], ui)],
-                        })
+                        Selection::Sound(SoundSelection::new(vec![fetch_source(
+                            list_view_items[*index],
+                            ui,
+                        )]))
                     }
                 };
 
@@ -335,6 +640,63 @@ impl SoundPanel {
                         .unwrap();
                 }
             }
+            UiMessageData::Button(ButtonMessage::Click) if message.destination() == self.detach => {
+                if let Selection::Sound(selection) = &editor_scene.selection {
+                    let commands = selection
+                        .sources()
+                        .iter()
+                        .filter(|&&source| editor_scene.sound_binder.node_of(source).is_some())
+                        .map(|&source| {
+                            SceneCommand::new(SetSoundSourceNodeCommand::new(source, Handle::NONE))
+                        })
+                        .collect::<Vec<_>>();
+                    if !commands.is_empty() {
+                        sender
+                            .send(Message::do_scene_command(CommandGroup::from(commands)))
+                            .unwrap();
+                    }
+                }
+            }
+            UiMessageData::Button(ButtonMessage::Click) => {
+                // Playback is not a part of the scene, so there are no commands for it.
+                if let Selection::Sound(selection) = &editor_scene.selection {
+                    let mut state = engine.scenes[editor_scene.scene].sound_context.state();
+                    for &handle in selection.sources() {
+                        if !state.is_valid_handle(handle) {
+                            continue;
+                        }
+                        let source = state.source_mut(handle);
+                        if message.destination() == self.play {
+                            if source.is_play_once() {
+                                // Such source is removed from context when it stops, which
+                                // would silently change the scene.
+                                sender
+                                    .send(Message::Log(format!(
+                                        "Sound source {} is played once, it cannot be auditioned.",
+                                        source.name()
+                                    )))
+                                    .unwrap();
+                                continue;
+                            }
+                            source.play();
+                        } else if message.destination() == self.pause {
+                            source.pause();
+                        } else if message.destination() == self.stop {
+                            let _ = source.stop();
+                        }
+                    }
+                }
+            }
+            &UiMessageData::CheckBox(CheckBoxMessage::Check(Some(value)))
+                if message.direction() == MessageDirection::FromWidget =>
+            {
+                if message.destination() == self.mute {
+                    self.muted = value;
+                } else if message.destination() == self.listener_at_camera {
+                    self.bind_listener = value;
+                }
+            }
+            _ => {}
         }
     }
 }
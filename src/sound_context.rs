@@ -0,0 +1,387 @@
+//! Settings of sound context of a scene: reverb and renderer. Both are part of the scene, so
+//! they are saved together with it.
+
+use crate::{
+    gui::make_dropdown_list_option,
+    scene::{
+        commands::{
+            sound::{
+                find_reverb, AddReverbCommand, RemoveReverbCommand, ReplaceReverbCommand,
+                SetReverbDecayTimeCommand, SetReverbDryCommand, SetReverbGainCommand,
+                SetReverbWetCommand, SetSoundRendererCommand,
+            },
+            SceneCommand,
+        },
+        EditorScene,
+    },
+    send_sync_message, GameEngine, Message,
+};
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::{BuildContext, UiNode};
+use rg3d::{
+    core::{pool::Handle, scope_profile},
+    gui::{
+        button::ButtonBuilder,
+        check_box::CheckBoxBuilder,
+        dropdown_list::DropdownListBuilder,
+        grid::{Column, GridBuilder, Row},
+        message::{
+            ButtonMessage, CheckBoxMessage, DropdownListMessage, MessageDirection,
+            NumericUpDownMessage, TextBoxMessage, UiMessageData, WidgetMessage,
+        },
+        numeric::NumericUpDownBuilder,
+        text::TextBuilder,
+        text_box::TextBoxBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+        Thickness, VerticalAlignment,
+    },
+    sound::{
+        context::SAMPLE_RATE,
+        effects::{reverb::Reverb, BaseEffect, Effect, EffectInput},
+        hrtf::HrirSphere,
+        renderer::{hrtf::HrtfRenderer, Renderer},
+    },
+};
+use std::{sync::mpsc::Sender, time::Duration};
+
+const ROW_HEIGHT: f32 = 25.0;
+
+pub struct SoundContextPanel {
+    pub window: Handle<UiNode>,
+    renderer: Handle<UiNode>,
+    hrir_path: Handle<UiNode>,
+    reverb_enabled: Handle<UiNode>,
+    decay_time: Handle<UiNode>,
+    dry: Handle<UiNode>,
+    wet: Handle<UiNode>,
+    gain: Handle<UiNode>,
+    route_sources: Handle<UiNode>,
+    hrir_path_value: String,
+}
+
+fn make_text_mark(ctx: &mut BuildContext, text: &str, row: usize) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(0)
+            .with_vertical_alignment(VerticalAlignment::Center),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_f32_input_field(
+    ctx: &mut BuildContext,
+    row: usize,
+    min: f32,
+    max: f32,
+    step: f32,
+) -> Handle<UiNode> {
+    NumericUpDownBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(1)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_min_value(min)
+    .with_max_value(max)
+    .with_step(step)
+    .build(ctx)
+}
+
+/// Makes reverb with same parameters as given one, but with every source of the context as
+/// its input.
+fn route_all_sources(reverb: &Reverb, sources: impl Iterator<Item = EffectInput>) -> Effect {
+    let mut base = BaseEffect::default();
+    for input in sources {
+        base.add_input(input);
+    }
+    let mut routed = Reverb::new(base);
+    routed.set_decay_time(reverb.decay_time());
+    routed.set_dry(reverb.dry());
+    routed.set_wet(reverb.wet());
+    routed.set_gain(reverb.gain());
+    Effect::Reverb(routed)
+}
+
+impl SoundContextPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let renderer;
+        let hrir_path;
+        let reverb_enabled;
+        let decay_time;
+        let dry;
+        let wet;
+        let gain;
+        let route_sources;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(230.0))
+            .with_title(WindowTitle::text("Sound Context"))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(make_text_mark(ctx, "Renderer", 0))
+                        .with_child({
+                            renderer = DropdownListBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_close_on_selection(true)
+                            .with_items(vec![
+                                make_dropdown_list_option(ctx, "Default"),
+                                make_dropdown_list_option(ctx, "HRTF"),
+                            ])
+                            .build(ctx);
+                            renderer
+                        })
+                        .with_child(make_text_mark(ctx, "HRIR Sphere", 1))
+                        .with_child({
+                            hrir_path = TextBoxBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .build(ctx);
+                            hrir_path
+                        })
+                        .with_child(make_text_mark(ctx, "Reverb", 2))
+                        .with_child({
+                            reverb_enabled = CheckBoxBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            reverb_enabled
+                        })
+                        .with_child(make_text_mark(ctx, "Decay Time (s)", 3))
+                        .with_child({
+                            decay_time = make_f32_input_field(ctx, 3, 0.0, 30.0, 0.1);
+                            decay_time
+                        })
+                        .with_child(make_text_mark(ctx, "Dry", 4))
+                        .with_child({
+                            dry = make_f32_input_field(ctx, 4, 0.0, 1.0, 0.05);
+                            dry
+                        })
+                        .with_child(make_text_mark(ctx, "Wet", 5))
+                        .with_child({
+                            wet = make_f32_input_field(ctx, 5, 0.0, 1.0, 0.05);
+                            wet
+                        })
+                        .with_child(make_text_mark(ctx, "Gain", 6))
+                        .with_child({
+                            gain = make_f32_input_field(ctx, 6, 0.0, 10.0, 0.05);
+                            gain
+                        })
+                        .with_child({
+                            route_sources = ButtonBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(7)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text("Route All Sources")
+                            .build(ctx);
+                            route_sources
+                        }),
+                )
+                .add_column(Column::strict(100.0))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::strict(ROW_HEIGHT))
+                .add_row(Row::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            renderer,
+            hrir_path,
+            reverb_enabled,
+            decay_time,
+            dry,
+            wet,
+            gain,
+            route_sources,
+            hrir_path_value: Default::default(),
+        }
+    }
+
+    pub fn sync_to_model(&mut self, editor_scene: &EditorScene, engine: &mut GameEngine) {
+        let ui = &mut engine.user_interface;
+        let state = engine.scenes[editor_scene.scene].sound_context.state();
+
+        let renderer_index = match state.renderer() {
+            Renderer::Default => 0,
+            Renderer::HrtfRenderer(_) => 1,
+        };
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.renderer,
+                MessageDirection::ToWidget,
+                Some(renderer_index),
+            ),
+        );
+
+        let reverb = find_reverb(state.effects());
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(
+                self.reverb_enabled,
+                MessageDirection::ToWidget,
+                Some(reverb.is_some()),
+            ),
+        );
+
+        for &field in &[
+            self.decay_time,
+            self.dry,
+            self.wet,
+            self.gain,
+            self.route_sources,
+        ] {
+            send_sync_message(
+                ui,
+                WidgetMessage::enabled(field, MessageDirection::ToWidget, reverb.is_some()),
+            );
+        }
+
+        if reverb.is_some() {
+            if let Effect::Reverb(reverb) = state.effect(reverb) {
+                for &(field, value) in &[
+                    (self.decay_time, reverb.decay_time().as_secs_f32()),
+                    (self.dry, reverb.dry()),
+                    (self.wet, reverb.wet()),
+                    (self.gain, reverb.gain()),
+                ] {
+                    send_sync_message(
+                        ui,
+                        NumericUpDownMessage::value(field, MessageDirection::ToWidget, value),
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &GameEngine,
+        sender: &Sender<Message>,
+    ) {
+        scope_profile!();
+
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        let state = engine.scenes[editor_scene.scene].sound_context.state();
+
+        match message.data() {
+            UiMessageData::DropdownList(DropdownListMessage::SelectionChanged(Some(index)))
+                if message.destination() == self.renderer =>
+            {
+                let renderer = match (*index, state.renderer()) {
+                    (0, Renderer::HrtfRenderer(_)) => Renderer::Default,
+                    (1, Renderer::Default) => {
+                        match HrirSphere::from_file(&self.hrir_path_value, SAMPLE_RATE) {
+                            Ok(sphere) => Renderer::HrtfRenderer(HrtfRenderer::new(sphere)),
+                            Err(e) => {
+                                sender
+                                    .send(Message::Log(format!(
+                                        "Unable to load HRIR sphere {}. Reason: {:?}",
+                                        self.hrir_path_value, e
+                                    )))
+                                    .unwrap();
+                                // Show actual renderer again.
+                                sender.send(Message::SyncToModel).unwrap();
+                                return;
+                            }
+                        }
+                    }
+                    _ => return,
+                };
+                sender
+                    .send(Message::do_scene_command(SetSoundRendererCommand::new(
+                        renderer,
+                    )))
+                    .unwrap();
+            }
+            UiMessageData::TextBox(TextBoxMessage::Text(text))
+                if message.destination() == self.hrir_path =>
+            {
+                self.hrir_path_value = text.clone();
+            }
+            &UiMessageData::CheckBox(CheckBoxMessage::Check(Some(value)))
+                if message.destination() == self.reverb_enabled =>
+            {
+                let has_reverb = find_reverb(state.effects()).is_some();
+                let command = if value && !has_reverb {
+                    let mut base = BaseEffect::default();
+                    for (source, _) in state.sources().pair_iter() {
+                        base.add_input(EffectInput::direct(source));
+                    }
+                    SceneCommand::new(AddReverbCommand::new(Effect::Reverb(Reverb::new(base))))
+                } else if !value && has_reverb {
+                    SceneCommand::new(RemoveReverbCommand::default())
+                } else {
+                    return;
+                };
+                sender.send(Message::DoSceneCommand(command)).unwrap();
+            }
+            &UiMessageData::NumericUpDown(NumericUpDownMessage::Value(value)) => {
+                let command = if message.destination() == self.decay_time {
+                    SceneCommand::new(SetReverbDecayTimeCommand::new(Duration::from_secs_f32(
+                        value,
+                    )))
+                } else if message.destination() == self.dry {
+                    SceneCommand::new(SetReverbDryCommand::new(value))
+                } else if message.destination() == self.wet {
+                    SceneCommand::new(SetReverbWetCommand::new(value))
+                } else if message.destination() == self.gain {
+                    SceneCommand::new(SetReverbGainCommand::new(value))
+                } else {
+                    return;
+                };
+                sender.send(Message::DoSceneCommand(command)).unwrap();
+            }
+            UiMessageData::Button(ButtonMessage::Click)
+                if message.destination() == self.route_sources =>
+            {
+                let handle = find_reverb(state.effects());
+                if handle.is_none() {
+                    return;
+                }
+                if let Effect::Reverb(reverb) = state.effect(handle) {
+                    let routed = route_all_sources(
+                        reverb,
+                        state
+                            .sources()
+                            .pair_iter()
+                            .map(|(source, _)| EffectInput::direct(source)),
+                    );
+                    sender
+                        .send(Message::do_scene_command(ReplaceReverbCommand::new(routed)))
+                        .unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+}
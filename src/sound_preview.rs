@@ -0,0 +1,329 @@
+//! Preview of sound assets: playback, properties of a buffer and its waveform. Sounds are
+//! played by sound context of model preview scene, so they never get into edited scene.
+
+use crate::GameEngine;
+use rg3d::gui::message::UiMessage;
+use rg3d::gui::widget::Widget;
+use rg3d::gui::{BuildContext, UiNode, UserInterface};
+use rg3d::{
+    core::{algebra::Vector2, color::Color, pool::Handle, scope_profile},
+    gui::{
+        brush::Brush,
+        button::ButtonBuilder,
+        draw::{CommandTexture, Draw, DrawingContext},
+        grid::{Column, GridBuilder, Row},
+        message::{ButtonMessage, MessageDirection, TextMessage, UiMessageData},
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        widget::WidgetBuilder,
+        Control, Orientation, Thickness,
+    },
+    scene::Scene,
+    sound::{
+        buffer::SoundBufferResource,
+        source::{generic::GenericSourceBuilder, SoundSource},
+    },
+};
+use std::{
+    any::Any,
+    ops::{Deref, DerefMut},
+    path::Path,
+};
+
+/// Amount of columns of waveform, each column shows peak amplitude of a part of a sound.
+const WAVEFORM_COLUMNS: usize = 240;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WaveformMessage {
+    Peaks(Vec<f32>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Waveform {
+    widget: Widget,
+    peaks: Vec<f32>,
+}
+
+impl Deref for Waveform {
+    type Target = Widget;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for Waveform {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+impl Control for Waveform {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Control> {
+        Box::new(self.clone())
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.screen_bounds();
+        drawing_context.push_rect_filled(&bounds, None);
+        drawing_context.commit(bounds, self.background(), CommandTexture::None, None);
+
+        if !self.peaks.is_empty() {
+            let step = bounds.size.x / self.peaks.len() as f32;
+            let middle = bounds.position.y + bounds.size.y * 0.5;
+            for (i, peak) in self.peaks.iter().enumerate() {
+                let x = bounds.position.x + (i as f32 + 0.5) * step;
+                let half_height = (peak * bounds.size.y * 0.5).max(0.5);
+                drawing_context.push_line(
+                    Vector2::new(x, middle - half_height),
+                    Vector2::new(x, middle + half_height),
+                    step.max(1.0),
+                );
+            }
+            drawing_context.commit(bounds, self.foreground(), CommandTexture::None, None);
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let UiMessageData::User(msg) = message.data() {
+            if let Some(WaveformMessage::Peaks(peaks)) = msg.cast::<WaveformMessage>() {
+                if message.destination() == self.handle() {
+                    self.peaks = peaks.clone();
+                }
+            }
+        }
+    }
+}
+
+pub struct WaveformBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl WaveformBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let waveform = Waveform {
+            widget: self
+                .widget_builder
+                .with_background(Brush::Solid(Color::opaque(30, 30, 30)))
+                .with_foreground(Brush::Solid(Color::opaque(120, 200, 120)))
+                .build(),
+            peaks: Default::default(),
+        };
+        ctx.add_node(UiNode::new(waveform))
+    }
+}
+
+/// Returns peak amplitude of each of `count` equal parts of interleaved samples.
+fn calculate_peaks(samples: &[f32], count: usize) -> Vec<f32> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    let chunk_size = (samples.len() + count - 1) / count;
+    samples
+        .chunks(chunk_size.max(1))
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0.0f32, |peak, sample| peak.max(sample.abs()))
+                .min(1.0)
+        })
+        .collect()
+}
+
+pub struct SoundPreviewPanel {
+    pub root: Handle<UiNode>,
+    properties: Handle<UiNode>,
+    waveform: Handle<UiNode>,
+    play: Handle<UiNode>,
+    stop: Handle<UiNode>,
+    source: Handle<SoundSource>,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(60.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl SoundPreviewPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let properties;
+        let waveform;
+        let play;
+        let stop;
+        let root = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_child({
+                    waveform = WaveformBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(0)
+                            .with_margin(Thickness::uniform(2.0)),
+                    )
+                    .build(ctx);
+                    waveform
+                })
+                .with_child({
+                    properties = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(1)
+                            .with_margin(Thickness::uniform(2.0)),
+                    )
+                    .build(ctx);
+                    properties
+                })
+                .with_child(
+                    StackPanelBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(2)
+                            .with_child({
+                                play = make_button(ctx, "Play");
+                                play
+                            })
+                            .with_child({
+                                stop = make_button(ctx, "Stop");
+                                stop
+                            }),
+                    )
+                    .with_orientation(Orientation::Horizontal)
+                    .build(ctx),
+                ),
+        )
+        .add_column(Column::stretch())
+        .add_row(Row::strict(100.0))
+        .add_row(Row::strict(60.0))
+        .add_row(Row::strict(26.0))
+        .build(ctx);
+
+        Self {
+            root,
+            properties,
+            waveform,
+            play,
+            stop,
+            source: Handle::NONE,
+        }
+    }
+
+    /// Stops playback and releases previewed sound.
+    pub fn clear(&mut self, engine: &mut GameEngine, scene: Handle<Scene>) {
+        if self.source.is_some() {
+            engine.scenes[scene]
+                .sound_context
+                .state()
+                .remove_source(self.source);
+            self.source = Handle::NONE;
+        }
+    }
+
+    pub async fn load_sound(&mut self, path: &Path, engine: &mut GameEngine, scene: Handle<Scene>) {
+        self.clear(engine, scene);
+
+        let (text, peaks) = match engine
+            .resource_manager
+            .request_sound_buffer(path, false)
+            .await
+        {
+            Ok(buffer) => {
+                let (mut text, peaks) = describe(&buffer);
+
+                match GenericSourceBuilder::new()
+                    .with_buffer(buffer)
+                    .build_source()
+                {
+                    Ok(source) => {
+                        self.source = engine.scenes[scene]
+                            .sound_context
+                            .state()
+                            .add_source(source);
+                    }
+                    Err(e) => {
+                        text += &format!("\nUnable to play sound. Reason: {:?}", e);
+                    }
+                }
+
+                (text, peaks)
+            }
+            Err(_) => (
+                format!("Unable to load sound {}!", path.display()),
+                Vec::new(),
+            ),
+        };
+
+        let ui = &engine.user_interface;
+        ui.send_message(TextMessage::text(
+            self.properties,
+            MessageDirection::ToWidget,
+            text,
+        ));
+        ui.send_message(UiMessage::user(
+            self.waveform,
+            MessageDirection::ToWidget,
+            Box::new(WaveformMessage::Peaks(peaks)),
+        ));
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        engine: &mut GameEngine,
+        scene: Handle<Scene>,
+    ) {
+        scope_profile!();
+
+        if let UiMessageData::Button(ButtonMessage::Click) = message.data() {
+            if self.source.is_none() {
+                return;
+            }
+
+            let mut state = engine.scenes[scene].sound_context.state();
+            let source = state.source_mut(self.source);
+            if message.destination() == self.play {
+                // Playback always starts from the beginning.
+                let _ = source.stop();
+                source.play();
+            } else if message.destination() == self.stop {
+                let _ = source.stop();
+            }
+        }
+    }
+}
+
+/// Makes text with properties of the buffer and peaks of its waveform.
+fn describe(buffer: &SoundBufferResource) -> (String, Vec<f32>) {
+    let buffer = buffer.data_ref();
+    let channel_count = buffer.channel_count();
+    let sample_rate = buffer.sample_rate();
+    let samples = buffer.samples();
+    let duration = if channel_count > 0 && sample_rate > 0 {
+        samples.len() as f32 / (channel_count * sample_rate) as f32
+    } else {
+        0.0
+    };
+    (
+        format!(
+            "Duration: {:.2} s\nChannels: {}\nSample Rate: {} Hz",
+            duration, channel_count, sample_rate
+        ),
+        calculate_peaks(samples, WAVEFORM_COLUMNS),
+    )
+}
@@ -0,0 +1,153 @@
+//! Rule-based generation of layer masks from height and slope of a terrain.
+
+use crate::terrain::{noise, Grid};
+use rg3d::{core::algebra::Vector2, resource::texture::TextureKind, scene::terrain::Terrain};
+
+/// Defines where a layer should be painted. Heights are in local space of the terrain,
+/// slopes are in degrees.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaintRule {
+    pub enabled: bool,
+    pub min_height: f32,
+    pub max_height: f32,
+    /// Width of transition at the borders of the height range.
+    pub height_blend: f32,
+    pub min_slope: f32,
+    pub max_slope: f32,
+    /// Width of transition at the borders of the slope range.
+    pub slope_blend: f32,
+    /// Noise shifts borders of the ranges by up to `noise_amount * blend` to make
+    /// transitions less uniform.
+    pub noise_amount: f32,
+    pub noise_frequency: f32,
+}
+
+impl Default for PaintRule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_height: 0.0,
+            max_height: 100.0,
+            height_blend: 1.0,
+            min_slope: 0.0,
+            max_slope: 90.0,
+            slope_blend: 5.0,
+            noise_amount: 0.0,
+            noise_frequency: 0.5,
+        }
+    }
+}
+
+/// Returns 1.0 inside of the range, 0.0 outside and linear transition of `blend` width
+/// around its borders.
+fn band(value: f32, min: f32, max: f32, blend: f32) -> f32 {
+    if blend <= f32::EPSILON {
+        if value >= min && value <= max {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        ((value - min) / blend + 0.5).clamp(0.0, 1.0)
+            * ((max - value) / blend + 0.5).clamp(0.0, 1.0)
+    }
+}
+
+impl PaintRule {
+    fn weight(&self, height: f32, slope: f32, position: Vector2<f32>, seed: u32) -> f32 {
+        let n = if self.noise_amount > 0.0 {
+            noise::value_noise(
+                position.x * self.noise_frequency,
+                position.y * self.noise_frequency,
+                seed,
+            ) * self.noise_amount
+        } else {
+            0.0
+        };
+
+        band(
+            height + n * self.height_blend,
+            self.min_height,
+            self.max_height,
+            self.height_blend,
+        ) * band(
+            slope + n * self.slope_blend,
+            self.min_slope,
+            self.max_slope,
+            self.slope_blend,
+        )
+    }
+}
+
+/// Returns height and slope angle (in degrees) at given normalized coordinates.
+fn height_and_slope(grid: &Grid, u: f32, v: f32, width: f32, length: f32) -> (f32, f32) {
+    let du = 1.0 / (grid.width - 1).max(1) as f32;
+    let dv = 1.0 / (grid.length - 1).max(1) as f32;
+
+    let dhdx = (grid.sample(u + du, v) - grid.sample(u - du, v)) / (2.0 * du * width);
+    let dhdz = (grid.sample(u, v + dv) - grid.sample(u, v - dv)) / (2.0 * dv * length);
+
+    (
+        grid.sample(u, v),
+        Vector2::new(dhdx, dhdz).norm().atan().to_degrees(),
+    )
+}
+
+/// Generates masks of every chunk for each layer with enabled rule. Result contains
+/// layer index together with its new masks.
+pub fn generate_masks(terrain: &Terrain, rules: &[PaintRule]) -> Vec<(usize, Vec<Vec<u8>>)> {
+    let grid = Grid::from_terrain(terrain);
+    let chunk_size = Vector2::new(
+        terrain.width() / terrain.width_chunks() as f32,
+        terrain.length() / terrain.length_chunks() as f32,
+    );
+
+    let mut result = Vec::new();
+
+    for (layer_index, (layer, rule)) in terrain.layers().iter().zip(rules).enumerate() {
+        if !rule.enabled {
+            continue;
+        }
+
+        let mut masks = Vec::with_capacity(layer.chunk_masks().len());
+        for (chunk, mask) in terrain.chunks_ref().iter().zip(layer.chunk_masks()) {
+            let (width, height) =
+                if let TextureKind::Rectangle { width, height } = mask.data_ref().kind() {
+                    (width as usize, height as usize)
+                } else {
+                    masks.push(mask.data_ref().data().to_vec());
+                    continue;
+                };
+
+            let origin = chunk.local_position();
+
+            let mut pixels = Vec::with_capacity(width * height);
+            for z in 0..height {
+                for x in 0..width {
+                    let position = origin
+                        + Vector2::new(
+                            (x as f32 + 0.5) / width as f32 * chunk_size.x,
+                            (z as f32 + 0.5) / height as f32 * chunk_size.y,
+                        );
+
+                    let (h, slope) = height_and_slope(
+                        &grid,
+                        position.x / terrain.width(),
+                        position.y / terrain.length(),
+                        terrain.width(),
+                        terrain.length(),
+                    );
+
+                    let weight = rule.weight(h, slope, position, layer_index as u32);
+
+                    pixels.push((weight * 255.0).clamp(0.0, 255.0) as u8);
+                }
+            }
+            masks.push(pixels);
+        }
+
+        result.push((layer_index, masks));
+    }
+
+    result
+}
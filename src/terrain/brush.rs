@@ -0,0 +1,447 @@
+//! Editor-side terrain brush. Unlike the engine brush it supports sculpting modes that
+//! need to look at neighbouring points (smoothing) or at data captured on click
+//! (flattening, ramps), so it operates on stitched height map of the chunks under the brush.
+
+use crate::terrain::{noise, ChunkLayout, Grid};
+use image::ImageError;
+use rg3d::{
+    core::algebra::{Point3, Vector2, Vector3},
+    resource::texture::TextureKind,
+    scene::terrain::Terrain,
+};
+use std::{path::Path, sync::Arc};
+
+/// Grayscale image that defines strength of a stamp brush.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StampMask {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Arc<Vec<u8>>,
+}
+
+impl StampMask {
+    pub fn load(path: &Path) -> Result<Self, ImageError> {
+        let image = image::open(path)?.into_luma8();
+        Ok(Self {
+            width: image.width() as usize,
+            height: image.height() as usize,
+            pixels: Arc::new(image.into_raw()),
+        })
+    }
+
+    /// Samples the mask at given normalized coordinates, returns value in [0; 1] range.
+    fn sample(&self, u: f32, v: f32) -> f32 {
+        if self.pixels.is_empty() {
+            return 0.0;
+        }
+        let x = ((u * self.width as f32) as usize).min(self.width - 1);
+        let y = ((v * self.height as f32) as usize).min(self.height - 1);
+        self.pixels[y * self.width + x] as f32 / 255.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BrushShape {
+    Circle {
+        radius: f32,
+    },
+    Rectangle {
+        width: f32,
+        length: f32,
+    },
+    /// Square brush which strength is defined by a grayscale image.
+    Stamp {
+        size: f32,
+        /// Rotation around vertical axis in radians.
+        rotation: f32,
+        mask: StampMask,
+    },
+}
+
+impl BrushShape {
+    /// Returns normalized distance from the center (in [0; 1] range) and strength of the
+    /// shape at given point. `None` means that point is outside of the brush.
+    fn sample(&self, center: Vector2<f32>, point: Vector2<f32>) -> Option<(f32, f32)> {
+        let d = point - center;
+        match self {
+            BrushShape::Circle { radius } => {
+                let t = d.norm() / radius.max(f32::EPSILON);
+                if t <= 1.0 {
+                    Some((t, 1.0))
+                } else {
+                    None
+                }
+            }
+            BrushShape::Rectangle { width, length } => {
+                let t = (d.x.abs() / (width * 0.5).max(f32::EPSILON))
+                    .max(d.y.abs() / (length * 0.5).max(f32::EPSILON));
+                if t <= 1.0 {
+                    Some((t, 1.0))
+                } else {
+                    None
+                }
+            }
+            BrushShape::Stamp {
+                size,
+                rotation,
+                mask,
+            } => {
+                let half_size = (size * 0.5).max(f32::EPSILON);
+                let (sin, cos) = rotation.sin_cos();
+                // Rotate point back to the space of the stamp.
+                let local = Vector2::new(d.x * cos + d.y * sin, -d.x * sin + d.y * cos);
+                let t = (local.x.abs() / half_size).max(local.y.abs() / half_size);
+                if t <= 1.0 {
+                    Some((
+                        t,
+                        mask.sample(
+                            local.x / (2.0 * half_size) + 0.5,
+                            local.y / (2.0 * half_size) + 0.5,
+                        ),
+                    ))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Half of the size of the brush across a stroke.
+    pub fn half_width(&self) -> f32 {
+        match *self {
+            BrushShape::Circle { radius } => radius,
+            BrushShape::Rectangle { width, .. } => width * 0.5,
+            BrushShape::Stamp { size, .. } => size * 0.5,
+        }
+    }
+}
+
+/// Defines how strength of the brush changes from its center to its border.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BrushFalloff {
+    /// Same strength everywhere.
+    Hard,
+    Linear,
+    Smooth,
+    Spherical,
+    /// Strength values evenly distributed from the center to the border, linearly
+    /// interpolated in between.
+    Curve(Vec<f32>),
+}
+
+impl BrushFalloff {
+    /// Returns strength at given normalized distance from the center.
+    pub fn evaluate(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            BrushFalloff::Hard => 1.0,
+            BrushFalloff::Linear => 1.0 - t,
+            BrushFalloff::Smooth => 1.0 - t * t * (3.0 - 2.0 * t),
+            BrushFalloff::Spherical => (1.0 - t * t).sqrt(),
+            BrushFalloff::Curve(keys) => match keys.len() {
+                0 => 1.0,
+                1 => keys[0],
+                n => {
+                    let f = t * (n - 1) as f32;
+                    let i = (f as usize).min(n - 2);
+                    let k = f - i as f32;
+                    keys[i] + (keys[i + 1] - keys[i]) * k
+                }
+            },
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BrushMode {
+    /// Raises or lowers height map.
+    ModifyHeightMap { amount: f32 },
+    /// Pulls heights towards average of neighbouring points.
+    Smooth { strength: f32 },
+    /// Pulls heights towards the height sampled on click (in terrain local space).
+    Flatten { height: f32, strength: f32 },
+    /// Adds procedural noise on top of height map.
+    Noise {
+        amount: f32,
+        frequency: f32,
+        seed: u32,
+    },
+    /// Builds a slope from the point sampled on click (in terrain local space) to
+    /// the current brush position.
+    Ramp { begin: Vector3<f32> },
+    /// Draws on the mask of a layer.
+    DrawOnMask { layer: usize, alpha: f32 },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Brush {
+    /// Position of the brush in world space.
+    pub center: Vector3<f32>,
+    pub shape: BrushShape,
+    pub mode: BrushMode,
+    pub falloff: BrushFalloff,
+}
+
+fn average_around(grid: &Grid, x: usize, z: usize) -> f32 {
+    let mut sum = 0.0;
+    let mut count = 0;
+    for nz in z.saturating_sub(1)..(z + 2).min(grid.length) {
+        for nx in x.saturating_sub(1)..(x + 2).min(grid.width) {
+            sum += grid.get(nx, nz);
+            count += 1;
+        }
+    }
+    sum / count as f32
+}
+
+/// Returns normalized position of a point along `begin -> end` segment and distance
+/// from the point to the segment.
+fn project_on_segment(begin: Vector2<f32>, end: Vector2<f32>, point: Vector2<f32>) -> (f32, f32) {
+    let dir = end - begin;
+    let len_sqr = dir.norm_squared();
+    let t = if len_sqr > f32::EPSILON {
+        ((point - begin).dot(&dir) / len_sqr).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (t, (begin + dir.scale(t) - point).norm())
+}
+
+impl Brush {
+    /// Returns strength of the brush in given point, zero means that point is outside.
+    pub fn influence(&self, center: Vector2<f32>, point: Vector2<f32>) -> f32 {
+        match self.shape.sample(center, point) {
+            Some((t, strength)) => strength * self.falloff.evaluate(t),
+            None => 0.0,
+        }
+    }
+
+    /// Applies the brush to the terrain. `initial_heightmaps` are the height maps at the
+    /// beginning of the stroke, they're used by modes that rebuild the whole stroke on
+    /// each move instead of accumulating changes.
+    pub fn draw(&self, terrain: &mut Terrain, initial_heightmaps: &[Vec<f32>]) {
+        let center = terrain
+            .global_transform()
+            .try_inverse()
+            .unwrap_or_default()
+            .transform_point(&Point3::from(self.center))
+            .coords;
+
+        match self.mode {
+            BrushMode::DrawOnMask { layer, alpha } => {
+                self.draw_on_mask(terrain, Vector2::new(center.x, center.z), layer, alpha)
+            }
+            _ => self.modify_heights(terrain, center, initial_heightmaps),
+        }
+    }
+
+    /// Returns axis-aligned bounds of the area in terrain local space (XZ plane) that can be
+    /// affected by the brush.
+    fn bounds(&self, center: Vector3<f32>) -> (Vector2<f32>, Vector2<f32>) {
+        let center2 = Vector2::new(center.x, center.z);
+        if let BrushMode::Ramp { begin } = self.mode {
+            let begin2 = Vector2::new(begin.x, begin.z);
+            let half_width = Vector2::repeat(self.shape.half_width());
+            return (
+                begin2.inf(&center2) - half_width,
+                begin2.sup(&center2) + half_width,
+            );
+        }
+        let half_extents = match self.shape {
+            BrushShape::Circle { radius } => Vector2::repeat(radius),
+            BrushShape::Rectangle { width, length } => Vector2::new(width * 0.5, length * 0.5),
+            BrushShape::Stamp { size, rotation, .. } => {
+                let (sin, cos) = rotation.sin_cos();
+                Vector2::repeat(size * 0.5 * (sin.abs() + cos.abs()))
+            }
+        };
+        (center2 - half_extents, center2 + half_extents)
+    }
+
+    fn modify_heights(
+        &self,
+        terrain: &mut Terrain,
+        center: Vector3<f32>,
+        initial_heightmaps: &[Vec<f32>],
+    ) {
+        let layout = ChunkLayout::of_terrain(terrain);
+        let step_x = layout.chunk_width_points - 1;
+        let step_z = layout.chunk_length_points - 1;
+        let dx = terrain.width() / (layout.grid_width() - 1) as f32;
+        let dz = terrain.length() / (layout.grid_length() - 1) as f32;
+        let center2 = Vector2::new(center.x, center.z);
+
+        let (min, max) = self.bounds(center);
+
+        // Points of the terrain that can be affected by the brush.
+        let x0 = ((min.x / dx).floor().max(0.0) as usize).min(layout.grid_width() - 1);
+        let x1 = ((max.x / dx).ceil().max(0.0) as usize).min(layout.grid_width() - 1);
+        let z0 = ((min.y / dz).floor().max(0.0) as usize).min(layout.grid_length() - 1);
+        let z1 = ((max.y / dz).ceil().max(0.0) as usize).min(layout.grid_length() - 1);
+
+        // Chunks that contain these points and one more ring of points around them, smoothing
+        // needs neighbours. Points on borders are shared with neighbour chunks, so they're
+        // included too.
+        let mut cx0 = x0.saturating_sub(1) / step_x;
+        let mut cx1 = ((x1 + 1) / step_x).min(layout.width_chunks - 1);
+        let mut cz0 = z0.saturating_sub(1) / step_z;
+        let mut cz1 = ((z1 + 1) / step_z).min(layout.length_chunks - 1);
+
+        if let BrushMode::Ramp { .. } = self.mode {
+            // Ramp is rebuilt from initial heights on each move, so chunks changed by previous
+            // moves must be restored even if the brush does not cover them anymore.
+            for (index, (chunk, initial)) in terrain
+                .chunks_ref()
+                .iter()
+                .zip(initial_heightmaps)
+                .enumerate()
+            {
+                if chunk.heightmap() != initial.as_slice() {
+                    let (cx, cz) = (index % layout.width_chunks, index / layout.width_chunks);
+                    cx0 = cx0.min(cx);
+                    cx1 = cx1.max(cx);
+                    cz0 = cz0.min(cz);
+                    cz1 = cz1.max(cz);
+                }
+            }
+        }
+
+        // Stitched height map of the chunks, starting at (ox, oz) point of the terrain.
+        let (ox, oz) = (cx0 * step_x, cz0 * step_z);
+        let mut source = Grid::new((cx1 - cx0 + 1) * step_x + 1, (cz1 - cz0 + 1) * step_z + 1);
+        for cz in cz0..=cz1 {
+            for cx in cx0..=cx1 {
+                let index = cz * layout.width_chunks + cx;
+                let heightmap = match self.mode {
+                    BrushMode::Ramp { .. } => initial_heightmaps[index].as_slice(),
+                    _ => terrain.chunks_ref()[index].heightmap(),
+                };
+                for z in 0..layout.chunk_length_points {
+                    for x in 0..layout.chunk_width_points {
+                        source.set(
+                            (cx - cx0) * step_x + x,
+                            (cz - cz0) * step_z + z,
+                            heightmap[z * layout.chunk_width_points + x],
+                        );
+                    }
+                }
+            }
+        }
+        let mut grid = source.clone();
+
+        for z in z0..=z1 {
+            for x in x0..=x1 {
+                let point = Vector2::new(x as f32 * dx, z as f32 * dz);
+                let (lx, lz) = (x - ox, z - oz);
+                let height = source.get(lx, lz);
+
+                let new_height = if let BrushMode::Ramp { begin } = self.mode {
+                    let (t, distance) =
+                        project_on_segment(Vector2::new(begin.x, begin.z), center2, point);
+                    let half_width = self.shape.half_width();
+                    if distance > half_width {
+                        continue;
+                    }
+                    let ramp_height = begin.y + (center.y - begin.y) * t;
+                    let k = self
+                        .falloff
+                        .evaluate(distance / half_width.max(f32::EPSILON));
+                    height + (ramp_height - height) * k
+                } else {
+                    let k = self.influence(center2, point);
+                    if k <= 0.0 {
+                        continue;
+                    }
+                    match self.mode {
+                        BrushMode::ModifyHeightMap { amount } => height + amount * k,
+                        BrushMode::Smooth { strength } => {
+                            let average = average_around(&source, lx, lz);
+                            height + (average - height) * (strength * k).clamp(0.0, 1.0)
+                        }
+                        BrushMode::Flatten {
+                            height: target,
+                            strength,
+                        } => height + (target - height) * (strength * k).clamp(0.0, 1.0),
+                        BrushMode::Noise {
+                            amount,
+                            frequency,
+                            seed,
+                        } => {
+                            height
+                                + amount
+                                    * k
+                                    * noise::value_noise(
+                                        point.x * frequency,
+                                        point.y * frequency,
+                                        seed,
+                                    )
+                        }
+                        BrushMode::Ramp { .. } | BrushMode::DrawOnMask { .. } => unreachable!(),
+                    }
+                };
+
+                grid.set(lx, lz, new_height);
+            }
+        }
+
+        // Only touch chunks that were actually changed, each modified chunk must rebuild
+        // its geometry.
+        for cz in cz0..=cz1 {
+            for cx in cx0..=cx1 {
+                let mut heightmap =
+                    Vec::with_capacity(layout.chunk_width_points * layout.chunk_length_points);
+                for z in 0..layout.chunk_length_points {
+                    for x in 0..layout.chunk_width_points {
+                        heightmap.push(grid.get((cx - cx0) * step_x + x, (cz - cz0) * step_z + z));
+                    }
+                }
+                let chunk = &mut terrain.chunks_mut()[cz * layout.width_chunks + cx];
+                if chunk.heightmap() != heightmap.as_slice() {
+                    chunk.set_heightmap(heightmap);
+                }
+            }
+        }
+    }
+
+    fn draw_on_mask(&self, terrain: &Terrain, center: Vector2<f32>, layer: usize, alpha: f32) {
+        let chunk_size = Vector2::new(
+            terrain.width() / terrain.width_chunks() as f32,
+            terrain.length() / terrain.length_chunks() as f32,
+        );
+
+        let layer = if let Some(layer) = terrain.layers().get(layer) {
+            layer
+        } else {
+            return;
+        };
+
+        for (chunk, mask) in terrain.chunks_ref().iter().zip(layer.chunk_masks()) {
+            let mut data = mask.data_ref();
+            let (width, height) = if let TextureKind::Rectangle { width, height } = data.kind() {
+                (width as usize, height as usize)
+            } else {
+                continue;
+            };
+
+            let origin = chunk.local_position();
+
+            let mut modify = data.modify();
+            let pixels = modify.data_mut();
+            for z in 0..height {
+                for x in 0..width {
+                    let point = origin
+                        + Vector2::new(
+                            (x as f32 + 0.5) / width as f32 * chunk_size.x,
+                            (z as f32 + 0.5) / height as f32 * chunk_size.y,
+                        );
+                    let k = self.influence(center, point);
+                    if k > 0.0 {
+                        // Masks have R8 format, so we can write directly into them.
+                        let pixel = &mut pixels[z * width + x];
+                        *pixel = (*pixel as f32 + alpha * k * 255.0).clamp(0.0, 255.0) as u8;
+                    }
+                }
+            }
+        }
+    }
+}
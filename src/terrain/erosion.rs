@@ -0,0 +1,216 @@
+//! Erosion simulation over stitched terrain height map. Both simulations work in grid
+//! space, so results depend on the resolution of the terrain.
+
+use crate::terrain::Grid;
+use rg3d::core::algebra::Vector2;
+
+/// Simulates material sliding down from slopes that are steeper than the talus.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThermalErosion {
+    pub iterations: usize,
+    /// Maximum stable height difference between neighbouring points.
+    pub talus: f32,
+    /// Portion of excess material that moves each iteration, in [0; 1] range.
+    pub strength: f32,
+}
+
+impl Default for ThermalErosion {
+    fn default() -> Self {
+        Self {
+            iterations: 0,
+            talus: 0.5,
+            strength: 0.5,
+        }
+    }
+}
+
+impl ThermalErosion {
+    pub fn apply(&self, grid: &mut Grid) {
+        let strength = self.strength.clamp(0.0, 1.0) * 0.25;
+        let mut delta = vec![0.0; grid.values.len()];
+
+        for _ in 0..self.iterations {
+            for d in delta.iter_mut() {
+                *d = 0.0;
+            }
+
+            for z in 0..grid.length {
+                for x in 0..grid.width {
+                    let height = grid.get(x, z);
+                    let index = z * grid.width + x;
+
+                    for &(nx, nz) in &[
+                        (x.wrapping_sub(1), z),
+                        (x + 1, z),
+                        (x, z.wrapping_sub(1)),
+                        (x, z + 1),
+                    ] {
+                        if nx >= grid.width || nz >= grid.length {
+                            continue;
+                        }
+
+                        let diff = height - grid.get(nx, nz);
+                        if diff > self.talus {
+                            let amount = (diff - self.talus) * strength;
+                            delta[index] -= amount;
+                            delta[nz * grid.width + nx] += amount;
+                        }
+                    }
+                }
+            }
+
+            for (value, d) in grid.values.iter_mut().zip(delta.iter()) {
+                *value += *d;
+            }
+        }
+    }
+}
+
+/// Particle-based simulation of rain drops that carry sediment down the slopes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HydraulicErosion {
+    pub droplets: usize,
+    pub seed: u32,
+    /// How fast droplets pick up sediment, in [0; 1] range.
+    pub erosion_rate: f32,
+    /// How fast droplets drop excess sediment, in [0; 1] range.
+    pub deposition_rate: f32,
+    /// Portion of water that evaporates each step, in [0; 1] range.
+    pub evaporation: f32,
+}
+
+impl Default for HydraulicErosion {
+    fn default() -> Self {
+        Self {
+            droplets: 0,
+            seed: 0,
+            erosion_rate: 0.3,
+            deposition_rate: 0.3,
+            evaporation: 0.01,
+        }
+    }
+}
+
+const DROPLET_LIFETIME: usize = 64;
+const INERTIA: f32 = 0.05;
+const SEDIMENT_CAPACITY: f32 = 4.0;
+const MIN_SLOPE: f32 = 0.01;
+const GRAVITY: f32 = 4.0;
+
+/// Tiny xorshift generator, results must be reproducible for the same seed.
+struct Random(u32);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 & 0x00FF_FFFF) as f32 / 0x0100_0000 as f32
+    }
+}
+
+fn cell_of(grid: &Grid, position: Vector2<f32>) -> (usize, usize, f32, f32) {
+    let x = (position.x as usize).min(grid.width - 2);
+    let z = (position.y as usize).min(grid.length - 2);
+    (x, z, position.x - x as f32, position.y - z as f32)
+}
+
+fn height_and_gradient(grid: &Grid, position: Vector2<f32>) -> (f32, Vector2<f32>) {
+    let (x, z, fx, fz) = cell_of(grid, position);
+
+    let h00 = grid.get(x, z);
+    let h10 = grid.get(x + 1, z);
+    let h01 = grid.get(x, z + 1);
+    let h11 = grid.get(x + 1, z + 1);
+
+    let gradient = Vector2::new(
+        (h10 - h00) * (1.0 - fz) + (h11 - h01) * fz,
+        (h01 - h00) * (1.0 - fx) + (h11 - h10) * fx,
+    );
+
+    let height = h00 * (1.0 - fx) * (1.0 - fz)
+        + h10 * fx * (1.0 - fz)
+        + h01 * (1.0 - fx) * fz
+        + h11 * fx * fz;
+
+    (height, gradient)
+}
+
+/// Adds given amount of material to the cell that contains the position, distributing it
+/// between corners of the cell bilinearly.
+fn deposit(grid: &mut Grid, position: Vector2<f32>, amount: f32) {
+    let (x, z, fx, fz) = cell_of(grid, position);
+
+    let h = grid.get(x, z);
+    grid.set(x, z, h + amount * (1.0 - fx) * (1.0 - fz));
+    let h = grid.get(x + 1, z);
+    grid.set(x + 1, z, h + amount * fx * (1.0 - fz));
+    let h = grid.get(x, z + 1);
+    grid.set(x, z + 1, h + amount * (1.0 - fx) * fz);
+    let h = grid.get(x + 1, z + 1);
+    grid.set(x + 1, z + 1, h + amount * fx * fz);
+}
+
+impl HydraulicErosion {
+    pub fn apply(&self, grid: &mut Grid) {
+        if grid.width < 2 || grid.length < 2 {
+            return;
+        }
+
+        // Xorshift gets stuck on zero.
+        let mut random = Random(self.seed.wrapping_mul(2_654_435_761) | 1);
+        let max = Vector2::new((grid.width - 1) as f32, (grid.length - 1) as f32);
+
+        for _ in 0..self.droplets {
+            let mut position = Vector2::new(random.next() * max.x, random.next() * max.y);
+            let mut direction = Vector2::<f32>::zeros();
+            let mut speed = 1.0f32;
+            let mut water = 1.0;
+            let mut sediment = 0.0f32;
+
+            for _ in 0..DROPLET_LIFETIME {
+                let (height, gradient) = height_and_gradient(grid, position);
+
+                direction = direction.scale(INERTIA) - gradient.scale(1.0 - INERTIA);
+                let length = direction.norm();
+                if length <= f32::EPSILON {
+                    break;
+                }
+                direction /= length;
+
+                let new_position = position + direction;
+                if new_position.x < 0.0
+                    || new_position.y < 0.0
+                    || new_position.x > max.x
+                    || new_position.y > max.y
+                {
+                    break;
+                }
+
+                let delta_height = height_and_gradient(grid, new_position).0 - height;
+                let capacity = (-delta_height).max(MIN_SLOPE) * speed * water * SEDIMENT_CAPACITY;
+
+                if delta_height > 0.0 || sediment > capacity {
+                    // Fill the pit we're in or drop excess of the sediment.
+                    let amount = if delta_height > 0.0 {
+                        delta_height.min(sediment)
+                    } else {
+                        (sediment - capacity) * self.deposition_rate
+                    };
+                    sediment -= amount;
+                    deposit(grid, position, amount);
+                } else {
+                    // Never dig deeper than the height difference, otherwise droplet will
+                    // leave holes behind.
+                    let amount = ((capacity - sediment) * self.erosion_rate).min(-delta_height);
+                    sediment += amount;
+                    deposit(grid, position, -amount);
+                }
+
+                speed = (speed * speed - delta_height * GRAVITY).max(0.0).sqrt();
+                water *= 1.0 - self.evaporation;
+                position = new_position;
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Procedural generation of terrain heights.
+
+use crate::terrain::{noise, Grid};
+use rg3d::{
+    core::pool::Handle,
+    scene::{graph::Graph, node::Node},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NoiseKind {
+    /// Smooth rolling hills.
+    Fbm,
+    /// Sharp mountain ridges.
+    Ridged,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoiseParameters {
+    pub kind: NoiseKind,
+    pub seed: u32,
+    pub octaves: usize,
+    /// Frequency of the first octave per meter.
+    pub frequency: f32,
+    /// Maximum height of generated terrain.
+    pub amplitude: f32,
+}
+
+impl Default for NoiseParameters {
+    fn default() -> Self {
+        Self {
+            kind: NoiseKind::Fbm,
+            seed: 0,
+            octaves: 6,
+            frequency: 0.02,
+            amplitude: 20.0,
+        }
+    }
+}
+
+impl NoiseParameters {
+    /// Fills the grid with generated heights, `width` and `length` are physical sizes of the
+    /// terrain which are needed to keep frequency independent of the resolution.
+    pub fn generate(&self, grid: &mut Grid, width: f32, length: f32) {
+        let dx = width / (grid.width - 1).max(1) as f32;
+        let dz = length / (grid.length - 1).max(1) as f32;
+
+        for z in 0..grid.length {
+            for x in 0..grid.width {
+                let px = x as f32 * dx * self.frequency;
+                let pz = z as f32 * dz * self.frequency;
+
+                let value = match self.kind {
+                    NoiseKind::Fbm => noise::fbm(px, pz, self.seed, self.octaves) * 0.5 + 0.5,
+                    NoiseKind::Ridged => noise::ridged(px, pz, self.seed, self.octaves),
+                };
+
+                grid.set(x, z, value * self.amplitude);
+            }
+        }
+    }
+}
+
+/// Generated heights that are shown on a terrain, but not committed yet.
+pub struct TerrainPreview {
+    pub terrain: Handle<Node>,
+    pub original: Vec<Vec<f32>>,
+    pub generated: Vec<Vec<f32>>,
+}
+
+impl TerrainPreview {
+    /// Puts original heights back to the terrain.
+    pub fn discard(self, graph: &mut Graph) {
+        if graph.is_valid_handle(self.terrain) {
+            set_heightmaps(graph, self.terrain, &self.original);
+        }
+    }
+}
+
+pub fn set_heightmaps(graph: &mut Graph, terrain: Handle<Node>, heightmaps: &[Vec<f32>]) {
+    if let Node::Terrain(terrain) = &mut graph[terrain] {
+        for (chunk, heightmap) in terrain.chunks_mut().iter_mut().zip(heightmaps) {
+            chunk.set_heightmap(heightmap.clone());
+        }
+    }
+}
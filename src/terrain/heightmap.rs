@@ -0,0 +1,189 @@
+//! Import and export of terrain heights as 16-bit grayscale PNG or headerless RAW files.
+//! Heights are stored normalized, `HeightRange` defines what heights correspond to the
+//! minimum and maximum values of the file.
+
+use crate::terrain::Grid;
+use image::{ImageBuffer, ImageError, Luma};
+use std::{
+    convert::TryInto,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HeightmapFormat {
+    /// 16-bit grayscale PNG.
+    Png,
+    /// Headerless square array of little-endian `u16`.
+    RawU16,
+    /// Headerless square array of little-endian `f32` in [0; 1] range.
+    RawF32,
+}
+
+impl HeightmapFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_string_lossy().to_lowercase().as_ref() {
+            "png" => Some(Self::Png),
+            "r16" | "raw" => Some(Self::RawU16),
+            "r32" => Some(Self::RawF32),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum HeightmapError {
+    Io(std::io::Error),
+    Image(ImageError),
+    UnsupportedFormat(PathBuf),
+    /// Raw files have no header, so they must contain square array of heights.
+    NotSquare(usize),
+}
+
+impl From<std::io::Error> for HeightmapError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ImageError> for HeightmapError {
+    fn from(e: ImageError) -> Self {
+        Self::Image(e)
+    }
+}
+
+fn format_of(path: &Path) -> Result<HeightmapFormat, HeightmapError> {
+    HeightmapFormat::from_path(path)
+        .ok_or_else(|| HeightmapError::UnsupportedFormat(path.to_owned()))
+}
+
+/// Normalized 0 of a file corresponds to `offset` height, 1 - to `offset + scale`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HeightRange {
+    pub offset: f32,
+    pub scale: f32,
+}
+
+impl HeightRange {
+    /// Range that covers every height of the grid, so nothing is clamped on export.
+    pub fn of_grid(grid: &Grid) -> Self {
+        let (min, max) = grid
+            .values
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), &h| {
+                (min.min(h), max.max(h))
+            });
+
+        if min <= max {
+            Self {
+                offset: min,
+                scale: max - min,
+            }
+        } else {
+            Self {
+                offset: 0.0,
+                scale: 0.0,
+            }
+        }
+    }
+
+    fn normalize(&self, height: f32) -> f32 {
+        if self.scale.abs() > f32::EPSILON {
+            ((height - self.offset) / self.scale).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn denormalize(&self, value: f32) -> f32 {
+        self.offset + value * self.scale
+    }
+}
+
+fn square_side(count: usize) -> Result<usize, HeightmapError> {
+    let side = (count as f64).sqrt().round() as usize;
+    if side > 1 && side * side == count {
+        Ok(side)
+    } else {
+        Err(HeightmapError::NotSquare(count))
+    }
+}
+
+pub fn load(path: &Path, range: HeightRange) -> Result<Grid, HeightmapError> {
+    match format_of(path)? {
+        HeightmapFormat::Png => {
+            let image = image::open(path)?.into_luma16();
+            Ok(Grid {
+                width: image.width() as usize,
+                length: image.height() as usize,
+                values: image
+                    .pixels()
+                    .map(|p| range.denormalize(p.0[0] as f32 / u16::MAX as f32))
+                    .collect(),
+            })
+        }
+        HeightmapFormat::RawU16 => {
+            let bytes = fs::read(path)?;
+            let values = bytes
+                .chunks_exact(2)
+                .map(|b| {
+                    range.denormalize(
+                        u16::from_le_bytes(b.try_into().unwrap()) as f32 / u16::MAX as f32,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let side = square_side(values.len())?;
+            Ok(Grid {
+                width: side,
+                length: side,
+                values,
+            })
+        }
+        HeightmapFormat::RawF32 => {
+            let bytes = fs::read(path)?;
+            let values = bytes
+                .chunks_exact(4)
+                .map(|b| range.denormalize(f32::from_le_bytes(b.try_into().unwrap())))
+                .collect::<Vec<_>>();
+            let side = square_side(values.len())?;
+            Ok(Grid {
+                width: side,
+                length: side,
+                values,
+            })
+        }
+    }
+}
+
+pub fn save(grid: &Grid, path: &Path, range: HeightRange) -> Result<(), HeightmapError> {
+    match format_of(path)? {
+        HeightmapFormat::Png => {
+            let pixels = grid
+                .values
+                .iter()
+                .map(|&h| (range.normalize(h) * u16::MAX as f32) as u16)
+                .collect::<Vec<_>>();
+            // Buffer size always matches the grid size.
+            ImageBuffer::<Luma<u16>, _>::from_raw(grid.width as u32, grid.length as u32, pixels)
+                .unwrap()
+                .save(path)?;
+        }
+        HeightmapFormat::RawU16 => {
+            let mut bytes = Vec::with_capacity(grid.values.len() * 2);
+            for &h in grid.values.iter() {
+                bytes.extend_from_slice(
+                    &((range.normalize(h) * u16::MAX as f32) as u16).to_le_bytes(),
+                );
+            }
+            fs::write(path, bytes)?;
+        }
+        HeightmapFormat::RawF32 => {
+            let mut bytes = Vec::with_capacity(grid.values.len() * 4);
+            for &h in grid.values.iter() {
+                bytes.extend_from_slice(&range.normalize(h).to_le_bytes());
+            }
+            fs::write(path, bytes)?;
+        }
+    }
+    Ok(())
+}
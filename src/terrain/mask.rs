@@ -0,0 +1,118 @@
+//! Import and export of terrain layer masks as 8-bit grayscale PNG. Unlike height maps,
+//! masks of neighbouring chunks do not share border pixels, so stitched mask is just
+//! masks of every chunk placed side by side.
+
+use crate::terrain::Grid;
+use image::{ImageBuffer, ImageError, Luma};
+use rg3d::{resource::texture::TextureKind, scene::terrain::Terrain};
+use std::path::Path;
+
+/// Size of a single chunk mask in pixels together with amount of chunks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MaskLayout {
+    pub width_chunks: usize,
+    pub length_chunks: usize,
+    pub mask_width: usize,
+    pub mask_height: usize,
+}
+
+impl MaskLayout {
+    pub fn of_terrain(terrain: &Terrain) -> Option<Self> {
+        let layer = terrain.layers().first()?;
+        let mask = layer.chunk_masks().first()?;
+        if let TextureKind::Rectangle { width, height } = mask.data_ref().kind() {
+            Some(Self {
+                width_chunks: terrain.width_chunks() as usize,
+                length_chunks: terrain.length_chunks() as usize,
+                mask_width: width as usize,
+                mask_height: height as usize,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn image_width(&self) -> usize {
+        self.width_chunks * self.mask_width
+    }
+
+    pub fn image_height(&self) -> usize {
+        self.length_chunks * self.mask_height
+    }
+}
+
+/// Makes a copy of masks of every chunk of given layer.
+pub fn copy_layer_masks(terrain: &Terrain, layer: usize) -> Vec<Vec<u8>> {
+    terrain.layers()[layer]
+        .chunk_masks()
+        .iter()
+        .map(|mask| mask.data_ref().data().to_vec())
+        .collect()
+}
+
+/// Places masks of every chunk side by side into a single image, row by row.
+pub fn stitch(layout: &MaskLayout, masks: &[Vec<u8>]) -> Vec<u8> {
+    let image_width = layout.image_width();
+    let mut pixels = vec![0; image_width * layout.image_height()];
+
+    for cz in 0..layout.length_chunks {
+        for cx in 0..layout.width_chunks {
+            let mask = &masks[cz * layout.width_chunks + cx];
+            for y in 0..layout.mask_height {
+                let row = (cz * layout.mask_height + y) * image_width + cx * layout.mask_width;
+                pixels[row..(row + layout.mask_width)]
+                    .copy_from_slice(&mask[(y * layout.mask_width)..((y + 1) * layout.mask_width)]);
+            }
+        }
+    }
+
+    pixels
+}
+
+/// Splits stitched image back to per-chunk masks. Image size must match the layout.
+pub fn split(layout: &MaskLayout, pixels: &[u8]) -> Vec<Vec<u8>> {
+    let image_width = layout.image_width();
+    assert_eq!(pixels.len(), image_width * layout.image_height());
+
+    let mut masks = Vec::with_capacity(layout.width_chunks * layout.length_chunks);
+    for cz in 0..layout.length_chunks {
+        for cx in 0..layout.width_chunks {
+            let mut mask = Vec::with_capacity(layout.mask_width * layout.mask_height);
+            for y in 0..layout.mask_height {
+                let row = (cz * layout.mask_height + y) * image_width + cx * layout.mask_width;
+                mask.extend_from_slice(&pixels[row..(row + layout.mask_width)]);
+            }
+            masks.push(mask);
+        }
+    }
+    masks
+}
+
+/// Loads grayscale image and resamples it to the size of stitched mask.
+pub fn load(path: &Path, layout: &MaskLayout) -> Result<Vec<u8>, ImageError> {
+    let image = image::open(path)?.into_luma8();
+
+    let grid = Grid {
+        width: image.width() as usize,
+        length: image.height() as usize,
+        values: image.pixels().map(|p| p.0[0] as f32).collect(),
+    }
+    .resample(layout.image_width(), layout.image_height());
+
+    Ok(grid
+        .values
+        .iter()
+        .map(|v| v.clamp(0.0, 255.0) as u8)
+        .collect())
+}
+
+pub fn save(path: &Path, layout: &MaskLayout, pixels: Vec<u8>) -> Result<(), ImageError> {
+    // Buffer size always matches the layout.
+    ImageBuffer::<Luma<u8>, _>::from_raw(
+        layout.image_width() as u32,
+        layout.image_height() as u32,
+        pixels,
+    )
+    .unwrap()
+    .save(path)
+}
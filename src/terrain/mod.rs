@@ -0,0 +1,181 @@
+//! Editor-side helpers that allow to operate on terrain data as on a single grid instead
+//! of a set of separate chunks.
+
+use rg3d::scene::terrain::Terrain;
+
+pub mod auto_paint;
+pub mod brush;
+pub mod erosion;
+pub mod generator;
+pub mod heightmap;
+pub mod mask;
+pub mod noise;
+pub mod resize;
+
+/// Describes how chunks of a terrain are laid out. Chunks are stored row by row
+/// (X first, then Z) and neighbouring chunks share their border points.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ChunkLayout {
+    pub width_chunks: usize,
+    pub length_chunks: usize,
+    pub chunk_width_points: usize,
+    pub chunk_length_points: usize,
+}
+
+impl ChunkLayout {
+    pub fn of_terrain(terrain: &Terrain) -> Self {
+        let first = terrain
+            .chunks_ref()
+            .first()
+            .expect("Terrain must have at least one chunk!");
+
+        Self {
+            width_chunks: terrain.width_chunks() as usize,
+            length_chunks: terrain.length_chunks() as usize,
+            chunk_width_points: first.width_point_count() as usize,
+            chunk_length_points: first.length_point_count() as usize,
+        }
+    }
+
+    /// Amount of points along X axis of the whole terrain.
+    pub fn grid_width(&self) -> usize {
+        self.width_chunks * (self.chunk_width_points - 1) + 1
+    }
+
+    /// Amount of points along Z axis of the whole terrain.
+    pub fn grid_length(&self) -> usize {
+        self.length_chunks * (self.chunk_length_points - 1) + 1
+    }
+}
+
+/// Dense 2D grid of values, row by row. Used to store stitched height maps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid {
+    pub width: usize,
+    pub length: usize,
+    pub values: Vec<f32>,
+}
+
+impl Grid {
+    pub fn new(width: usize, length: usize) -> Self {
+        Self {
+            width,
+            length,
+            values: vec![0.0; width * length],
+        }
+    }
+
+    /// Stitches height maps of every chunk into a single grid. Shared border points
+    /// are taken from the last chunk that contains them.
+    pub fn from_heightmaps(layout: &ChunkLayout, heightmaps: &[Vec<f32>]) -> Self {
+        let mut grid = Self::new(layout.grid_width(), layout.grid_length());
+
+        for cz in 0..layout.length_chunks {
+            for cx in 0..layout.width_chunks {
+                let heightmap = &heightmaps[cz * layout.width_chunks + cx];
+                for z in 0..layout.chunk_length_points {
+                    for x in 0..layout.chunk_width_points {
+                        grid.set(
+                            cx * (layout.chunk_width_points - 1) + x,
+                            cz * (layout.chunk_length_points - 1) + z,
+                            heightmap[z * layout.chunk_width_points + x],
+                        );
+                    }
+                }
+            }
+        }
+
+        grid
+    }
+
+    pub fn from_terrain(terrain: &Terrain) -> Self {
+        Self::from_heightmaps(
+            &ChunkLayout::of_terrain(terrain),
+            &terrain
+                .chunks_ref()
+                .iter()
+                .map(|c| c.heightmap().to_vec())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Splits the grid back to per-chunk height maps. Grid size must match the layout.
+    pub fn to_heightmaps(&self, layout: &ChunkLayout) -> Vec<Vec<f32>> {
+        assert_eq!(self.width, layout.grid_width());
+        assert_eq!(self.length, layout.grid_length());
+
+        let mut heightmaps = Vec::with_capacity(layout.width_chunks * layout.length_chunks);
+        for cz in 0..layout.length_chunks {
+            for cx in 0..layout.width_chunks {
+                let mut heightmap =
+                    Vec::with_capacity(layout.chunk_width_points * layout.chunk_length_points);
+                for z in 0..layout.chunk_length_points {
+                    for x in 0..layout.chunk_width_points {
+                        heightmap.push(self.get(
+                            cx * (layout.chunk_width_points - 1) + x,
+                            cz * (layout.chunk_length_points - 1) + z,
+                        ));
+                    }
+                }
+                heightmaps.push(heightmap);
+            }
+        }
+        heightmaps
+    }
+
+    pub fn get(&self, x: usize, z: usize) -> f32 {
+        self.values[z * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, z: usize, value: f32) {
+        self.values[z * self.width + x] = value;
+    }
+
+    /// Bilinearly samples the grid at given normalized coordinates.
+    pub fn sample(&self, u: f32, v: f32) -> f32 {
+        let fx = u.clamp(0.0, 1.0) * (self.width - 1) as f32;
+        let fz = v.clamp(0.0, 1.0) * (self.length - 1) as f32;
+
+        let x0 = fx.floor() as usize;
+        let z0 = fz.floor() as usize;
+        let x1 = (x0 + 1).min(self.width - 1);
+        let z1 = (z0 + 1).min(self.length - 1);
+
+        let tx = fx - x0 as f32;
+        let tz = fz - z0 as f32;
+
+        let top = self.get(x0, z0) + (self.get(x1, z0) - self.get(x0, z0)) * tx;
+        let bottom = self.get(x0, z1) + (self.get(x1, z1) - self.get(x0, z1)) * tx;
+
+        top + (bottom - top) * tz
+    }
+
+    /// Creates new grid of given size using bilinear filtering.
+    pub fn resample(&self, width: usize, length: usize) -> Self {
+        if width == self.width && length == self.length {
+            return self.clone();
+        }
+
+        let normalize = |i: usize, count: usize| {
+            if count > 1 {
+                i as f32 / (count - 1) as f32
+            } else {
+                0.0
+            }
+        };
+
+        let mut values = Vec::with_capacity(width * length);
+        for z in 0..length {
+            let v = normalize(z, length);
+            for x in 0..width {
+                values.push(self.sample(normalize(x, width), v));
+            }
+        }
+
+        Self {
+            width,
+            length,
+            values,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Simple deterministic 2D noise used by terrain tools.
+
+fn hash(x: i32, z: i32, seed: u32) -> f32 {
+    let mut h = seed
+        .wrapping_add((x as u32).wrapping_mul(374_761_393))
+        .wrapping_add((z as u32).wrapping_mul(668_265_263));
+    h = (h ^ (h >> 13)).wrapping_mul(1_274_126_177);
+    h ^= h >> 16;
+    (h & 0xFFFF) as f32 / 65535.0 * 2.0 - 1.0
+}
+
+fn smooth_step(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Value noise in [-1; 1] range.
+pub fn value_noise(x: f32, z: f32, seed: u32) -> f32 {
+    let x0 = x.floor();
+    let z0 = z.floor();
+
+    let tx = smooth_step(x - x0);
+    let tz = smooth_step(z - z0);
+
+    let (ix, iz) = (x0 as i32, z0 as i32);
+
+    let a = hash(ix, iz, seed);
+    let b = hash(ix + 1, iz, seed);
+    let c = hash(ix, iz + 1, seed);
+    let d = hash(ix + 1, iz + 1, seed);
+
+    let top = a + (b - a) * tx;
+    let bottom = c + (d - c) * tx;
+
+    top + (bottom - top) * tz
+}
+
+/// Fractal Brownian motion - sum of octaves of value noise, result is in [-1; 1] range.
+pub fn fbm(x: f32, z: f32, seed: u32, octaves: usize) -> f32 {
+    let mut sum = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+    let mut total_amplitude = 0.0;
+    for octave in 0..octaves.max(1) {
+        sum += value_noise(
+            x * frequency,
+            z * frequency,
+            seed.wrapping_add(octave as u32),
+        ) * amplitude;
+        total_amplitude += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    sum / total_amplitude
+}
+
+/// Ridged multifractal noise, produces sharp ridges, result is in [0; 1] range.
+pub fn ridged(x: f32, z: f32, seed: u32, octaves: usize) -> f32 {
+    let mut sum = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+    let mut total_amplitude = 0.0;
+    let mut weight = 1.0;
+    for octave in 0..octaves.max(1) {
+        let n = 1.0
+            - value_noise(
+                x * frequency,
+                z * frequency,
+                seed.wrapping_add(octave as u32),
+            )
+            .abs();
+        let n = n * n * weight;
+        // Ridges on higher octaves are visible only on top of ridges of lower octaves.
+        weight = n.clamp(0.0, 1.0);
+        sum += n * amplitude;
+        total_amplitude += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    sum / total_amplitude
+}
@@ -0,0 +1,143 @@
+//! Rebuilds a terrain with different size and resolution, resampling its heights and layer
+//! masks so existing data is preserved.
+
+use crate::terrain::{
+    mask::{self, MaskLayout},
+    ChunkLayout, Grid,
+};
+use rg3d::scene::{
+    base::BaseBuilder,
+    node::Node,
+    terrain::{LayerDefinition, Terrain, TerrainBuilder},
+};
+
+/// Size of a terrain in world units together with its resolution. Resolutions are
+/// amount of points (or texels) per world unit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TerrainSize {
+    pub width: f32,
+    pub length: f32,
+    pub width_chunks: usize,
+    pub length_chunks: usize,
+    pub height_map_resolution: f32,
+    pub mask_resolution: f32,
+}
+
+impl TerrainSize {
+    pub fn of_terrain(terrain: &Terrain) -> Self {
+        let layout = ChunkLayout::of_terrain(terrain);
+        let chunk_width = terrain.width() / layout.width_chunks as f32;
+
+        Self {
+            width: terrain.width(),
+            length: terrain.length(),
+            width_chunks: layout.width_chunks,
+            length_chunks: layout.length_chunks,
+            height_map_resolution: layout.chunk_width_points as f32 / chunk_width,
+            mask_resolution: MaskLayout::of_terrain(terrain)
+                .map_or(16.0, |layout| layout.mask_width as f32 / chunk_width),
+        }
+    }
+
+    /// Checks that every chunk gets at least 2 height map points and 1 mask pixel along
+    /// each axis. Engine truncates `chunk size * resolution`, so smaller values give empty
+    /// chunks that can't be resampled.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width <= 0.0 || self.length <= 0.0 {
+            return Err("Terrain size must be positive.".to_owned());
+        }
+
+        let chunk_width = self.width / self.width_chunks.max(1) as f32;
+        let chunk_length = self.length / self.length_chunks.max(1) as f32;
+        let min_side = chunk_width.min(chunk_length);
+
+        for &(name, resolution) in &[
+            ("Height map resolution", self.height_map_resolution),
+            ("Mask resolution", self.mask_resolution),
+        ] {
+            if (min_side * resolution) < 1.0 {
+                return Err(format!(
+                    "{} is too low for chunks of {:.2}x{:.2} units, it must be at least {:.3}.",
+                    name,
+                    chunk_width,
+                    chunk_length,
+                    1.0 / min_side
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates new terrain node of given size. Base properties and layers are copied from
+/// the source terrain, heights and masks are bilinearly resampled to fit new resolution.
+pub fn resize(terrain: &Terrain, size: &TerrainSize) -> Node {
+    let mut node = TerrainBuilder::new(BaseBuilder::new())
+        .with_width(size.width)
+        .with_length(size.length)
+        .with_width_chunks(size.width_chunks)
+        .with_length_chunks(size.length_chunks)
+        .with_height_map_resolution(size.height_map_resolution)
+        .with_mask_resolution(size.mask_resolution)
+        .with_layers(
+            terrain
+                .layers()
+                .iter()
+                .map(|layer| LayerDefinition {
+                    material: layer.material.clone(),
+                    mask_property_name: layer.mask_property_name.clone(),
+                })
+                .collect(),
+        )
+        .build_node();
+
+    let resized = node.as_terrain_mut();
+    // Copy whole base (tag, lifetime, lod group, etc.), hierarchy is restored by the command
+    // that replaces the terrain.
+    **resized = (**terrain).raw_copy();
+    resized.set_decal_layer_index(terrain.decal_layer_index());
+
+    let layout = ChunkLayout::of_terrain(resized);
+    let heightmaps = Grid::from_terrain(terrain)
+        .resample(layout.grid_width(), layout.grid_length())
+        .to_heightmaps(&layout);
+    for (chunk, heightmap) in resized.chunks_mut().iter_mut().zip(heightmaps) {
+        chunk.set_heightmap(heightmap);
+    }
+
+    if let (Some(old_layout), Some(new_layout)) = (
+        MaskLayout::of_terrain(terrain),
+        MaskLayout::of_terrain(resized),
+    ) {
+        for (index, layer) in resized.layers().iter().enumerate() {
+            let pixels = mask::stitch(&old_layout, &mask::copy_layer_masks(terrain, index));
+
+            let grid = Grid {
+                width: old_layout.image_width(),
+                length: old_layout.image_height(),
+                values: pixels.iter().map(|&p| p as f32).collect(),
+            }
+            .resample(new_layout.image_width(), new_layout.image_height());
+
+            let masks = mask::split(
+                &new_layout,
+                &grid
+                    .values
+                    .iter()
+                    .map(|v| v.clamp(0.0, 255.0) as u8)
+                    .collect::<Vec<_>>(),
+            );
+
+            for (chunk_mask, new_mask) in layer.chunk_masks().iter().zip(masks) {
+                chunk_mask
+                    .data_ref()
+                    .modify()
+                    .data_mut()
+                    .copy_from_slice(&new_mask);
+            }
+        }
+    }
+
+    node
+}
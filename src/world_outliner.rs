@@ -5,11 +5,15 @@ use crate::{
     scene::{
         commands::{
             graph::{LinkNodesCommand, SetVisibleCommand},
-            make_delete_selection_command, ChangeSelectionCommand,
+            make_delete_selection_command,
+            sound::SetSoundSourceNodeCommand,
+            ChangeSelectionCommand,
         },
         EditorScene, GraphSelection, Selection,
     },
-    send_sync_message, GameEngine, Message,
+    send_sync_message,
+    sound::{SoundItem, SoundSelection},
+    GameEngine, Message,
 };
 use rg3d::gui::message::UiMessage;
 use rg3d::gui::tree::TreeRoot;
@@ -41,6 +45,7 @@ use rg3d::{
         Control, HorizontalAlignment, NodeHandleMapping, Orientation, Thickness, VerticalAlignment,
     },
     scene::node::Node,
+    sound::source::SoundSource,
 };
 use std::any::Any;
 use std::{
@@ -124,6 +129,7 @@ impl ItemContextMenu {
                         graph_selection,
                         editor_scene.scene,
                         &editor_scene.physics,
+                        &editor_scene.sound_binder,
                         engine,
                     );
                 }
@@ -155,6 +161,8 @@ pub struct SceneItem {
     tree: Tree,
     text_name: Handle<UiNode>,
     node: Handle<Node>,
+    /// Sound source bound to parent node, such item has no node.
+    sound_source: Handle<SoundSource>,
     visibility_toggle: Handle<UiNode>,
     sender: Sender<Message>,
     visibility: bool,
@@ -266,12 +274,15 @@ impl Control for SceneItem {
                         }
                         SceneItemMessage::Name(name) => {
                             if message.destination() == self.handle() {
-                                let name = format!(
-                                    "{} ({}:{})",
-                                    name,
-                                    self.node.index(),
-                                    self.node.generation()
-                                );
+                                let name = if self.sound_source.is_some() {
+                                    make_item_name(
+                                        name,
+                                        self.sound_source.index(),
+                                        self.sound_source.generation(),
+                                    )
+                                } else {
+                                    make_item_name(name, self.node.index(), self.node.generation())
+                                };
 
                                 ui.send_message(TextMessage::text(
                                     self.text_name,
@@ -305,9 +316,14 @@ impl Control for SceneItem {
     }
 }
 
+fn make_item_name(name: &str, index: u32, generation: u32) -> String {
+    format!("{} ({}:{})", name, index, generation)
+}
+
 #[derive(Default)]
 pub struct SceneItemBuilder {
     node: Handle<Node>,
+    sound_source: Handle<SoundSource>,
     name: String,
     visibility: bool,
     icon: Option<SharedTexture>,
@@ -318,6 +334,7 @@ impl SceneItemBuilder {
     pub fn new() -> Self {
         Self {
             node: Default::default(),
+            sound_source: Default::default(),
             name: Default::default(),
             visibility: true,
             icon: None,
@@ -330,6 +347,11 @@ impl SceneItemBuilder {
         self
     }
 
+    pub fn with_sound_source(mut self, sound_source: Handle<SoundSource>) -> Self {
+        self.sound_source = sound_source;
+        self
+    }
+
     pub fn with_name(mut self, name: String) -> Self {
         self.name = name;
         self
@@ -355,7 +377,7 @@ impl SceneItemBuilder {
         ctx: &mut BuildContext,
         sender: Sender<Message>,
         resource_manager: ResourceManager,
-        node: &Node,
+        node: Option<&Node>,
     ) -> Handle<UiNode> {
         let visible_texture = load_image(include_bytes!("../resources/embed/visible.png"));
 
@@ -388,27 +410,34 @@ impl SceneItemBuilder {
                     .with_child({
                         text_name = TextBuilder::new(
                             WidgetBuilder::new()
-                                .with_foreground(if node.resource().is_some() {
-                                    Brush::Solid(Color::opaque(160, 160, 200))
-                                } else {
-                                    Brush::Solid(rg3d::gui::COLOR_FOREGROUND)
-                                })
+                                .with_foreground(
+                                    if node.map_or(false, |n| n.resource().is_some()) {
+                                        Brush::Solid(Color::opaque(160, 160, 200))
+                                    } else {
+                                        Brush::Solid(rg3d::gui::COLOR_FOREGROUND)
+                                    },
+                                )
                                 .with_margin(Thickness::uniform(1.0))
                                 .on_column(1)
                                 .with_vertical_alignment(VerticalAlignment::Center),
                         )
-                        .with_text(format!(
-                            "{} ({}:{})",
-                            self.name,
-                            self.node.index(),
-                            self.node.generation()
-                        ))
+                        .with_text(if self.sound_source.is_some() {
+                            make_item_name(
+                                &self.name,
+                                self.sound_source.index(),
+                                self.sound_source.generation(),
+                            )
+                        } else {
+                            make_item_name(&self.name, self.node.index(), self.node.generation())
+                        })
                         .build(ctx);
                         text_name
                     })
                     .with_child({
                         visibility_toggle = ButtonBuilder::new(
                             WidgetBuilder::new()
+                                // Sound sources have no visibility.
+                                .with_visibility(self.sound_source.is_none())
                                 .with_margin(Thickness::uniform(1.0))
                                 .with_width(22.0)
                                 .with_height(16.0)
@@ -437,6 +466,7 @@ impl SceneItemBuilder {
         let item = SceneItem {
             tree,
             node: self.node,
+            sound_source: self.sound_source,
             visibility_toggle,
             sender,
             visibility: self.visibility,
@@ -467,7 +497,23 @@ fn make_tree(
         .with_visibility(node.visibility())
         .with_icon(icon)
         .with_context_menu(context_menu)
-        .build(ctx, sender, resource_manager, node)
+        .build(ctx, sender, resource_manager, Some(node))
+}
+
+fn make_sound_tree(
+    name: String,
+    handle: Handle<SoundSource>,
+    ctx: &mut BuildContext,
+    sender: Sender<Message>,
+    resource_manager: ResourceManager,
+) -> Handle<UiNode> {
+    SceneItemBuilder::new()
+        .with_name(name)
+        .with_sound_source(handle)
+        .with_icon(load_image(include_bytes!(
+            "../resources/embed/sound_source.png"
+        )))
+        .build(ctx, sender, resource_manager, None)
 }
 
 fn tree_node(ui: &UserInterface, tree: Handle<UiNode>) -> Handle<Node> {
@@ -477,6 +523,26 @@ fn tree_node(ui: &UserInterface, tree: Handle<UiNode>) -> Handle<Node> {
     unreachable!()
 }
 
+fn tree_sound_source(ui: &UserInterface, tree: Handle<UiNode>) -> Handle<SoundSource> {
+    if let Some(item) = ui.node(tree).cast::<SceneItem>() {
+        return item.sound_source;
+    }
+    unreachable!()
+}
+
+/// Returns sound source of an item that is dragged either within the outliner or from sound
+/// panel.
+fn dragged_sound_source(ui: &UserInterface, dragged: Handle<UiNode>) -> Handle<SoundSource> {
+    let node = ui.node(dragged);
+    if let Some(item) = node.cast::<SceneItem>() {
+        item.sound_source
+    } else if let Some(item) = node.cast::<SoundItem>() {
+        item.sound_source()
+    } else {
+        Handle::NONE
+    }
+}
+
 fn colorize(tree: Handle<UiNode>, ui: &UserInterface, index: &mut usize) {
     let node = ui.node(tree);
 
@@ -612,6 +678,7 @@ impl WorldOutliner {
 
         let scene = &mut engine.scenes[editor_scene.scene];
         let graph = &mut scene.graph;
+        let sound_state = scene.sound_context.state();
         let ui = &mut engine.user_interface;
 
         let mut selected_items = Vec::new();
@@ -635,7 +702,14 @@ impl WorldOutliner {
                         child_count += 1;
                     }
                 }
-                let items = item.tree.items().to_vec();
+                // Sound sources bound to the node are shown as its children too, they are
+                // synced separately.
+                let (sound_items, items): (Vec<_>, Vec<_>) = item
+                    .tree
+                    .items()
+                    .iter()
+                    .cloned()
+                    .partition(|&i| tree_sound_source(ui, i).is_some());
                 if child_count < items.len() {
                     for &item in items.iter() {
                         let child_node = tree_node(ui, item);
@@ -652,7 +726,7 @@ impl WorldOutliner {
                             self.stack.push((item, child_node));
                         }
                     }
-                } else if child_count > item.tree.items().len() {
+                } else if child_count > items.len() {
                     for &child_handle in node.children() {
                         // Hide all editor nodes.
                         if child_handle == editor_scene.root {
@@ -698,6 +772,41 @@ impl WorldOutliner {
                         self.stack.push((tree, child));
                     }
                 }
+
+                for &sound_item in sound_items.iter() {
+                    let source = tree_sound_source(ui, sound_item);
+                    if !sound_state.is_valid_handle(source)
+                        || editor_scene.sound_binder.node_of(source) != node_handle
+                    {
+                        send_sync_message(
+                            ui,
+                            TreeMessage::remove_item(
+                                tree_handle,
+                                MessageDirection::ToWidget,
+                                sound_item,
+                            ),
+                        );
+                    }
+                }
+                for source in editor_scene.sound_binder.sources_of(node_handle) {
+                    if sound_state.is_valid_handle(source)
+                        && sound_items
+                            .iter()
+                            .all(|&i| tree_sound_source(ui, i) != source)
+                    {
+                        let tree = make_sound_tree(
+                            sound_state.source(source).name_owned(),
+                            source,
+                            &mut ui.build_ctx(),
+                            self.sender.clone(),
+                            engine.resource_manager.clone(),
+                        );
+                        send_sync_message(
+                            ui,
+                            TreeMessage::add_item(tree_handle, MessageDirection::ToWidget, tree),
+                        );
+                    }
+                }
             } else if let Some(root) = ui_node.cast::<TreeRoot>() {
                 if root.items().is_empty() {
                     let tree = make_tree(
@@ -773,6 +882,9 @@ impl WorldOutliner {
                     );
                     send_sync_message(ui, SceneItemMessage::name(handle, node.name().to_owned()));
                     stack.extend_from_slice(item.tree.items());
+                } else if sound_state.is_valid_handle(item.sound_source) {
+                    let name = sound_state.source(item.sound_source).name_owned();
+                    send_sync_message(ui, SceneItemMessage::name(handle, name));
                 }
             } else if let Some(root) = ui_node.cast::<TreeRoot>() {
                 stack.extend_from_slice(root.items())
@@ -812,12 +924,24 @@ impl WorldOutliner {
                     && message.direction() == MessageDirection::FromWidget
                 {
                     if let TreeRootMessage::Selected(selection) = msg {
-                        let new_selection = Selection::Graph(GraphSelection::from_list(
-                            selection
-                                .iter()
-                                .map(|&h| self.map_tree_to_node(h, &engine.user_interface))
-                                .collect(),
-                        ));
+                        let ui = &engine.user_interface;
+                        let sound_sources = selection
+                            .iter()
+                            .map(|&h| tree_sound_source(ui, h))
+                            .filter(|h| h.is_some())
+                            .collect::<Vec<_>>();
+                        // Bound sound sources are selected as sound, so they can be edited
+                        // the same way as in sound panel.
+                        let new_selection = if !sound_sources.is_empty() {
+                            Selection::Sound(SoundSelection::new(sound_sources))
+                        } else {
+                            Selection::Graph(GraphSelection::from_list(
+                                selection
+                                    .iter()
+                                    .map(|&h| self.map_tree_to_node(h, ui))
+                                    .collect(),
+                            ))
+                        };
                         if new_selection != editor_scene.selection {
                             self.sender
                                 .send(Message::do_scene_command(ChangeSelectionCommand::new(
@@ -830,7 +954,24 @@ impl WorldOutliner {
                 }
             }
             &UiMessageData::Widget(WidgetMessage::Drop(node)) => {
-                if engine.user_interface.is_node_child_of(node, self.root)
+                let sound_source = dragged_sound_source(&engine.user_interface, node);
+                if sound_source.is_some()
+                    && engine
+                        .user_interface
+                        .is_node_child_of(message.destination(), self.root)
+                {
+                    let parent =
+                        self.map_tree_to_node(message.destination(), &engine.user_interface);
+                    if parent.is_some() && editor_scene.sound_binder.node_of(sound_source) != parent
+                    {
+                        self.sender
+                            .send(Message::do_scene_command(SetSoundSourceNodeCommand::new(
+                                sound_source,
+                                parent,
+                            )))
+                            .unwrap();
+                    }
+                } else if engine.user_interface.is_node_child_of(node, self.root)
                     && engine
                         .user_interface
                         .is_node_child_of(message.destination(), self.root)